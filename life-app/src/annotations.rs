@@ -0,0 +1,112 @@
+// Annotations
+//
+// Text labels pinned to board cells, each optionally with an arrow to
+// another cell, for documenting what the parts of a pattern do ("gun",
+// "eater", "this glider gets reflected"). The annotate tool places them:
+// press on a cell to pin the label there, drag to give it an arrow. They are
+// drawn over the board, follow the view, and are saved with the session.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Language;
+use crate::viewport::Viewport;
+
+/// A label on the board.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    /// The cell the label is pinned to.
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+    /// The cell the label's arrow points at, if it has one.
+    pub arrow: Option<(usize, usize)>,
+}
+
+/// Labels and arrows laid over the viewport of the board in `slot`. Labels
+/// pinned outside the view are hidden; their arrows are clipped at its edge.
+#[component]
+pub fn AnnotationLayer(annotations: Vec<Annotation>, viewport: Viewport, cell_size: usize, slot: u64) -> Element {
+    let scale = cell_size as f64;
+    // Centre of a cell, relative to the view's corner.
+    let center = move |row: usize, col: usize| {
+        ((col as f64 - viewport.col as f64 + 0.5) * scale, (row as f64 - viewport.row as f64 + 0.5) * scale)
+    };
+    let visible = move |row: usize, col: usize| {
+        (viewport.row..viewport.row + viewport.rows).contains(&row) && (viewport.col..viewport.col + viewport.cols).contains(&col)
+    };
+
+    rsx! {
+        svg {
+            class: "annotations",
+            width: viewport.cols * cell_size,
+            height: viewport.rows * cell_size,
+            defs {
+                marker {
+                    id: "annotation-arrow-{slot}",
+                    "viewBox": "0 0 10 10",
+                    "refX": 9,
+                    "refY": 5,
+                    "markerWidth": 6,
+                    "markerHeight": 6,
+                    orient: "auto-start-reverse",
+                    path { d: "M 0 0 L 10 5 L 0 10 z", style: "fill: var(--gol-annotation)" }
+                }
+            }
+            for (index, note) in annotations.into_iter().enumerate() {
+                {
+                    let (x, y) = center(note.row, note.col);
+                    visible(note.row, note.col).then(|| rsx! {
+                        g { key: "{index}",
+                            if let Some((row, col)) = note.arrow {
+                                {
+                                    let (to_x, to_y) = center(row, col);
+                                    rsx! {
+                                        line {
+                                            x1: x,
+                                            y1: y,
+                                            x2: to_x,
+                                            y2: to_y,
+                                            style: "stroke: var(--gol-annotation)",
+                                            stroke_width: 2,
+                                            "marker-end": "url(#annotation-arrow-{slot})",
+                                        }
+                                    }
+                                }
+                            }
+                            circle { cx: x, cy: y, r: scale / 2.0 + 1.0, fill: "none", style: "stroke: var(--gol-annotation)" }
+                            text { x: x + scale, y: y - scale, "{note.text}" }
+                        }
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Every label on the board, each with a button to remove it, and one to
+/// clear them all. Shows nothing while there are none.
+#[component]
+pub fn AnnotationList(mut annotations: Signal<Vec<Annotation>>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    rsx! {
+        if !annotations.read().is_empty() {
+            div { class: "controls",
+                "{text.annotations} "
+                for (index, note) in annotations().into_iter().enumerate() {
+                    span { class: "bookmark",
+                        {(text.annotation_label)(&note.text, note.row + 1, note.col + 1)}
+                        button {
+                            aria_label: text.remove_annotation,
+                            onclick: move |_| {
+                                annotations.write().remove(index);
+                            },
+                            "×"
+                        }
+                    }
+                }
+                button { onclick: move |_| annotations.write().clear(), "{text.clear_annotations}" }
+            }
+        }
+    }
+}
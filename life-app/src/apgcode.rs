@@ -0,0 +1,180 @@
+// apgcodes
+//
+// Catagolue's compact object names, e.g. `xs4_33` (block), `xp2_7`
+// (blinker), `xq4_153` (glider):
+// * **Prefix** – `xs<population>` still life, `xp<period>` oscillator,
+//   `xq<period>` spaceship.
+// * **Body** – extended Wechsler format: the object is cut into strips five
+//   rows tall, each column of a strip becomes one base-32 digit (top row =
+//   lowest bit), strips are separated by `z`, and runs of empty columns are
+//   shortened (`w` = 2, `x` = 3, `y?` = 4 to 39).
+//
+// The canonical code is the shortest (then alphabetically first) over every
+// phase and orientation.
+
+use crate::census::{normalize, orientations, Kind, Object};
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+use life_engine::sparse::SparsePlane;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+/// Rows per Wechsler strip.
+const STRIP: i32 = 5;
+
+/// Canonical apgcode of an object; `None` for unstable ones.
+pub fn encode(object: &Object, rule: Rule) -> Option<String> {
+    let (prefix, period) = match object.kind {
+        Kind::StillLife => (format!("xs{}", object.cells.len()), 1),
+        Kind::Oscillator { period } => (format!("xp{period}"), period),
+        Kind::Spaceship { period, .. } => (format!("xq{period}"), period),
+        Kind::Unstable => return None,
+    };
+    let mut plane = SparsePlane::new(object.cells.iter().copied(), rule);
+    let mut best: Option<String> = None;
+    for _ in 0..period {
+        let phase: Vec<_> = plane.cells().collect();
+        for shape in orientations(&phase) {
+            let body = wechsler(&shape);
+            if best.as_ref().is_none_or(|best| (body.len(), &body) < (best.len(), best)) {
+                best = Some(body);
+            }
+        }
+        plane.step();
+    }
+    Some(format!("{prefix}_{}", best?))
+}
+
+/// Catagolue page for `code` under `rule`.
+pub fn catagolue_url(code: &str, rule: Rule) -> String {
+    let rule = rule.to_string().to_lowercase().replace('/', "");
+    format!("https://catagolue.hatsya.com/object/{code}/{rule}")
+}
+
+/// Extended Wechsler body of a shape.
+fn wechsler(cells: &[(i32, i32)]) -> String {
+    let shape = normalize(cells);
+    let (height, width) = shape.iter().fold((0, 0), |(h, w), &(r, c)| (h.max(r + 1), w.max(c + 1)));
+    let strips = (height + STRIP - 1) / STRIP;
+    let mut columns = vec![vec![0u8; width as usize]; strips as usize];
+    for &(row, col) in &shape {
+        columns[(row / STRIP) as usize][col as usize] |= 1 << (row % STRIP);
+    }
+    columns.iter().map(|strip| compress(strip)).collect::<Vec<_>>().join("z")
+}
+
+/// One strip's column values → digits, with runs of empty columns shortened
+/// and trailing ones dropped.
+fn compress(strip: &[u8]) -> String {
+    let end = strip.iter().rposition(|&column| column != 0).map_or(0, |last| last + 1);
+    let mut out = String::new();
+    let mut zeros = 0;
+    let flush = |out: &mut String, mut zeros: usize| {
+        while zeros > 0 {
+            let run = zeros.min(39);
+            match run {
+                1 => out.push('0'),
+                2 => out.push('w'),
+                3 => out.push('x'),
+                _ => {
+                    out.push('y');
+                    out.push(DIGITS[run - 4] as char);
+                }
+            }
+            zeros -= run;
+        }
+    };
+    for &column in &strip[..end] {
+        if column == 0 {
+            zeros += 1;
+            continue;
+        }
+        flush(&mut out, zeros);
+        zeros = 0;
+        out.push(DIGITS[column as usize] as char);
+    }
+    out
+}
+
+/// Decode an apgcode back into cells. The prefix is checked but only the
+/// body matters for the shape.
+pub fn decode(code: &str) -> Result<Pattern, String> {
+    let code = code.trim();
+    let (prefix, body) = code.split_once('_').ok_or_else(|| format!("`{code}` has no `_` after the prefix"))?;
+    let valid_prefix = ["xs", "xp", "xq"]
+        .iter()
+        .any(|tag| prefix.strip_prefix(tag).is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())));
+    if !valid_prefix {
+        return Err(format!("`{prefix}` is not an xs/xp/xq prefix"));
+    }
+
+    let digit = |c: char| DIGITS.iter().position(|&d| d as char == c);
+    let mut cells = Vec::new();
+    let (mut strip, mut col) = (0, 0);
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            'z' => (strip, col) = (strip + 1, 0),
+            'w' => col += 2,
+            'x' => col += 3,
+            'y' => {
+                let run = chars.next().and_then(digit).ok_or("`y` must be followed by a digit or letter")?;
+                col += 4 + run;
+            }
+            c => {
+                let value = digit(c).filter(|&v| v < 32).ok_or_else(|| format!("unexpected `{c}` in apgcode"))?;
+                for bit in 0..STRIP as usize {
+                    if value & 1 << bit != 0 {
+                        cells.push((strip * STRIP as usize + bit, col));
+                    }
+                }
+                col += 1;
+            }
+        }
+    }
+
+    let height = cells.iter().map(|&(r, _)| r + 1).max().unwrap_or(0);
+    let width = cells.iter().map(|&(_, c)| c + 1).max().unwrap_or(0);
+    let mut grid = vec![false; width * height];
+    for (row, col) in cells {
+        grid[row * width + col] = true;
+    }
+    Ok(Pattern { name: Some(code.to_string()), ..Pattern::new(width, height, grid) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_known_objects() {
+        let glider = decode("xq4_153").unwrap();
+        assert_eq!((glider.width, glider.height), (3, 3));
+        assert_eq!(glider.cells.iter().filter(|&&alive| alive).count(), 5);
+        let block = decode("xs4_33").unwrap();
+        assert_eq!(block.cells, [true; 4]);
+        assert!(decode("xz4_33").is_err());
+        assert!(decode("xs4_3!").is_err());
+    }
+
+    #[test]
+    fn bodies_round_trip() {
+        for code in ["xs4_33", "xp2_7", "xq4_153", "xs6_696", "xp15_4r4z4r4", "xs16_g88m952zd54c", "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"] {
+            let pattern = decode(code).unwrap();
+            let cells: Vec<(i32, i32)> = (0..pattern.cells.len())
+                .filter(|&idx| pattern.cells[idx])
+                .map(|idx| ((idx / pattern.width) as i32, (idx % pattern.width) as i32))
+                .collect();
+            let (_, body) = code.split_once('_').unwrap();
+            assert_eq!(wechsler(&cells), body, "{code}");
+        }
+    }
+
+    #[test]
+    fn encodes_the_canonical_phase_and_orientation() {
+        // A blinker found standing up is still `xp2_7`.
+        let object = Object { name: None, kind: Kind::Oscillator { period: 2 }, cells: vec![(4, 9), (5, 9), (6, 9)] };
+        assert_eq!(encode(&object, Rule::CONWAY).as_deref(), Some("xp2_7"));
+        let object = Object { name: None, kind: Kind::Unstable, cells: vec![(0, 0)] };
+        assert_eq!(encode(&object, Rule::CONWAY), None);
+    }
+}
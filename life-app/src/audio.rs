@@ -0,0 +1,315 @@
+// Audio sonification
+//
+// Board activity drives a tiny two-voice synth:
+// * **Drone** – pitch follows population density (110 Hz empty → 440 Hz half full).
+// * **Churn** – loudness follows births + deaths, pitch leans up when births
+//   dominate and down when deaths do.
+// * **Sequencer** – optional "Life sequencer": every live cell of one row
+//   plucks a note of a pentatonic scale each generation.
+//
+// * **Web**→ Web Audio (`AudioContext` + two oscillators)
+// * **Native**→ the same voices mixed in software and played through `cpal`,
+//   with the `native-audio` feature. Without it, or with no output device,
+//   there's no sound and the toggle says so.
+
+/// Whether this build can make any sound at all.
+pub const AVAILABLE: bool = cfg!(any(target_arch = "wasm32", feature = "native-audio"));
+
+/// Pentatonic major scale, as semitone offsets from the root.
+const SCALE: [usize; 5] = [0, 2, 4, 7, 9];
+/// How many octaves the sequencer row is stretched across.
+const OCTAVES: usize = 3;
+/// Root of the scale (middle C).
+const ROOT_HZ: f32 = 261.63;
+/// Upper bound on notes per generation, so a busy row doesn't turn to noise.
+const MAX_NOTES: usize = 8;
+
+/// Ambient soundtrack tied to the simulation. Starts muted, because browsers
+/// only allow audio to start from a user gesture.
+#[derive(Default)]
+pub struct Sonifier {
+    synth: Option<backend::Synth>,
+}
+
+impl Sonifier {
+    pub fn is_muted(&self) -> bool {
+        self.synth.is_none()
+    }
+
+    /// Mute (tear down the audio graph) or unmute (build it lazily). Stays
+    /// muted if there's no audio to build.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.synth = if muted { None } else { backend::Synth::new() };
+    }
+
+    /// Feed the stats of the generation that was just computed.
+    pub fn update(&self, population: usize, births: usize, deaths: usize, total: usize) {
+        let Some(synth) = &self.synth else { return };
+        let total = total.max(1) as f32;
+
+        let density = population as f32 / total;
+        let drone_hz = 110.0 * 2f32.powf(density.min(0.5) * 4.0);
+
+        let changes = (births + deaths) as f32;
+        let churn_level = (changes / total * 20.0).min(1.0) * 0.15;
+        let birth_share = if changes > 0.0 { births as f32 / changes } else { 0.5 };
+        let churn_hz = 440.0 + birth_share * 440.0;
+
+        synth.set_voices(drone_hz, 0.08, churn_hz, churn_level);
+    }
+
+    /// Pluck one note per live cell of `row`; columns map left→right onto the
+    /// scale, low to high. Each note rings for `note_secs`.
+    pub fn play_row(&self, row: &[bool], note_secs: f64) {
+        let Some(synth) = &self.synth else { return };
+        let steps = SCALE.len() * OCTAVES;
+
+        let mut notes: Vec<usize> = row
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(|(col, _)| col * steps / row.len())
+            .collect();
+        notes.dedup();
+
+        // Thin out evenly instead of keeping only the lowest notes.
+        let stride = notes.len().div_ceil(MAX_NOTES).max(1);
+        for step in notes.into_iter().step_by(stride) {
+            synth.pluck(note_hz(step), note_secs);
+        }
+    }
+}
+
+/// Frequency of the `step`-th note of the scale, counting across octaves.
+fn note_hz(step: usize) -> f32 {
+    let semitones = 12 * (step / SCALE.len()) + SCALE[step % SCALE.len()];
+    ROOT_HZ * 2f32.powf(semitones as f32 / 12.0)
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use web_sys::{AudioContext, GainNode, OscillatorNode, OscillatorType};
+
+    /// How quickly parameters glide to a new target, in seconds.
+    const GLIDE: f64 = 0.08;
+
+    pub struct Synth {
+        ctx: AudioContext,
+        drone: OscillatorNode,
+        drone_gain: GainNode,
+        churn: OscillatorNode,
+        churn_gain: GainNode,
+    }
+
+    impl Synth {
+        pub fn new() -> Option<Self> {
+            let ctx = AudioContext::new().ok()?;
+            let (drone, drone_gain) = voice(&ctx, OscillatorType::Sine)?;
+            let (churn, churn_gain) = voice(&ctx, OscillatorType::Triangle)?;
+            let _ = ctx.resume();
+            Some(Self { ctx, drone, drone_gain, churn, churn_gain })
+        }
+
+        pub fn set_voices(&self, drone_hz: f32, drone_level: f32, churn_hz: f32, churn_level: f32) {
+            let now = self.ctx.current_time();
+            let _ = self.drone.frequency().set_target_at_time(drone_hz, now, GLIDE);
+            let _ = self.drone_gain.gain().set_target_at_time(drone_level, now, GLIDE);
+            let _ = self.churn.frequency().set_target_at_time(churn_hz, now, GLIDE);
+            let _ = self.churn_gain.gain().set_target_at_time(churn_level, now, GLIDE);
+        }
+
+        /// Fire-and-forget note with a fast exponential decay.
+        pub fn pluck(&self, hz: f32, secs: f64) {
+            let (Ok(osc), Ok(env)) = (self.ctx.create_oscillator(), self.ctx.create_gain()) else {
+                return;
+            };
+            let now = self.ctx.current_time();
+            osc.set_type(OscillatorType::Triangle);
+            osc.frequency().set_value(hz);
+            let _ = env.gain().set_value_at_time(0.06, now);
+            let _ = env.gain().exponential_ramp_to_value_at_time(0.0001, now + secs);
+            let _ = osc.connect_with_audio_node(&env);
+            let _ = env.connect_with_audio_node(&self.ctx.destination());
+            let _ = osc.start_with_when(now);
+            let _ = osc.stop_with_when(now + secs);
+        }
+    }
+
+    impl Drop for Synth {
+        fn drop(&mut self) {
+            let _ = self.ctx.close();
+        }
+    }
+
+    /// One oscillator routed through its own gain node into the speakers.
+    fn voice(ctx: &AudioContext, kind: OscillatorType) -> Option<(OscillatorNode, GainNode)> {
+        let osc = ctx.create_oscillator().ok()?;
+        let gain = ctx.create_gain().ok()?;
+        osc.set_type(kind);
+        gain.gain().set_value(0.0);
+        osc.connect_with_audio_node(&gain).ok()?;
+        gain.connect_with_audio_node(&ctx.destination()).ok()?;
+        osc.start().ok()?;
+        Some((osc, gain))
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "native-audio"))]
+mod backend {
+    use std::f32::consts::TAU;
+    use std::sync::{Arc, Mutex};
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, SampleFormat, SizedSample, Stream, StreamConfig};
+
+    /// How quickly parameters glide to a new target, in seconds.
+    const GLIDE: f32 = 0.08;
+    /// Loudness of a pluck as it starts and as it is cut off.
+    const PLUCK_LEVEL: f32 = 0.06;
+    const PLUCK_END: f32 = 0.0001;
+    /// Plucks ringing at once; more are dropped rather than pile up.
+    const MAX_PLUCKS: usize = 64;
+
+    /// A held oscillator gliding towards its target pitch and loudness.
+    struct Voice {
+        hz: f32,
+        level: f32,
+        target: (f32, f32),
+        phase: f32,
+    }
+
+    impl Voice {
+        fn new() -> Self {
+            Self { hz: 440.0, level: 0.0, target: (440.0, 0.0), phase: 0.0 }
+        }
+
+        /// Advance one sample, `glide` of the way to the target, returning
+        /// the phase `0..1` and the level.
+        fn advance(&mut self, rate: f32, glide: f32) -> (f32, f32) {
+            self.hz += (self.target.0 - self.hz) * glide;
+            self.level += (self.target.1 - self.level) * glide;
+            self.phase = (self.phase + self.hz / rate).fract();
+            (self.phase, self.level)
+        }
+    }
+
+    /// A decaying note, scaled by `decay` every sample.
+    struct Pluck {
+        hz: f32,
+        phase: f32,
+        level: f32,
+        decay: f32,
+    }
+
+    /// What the audio callback plays, shared with the `Synth`.
+    struct Voices {
+        drone: Voice,
+        churn: Voice,
+        plucks: Vec<Pluck>,
+    }
+
+    impl Voices {
+        fn sample(&mut self, rate: f32) -> f32 {
+            let glide = 1.0 - (-1.0 / (GLIDE * rate)).exp();
+            let (phase, level) = self.drone.advance(rate, glide);
+            let mut out = (phase * TAU).sin() * level;
+            let (phase, level) = self.churn.advance(rate, glide);
+            out += triangle(phase) * level;
+            for pluck in &mut self.plucks {
+                pluck.phase = (pluck.phase + pluck.hz / rate).fract();
+                out += triangle(pluck.phase) * pluck.level;
+                pluck.level *= pluck.decay;
+            }
+            self.plucks.retain(|pluck| pluck.level > PLUCK_END);
+            out
+        }
+    }
+
+    fn triangle(phase: f32) -> f32 {
+        4.0 * (phase - 0.5).abs() - 1.0
+    }
+
+    pub struct Synth {
+        voices: Arc<Mutex<Voices>>,
+        rate: f32,
+        // Playing for as long as it is held.
+        _stream: Stream,
+    }
+
+    impl Synth {
+        pub fn new() -> Option<Self> {
+            let device = cpal::default_host().default_output_device()?;
+            let supported = device.default_output_config().ok()?;
+            let format = supported.sample_format();
+            let config = supported.config();
+            let voices = Arc::new(Mutex::new(Voices { drone: Voice::new(), churn: Voice::new(), plucks: Vec::new() }));
+            let stream = match format {
+                SampleFormat::F32 => stream::<f32>(&device, &config, voices.clone()),
+                SampleFormat::I16 => stream::<i16>(&device, &config, voices.clone()),
+                SampleFormat::U16 => stream::<u16>(&device, &config, voices.clone()),
+                other => {
+                    tracing::warn!("no sound: unsupported sample format {other}");
+                    return None;
+                }
+            };
+            let stream = stream.inspect_err(|err| tracing::warn!("no sound: {err}")).ok()?;
+            stream.play().inspect_err(|err| tracing::warn!("no sound: {err}")).ok()?;
+            Some(Self { voices, rate: config.sample_rate.0 as f32, _stream: stream })
+        }
+
+        fn voices(&self) -> std::sync::MutexGuard<'_, Voices> {
+            self.voices.lock().unwrap_or_else(|err| err.into_inner())
+        }
+
+        pub fn set_voices(&self, drone_hz: f32, drone_level: f32, churn_hz: f32, churn_level: f32) {
+            let mut voices = self.voices();
+            voices.drone.target = (drone_hz, drone_level);
+            voices.churn.target = (churn_hz, churn_level);
+        }
+
+        /// Fire-and-forget note with a fast exponential decay.
+        pub fn pluck(&self, hz: f32, secs: f64) {
+            let decay = (PLUCK_END / PLUCK_LEVEL).powf(1.0 / (secs as f32 * self.rate).max(1.0));
+            let mut voices = self.voices();
+            if voices.plucks.len() < MAX_PLUCKS {
+                voices.plucks.push(Pluck { hz, phase: 0.0, level: PLUCK_LEVEL, decay });
+            }
+        }
+    }
+
+    /// An output stream playing `voices` on every channel, in samples of `T`.
+    fn stream<T: SizedSample + FromSample<f32>>(
+        device: &cpal::Device,
+        config: &StreamConfig,
+        voices: Arc<Mutex<Voices>>,
+    ) -> Result<Stream, cpal::BuildStreamError> {
+        let (rate, channels) = (config.sample_rate.0 as f32, config.channels as usize);
+        device.build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                let mut voices = voices.lock().unwrap_or_else(|err| err.into_inner());
+                for frame in data.chunks_mut(channels) {
+                    frame.fill(T::from_sample(voices.sample(rate)));
+                }
+            },
+            |err| tracing::warn!("audio stream failed: {err}"),
+            None,
+        )
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "native-audio")))]
+mod backend {
+    /// No audio in this build: never built, so the sonifier stays muted.
+    pub struct Synth;
+
+    impl Synth {
+        pub fn new() -> Option<Self> {
+            None
+        }
+
+        pub fn set_voices(&self, _drone_hz: f32, _drone_level: f32, _churn_hz: f32, _churn_level: f32) {}
+
+        pub fn pluck(&self, _hz: f32, _secs: f64) {}
+    }
+}
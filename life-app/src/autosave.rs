@@ -0,0 +1,256 @@
+// Autosave
+//
+// The board and a few settings are written out every few seconds, so a
+// reload or crash doesn't lose a session's editing. On startup the app offers
+// to restore whatever was saved last. Each workspace tab saves to its own
+// numbered slot, and the tab list, keyboard shortcuts, starred rules and the
+// pattern library are saved next to them; slot 0 is the key single-board builds always used.
+//
+// * **Web**→ `localStorage`
+// * **Native**→ a file in the OS temp directory
+//
+// Saves are JSON, `{"version": n, "session": {...}}`, with the board itself
+// as packed text (see `life_formats::packed`). Whenever the shape of
+// `Session` (or anything inside it) changes, bump `SCHEMA_VERSION` and teach
+// `upgrade` to turn the previous version into the new one, so old saves keep
+// loading. Version 1 predates JSON: a handful of `key=value` lines followed
+// by the board as RLE; version 2 spelled the board out field by field;
+// version 3 had no annotations.
+
+use crate::annotations::Annotation;
+use crate::keys::Keymap;
+use crate::library::Entry;
+use crate::overlay::Overlay;
+use crate::tabs::TabList;
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+use life_engine::universe::{Boundary, Universe, UniverseBuilder};
+use life_formats::{packed, PatternText};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Storage key / file name of slot 0; other slots, the tab list, the keymap,
+/// the starred rules and the pattern library append to it.
+const KEY: &str = "game-of-life-autosave";
+/// First line of a version 1 save.
+const HEADER: &str = "# game-of-life autosave";
+/// Layout version written by this build.
+pub const SCHEMA_VERSION: u64 = 4;
+
+/// Everything needed to pick a session back up.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(with = "packed::serde_text")]
+    pub universe: Universe,
+    pub generation: u64,
+    pub rival_rule: Rule,
+    pub overlay: Overlay,
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Serialize)]
+struct SaveFile<'a> {
+    version: u64,
+    session: &'a Session,
+}
+
+impl Session {
+    fn encode(&self) -> String {
+        let file = SaveFile { version: SCHEMA_VERSION, session: self };
+        serde_json::to_string(&file).expect("sessions always serialize")
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        if text.starts_with(HEADER) {
+            return Self::decode_v1(text);
+        }
+        let save: Value = serde_json::from_str(text).ok()?;
+        let version = save.get("version")?.as_u64()?;
+        let session = upgrade(version, save.get("session")?.clone())?;
+        serde_json::from_value(session).ok()
+    }
+
+    /// Read a version 1 save.
+    fn decode_v1(text: &str) -> Option<Self> {
+        let rest = text.strip_prefix(HEADER)?;
+        let mut fields = std::collections::HashMap::new();
+        let mut rle = String::new();
+        for line in rest.lines() {
+            match line.split_once('=') {
+                Some((key, value)) if !line.starts_with('x') => {
+                    fields.insert(key.trim(), value.trim());
+                }
+                _ => {
+                    rle.push_str(line);
+                    rle.push('\n');
+                }
+            }
+        }
+        let pattern = Pattern::parse_rle(&rle).ok()?;
+        let universe = UniverseBuilder::new()
+            .size(pattern.width, pattern.height)
+            .rule(pattern.rule.unwrap_or_default())
+            // Saves from before boundaries existed were all tori.
+            .boundary(fields.get("boundary").map(|key| Boundary::from_key(key)).unwrap_or_default())
+            .build_with(pattern.cells, fields.get("seed")?.parse().ok()?);
+        Some(Self {
+            universe,
+            generation: fields.get("generation")?.parse().ok()?,
+            rival_rule: Rule::parse(fields.get("rival_rule")?).ok()?,
+            overlay: Overlay::from_key(fields.get("overlay")?),
+            annotations: Vec::new(),
+        })
+    }
+}
+
+/// `session` as saved by schema `version`, rewritten into the current
+/// layout; `None` for versions this build doesn't know (e.g. a save from a
+/// newer build).
+fn upgrade(version: u64, session: Value) -> Option<Value> {
+    match version {
+        SCHEMA_VERSION => Some(session),
+        3 => upgrade(4, from_v3(session)),
+        2 => upgrade(3, from_v2(session)?),
+        _ => None,
+    }
+}
+
+/// Version 3 → 4: no annotations yet.
+fn from_v3(mut session: Value) -> Value {
+    session["annotations"] = Value::Array(Vec::new());
+    session
+}
+
+/// Version 2 → 3: the board becomes packed text.
+fn from_v2(mut session: Value) -> Option<Value> {
+    let board: Universe = serde_json::from_value(session.get_mut("universe")?.take()).ok()?;
+    session["universe"] = Value::String(packed::to_text(&board));
+    Some(session)
+}
+
+/// Storage key of a session slot.
+fn slot_key(slot: u64) -> String {
+    match slot {
+        0 => KEY.to_string(),
+        slot => format!("{KEY}-{slot}"),
+    }
+}
+
+/// Write `text` under `key`. A failed write is logged and the previous
+/// save, if any, stays as it was.
+fn write(key: &str, text: &str) -> Result<(), String> {
+    backend::write(key, text).inspect_err(|err| tracing::warn!("skipped saving `{key}`: {err}"))
+}
+
+/// Overwrite the session saved in `slot`; the next one tries again.
+pub fn save(slot: u64, session: &Session) {
+    _ = write(&slot_key(slot), &session.encode());
+}
+
+/// The session saved in `slot`, if there is a readable one.
+pub fn load(slot: u64) -> Option<Session> {
+    backend::read(&slot_key(slot)).and_then(|text| Session::decode(&text))
+}
+
+/// Forget the session saved in `slot`.
+pub fn clear(slot: u64) {
+    backend::remove(&slot_key(slot));
+}
+
+/// Overwrite the saved tab list.
+pub fn save_tabs(tabs: &TabList) {
+    let text = serde_json::to_string(tabs).expect("tab lists always serialize");
+    _ = write(&format!("{KEY}-tabs"), &text);
+}
+
+/// The saved tab list, if there is a readable one.
+pub fn load_tabs() -> Option<TabList> {
+    backend::read(&format!("{KEY}-tabs")).and_then(|text| serde_json::from_str(&text).ok())
+}
+
+/// Overwrite the saved keyboard shortcuts.
+pub fn save_keymap(keymap: &Keymap) {
+    let text = serde_json::to_string(keymap).expect("keymaps always serialize");
+    _ = write(&format!("{KEY}-keys"), &text);
+}
+
+/// The saved keyboard shortcuts, if there are readable ones.
+pub fn load_keymap() -> Option<Keymap> {
+    backend::read(&format!("{KEY}-keys")).and_then(|text| serde_json::from_str(&text).ok())
+}
+
+/// Overwrite the saved starred rules.
+pub fn save_starred_rules(rules: &[Rule]) {
+    let text = serde_json::to_string(rules).expect("rules always serialize");
+    _ = write(&format!("{KEY}-rules"), &text);
+}
+
+/// The saved starred rules, if there are readable ones.
+pub fn load_starred_rules() -> Option<Vec<Rule>> {
+    backend::read(&format!("{KEY}-rules")).and_then(|text| serde_json::from_str(&text).ok())
+}
+
+/// Overwrite the saved pattern library. Unlike the other saves this one
+/// isn't repeated, and thumbnails make it the likeliest to fill the
+/// storage, so the caller hears about a failure.
+pub fn save_library(entries: &[Entry]) -> Result<(), String> {
+    let text = serde_json::to_string(entries).expect("library entries always serialize");
+    write(&format!("{KEY}-library"), &text)
+}
+
+/// The saved pattern library, if there is a readable one.
+pub fn load_library() -> Option<Vec<Entry>> {
+    backend::read(&format!("{KEY}-library")).and_then(|text| serde_json::from_str(&text).ok())
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn write(key: &str, text: &str) -> Result<(), String> {
+        let _span = tracing::debug_span!("io", op = "write", key, bytes = text.len()).entered();
+        let storage = storage().ok_or("storage is unavailable")?;
+        // The only error `setItem` throws is the quota's.
+        storage.set_item(key, text).map_err(|_| "storage is full".to_string())
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        let _span = tracing::debug_span!("io", op = "read", key).entered();
+        storage()?.get_item(key).ok()?
+    }
+
+    pub fn remove(key: &str) {
+        let _span = tracing::debug_span!("io", op = "remove", key).entered();
+        if let Some(storage) = storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::path::PathBuf;
+
+    fn path(key: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{key}.txt"))
+    }
+
+    pub fn write(key: &str, text: &str) -> Result<(), String> {
+        let _span = tracing::debug_span!("io", op = "write", key, bytes = text.len()).entered();
+        // Write then rename, so a crash mid-save never leaves half a file.
+        let partial = path(key).with_extension("tmp");
+        std::fs::write(&partial, text).and_then(|()| std::fs::rename(partial, path(key))).map_err(|err| err.to_string())
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        let _span = tracing::debug_span!("io", op = "read", key).entered();
+        std::fs::read_to_string(path(key)).ok()
+    }
+
+    pub fn remove(key: &str) {
+        let _span = tracing::debug_span!("io", op = "remove", key).entered();
+        let _ = std::fs::remove_file(path(key));
+    }
+}
@@ -0,0 +1,254 @@
+// Benchmark
+//
+// A fixed workload for comparing engines: an acorn in the middle of a
+// 256×256 board, run with nothing drawn for `DURATION_MS` of wall-clock time
+// per backend. The acorn keeps growing for over 5000 generations, so the
+// board stays busy for the whole run. `--bench` on the command line runs
+// the CPU backends straight through; the "Benchmark" button runs the GPU
+// as well, a slice at a time so the page keeps responding, and only counts
+// the time spent stepping. Both print the same report.
+
+use std::time::Duration;
+
+use dioxus::prelude::*;
+
+use crate::gpu::GpuRun;
+use crate::i18n::Language;
+use crate::patterns;
+use crate::speed::now_ms;
+use life_engine::sparse::SparsePlane;
+use life_engine::stepper::Stepper;
+use life_engine::universe::{Boundary, Universe, UniverseBuilder};
+
+/// Board edge, in cells.
+pub const SIZE: usize = 256;
+/// Wall-clock time per backend, in ms.
+pub const DURATION_MS: f64 = 1000.0;
+/// Generations run between looks at the clock.
+const BATCH: u64 = 8;
+/// Stepping time between yields to the page, in ms.
+const SLICE_MS: f64 = 50.0;
+
+/// An engine that can advance a board.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Backend {
+    /// `Universe`: a flat torus, stepped 64 cells a word as a bitboard.
+    Dense,
+    /// `Stepper` on the same torus, which keeps the board packed and skips
+    /// the tiles that have settled.
+    Stepper,
+    /// `Universe` on a Klein bottle: twisted edges don't fit the bitboard,
+    /// so each row is counted a byte per cell with SIMD.
+    Simd,
+    /// `SparsePlane`: 64×64 chunks where there are live cells, on an
+    /// unbounded plane.
+    Sparse,
+    /// `GpuRun`: a compute shader, stepped in batches. Only in the app.
+    Gpu,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [Backend::Dense, Backend::Stepper, Backend::Simd, Backend::Sparse, Backend::Gpu];
+    /// The backends that don't need the webview.
+    pub const CPU: [Backend; 4] = [Backend::Dense, Backend::Stepper, Backend::Simd, Backend::Sparse];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Dense => "dense",
+            Backend::Stepper => "stepper",
+            Backend::Simd => "simd",
+            Backend::Sparse => "sparse",
+            Backend::Gpu => "gpu",
+        }
+    }
+}
+
+/// How one backend did.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Measurement {
+    pub backend: Backend,
+    pub generations: u64,
+    pub millis: f64,
+}
+
+impl Measurement {
+    pub fn per_second(&self) -> f64 {
+        self.generations as f64 * 1000.0 / self.millis.max(f64::EPSILON)
+    }
+}
+
+/// The standard board: an acorn in the middle of an empty `SIZE`×`SIZE` torus.
+pub fn board() -> Universe {
+    let mut universe = UniverseBuilder::new().size(SIZE, SIZE).seed(0).density(0.0).build();
+    if let Some(acorn) = patterns::bundled("Acorn") {
+        let (top, left) = acorn.origin_centered_on((SIZE / 2, SIZE / 2), SIZE, SIZE);
+        acorn.stamp(&mut universe, top, left);
+    }
+    universe
+}
+
+/// A CPU backend part way through its run.
+pub struct Run {
+    backend: Backend,
+    engine: Engine,
+    generations: u64,
+    millis: f64,
+}
+
+enum Engine {
+    Universe(Universe),
+    Stepper(Stepper, Universe),
+    Sparse(SparsePlane),
+}
+
+impl Run {
+    /// `backend` set up on the standard board; `None` for the GPU, which
+    /// `run_gpu` runs.
+    pub fn new(backend: Backend) -> Option<Self> {
+        let mut board = board();
+        let engine = match backend {
+            Backend::Dense => Engine::Universe(board),
+            Backend::Stepper => Engine::Stepper(Stepper::default(), board),
+            Backend::Simd => {
+                board.boundary = Boundary::Klein;
+                Engine::Universe(board)
+            }
+            Backend::Sparse => {
+                let live = board.live_cells().map(|(row, col)| (row as i32, col as i32));
+                Engine::Sparse(SparsePlane::new(live, board.rule))
+            }
+            Backend::Gpu => return None,
+        };
+        Some(Self { backend, engine, generations: 0, millis: 0.0 })
+    }
+
+    /// Step for up to `ms` more of the run's `DURATION_MS`; `true` once
+    /// it's used it all.
+    pub fn run_for(&mut self, ms: f64) -> bool {
+        let start = now_ms();
+        let until = ms.min(DURATION_MS - self.millis);
+        while now_ms() - start < until {
+            match &mut self.engine {
+                Engine::Universe(universe) => {
+                    universe.step_n(BATCH);
+                }
+                Engine::Stepper(stepper, universe) => {
+                    for _ in 0..BATCH {
+                        stepper.tick(universe);
+                    }
+                }
+                Engine::Sparse(plane) => {
+                    for _ in 0..BATCH {
+                        plane.step();
+                    }
+                }
+            }
+            self.generations += BATCH;
+        }
+        self.millis += now_ms() - start;
+        self.millis >= DURATION_MS
+    }
+
+    pub fn measurement(&self) -> Measurement {
+        Measurement { backend: self.backend, generations: self.generations, millis: self.millis }
+    }
+}
+
+/// Run the CPU backend `backend` on the standard board for `DURATION_MS`.
+pub fn run(backend: Backend) -> Option<Measurement> {
+    let mut run = Run::new(backend)?;
+    while !run.run_for(DURATION_MS) {}
+    Some(run.measurement())
+}
+
+/// Every CPU backend, one after the other.
+pub fn run_all() -> Vec<Measurement> {
+    Backend::CPU.into_iter().filter_map(run).collect()
+}
+
+/// Run the GPU on the standard board for `DURATION_MS`, timing the whole
+/// round trip of each batch; `Err` when there is no GPU to run on.
+pub async fn run_gpu() -> Result<Measurement, String> {
+    let mut gpu = GpuRun::open().await?;
+    let result = gpu.load(&board()).await;
+    let mut generations = 0;
+    let start = now_ms();
+    while result.is_ok() && now_ms() - start < DURATION_MS {
+        if let Err(err) = gpu.step(BATCH).await {
+            gpu.close();
+            return Err(err);
+        }
+        generations += BATCH;
+    }
+    let millis = now_ms() - start;
+    gpu.close();
+    result.map(|()| Measurement { backend: Backend::Gpu, generations, millis })
+}
+
+/// Every backend, the GPU included, yielding to the page between slices.
+/// Each line of the report goes to `line` as soon as it's ready.
+pub async fn run_in_page(mut line: impl FnMut(String)) {
+    for backend in Backend::ALL {
+        let result = match Run::new(backend) {
+            Some(mut run) => {
+                while !run.run_for(SLICE_MS) {
+                    dioxus_time::sleep(Duration::ZERO).await;
+                }
+                Ok(run.measurement())
+            }
+            None => run_gpu().await,
+        };
+        line(match result {
+            Ok(result) => report_line(&result),
+            Err(err) => format!("{:<7} {err}", backend.name()),
+        });
+        dioxus_time::sleep(Duration::ZERO).await;
+    }
+}
+
+/// One line per backend, e.g. `dense   1234 gen/s (1234 generations in 1000 ms)`.
+pub fn report(results: &[Measurement]) -> String {
+    results.iter().map(report_line).collect::<Vec<_>>().join("\n")
+}
+
+fn report_line(result: &Measurement) -> String {
+    format!(
+        "{:<7} {:>8.0} gen/s ({} generations in {:.0} ms)",
+        result.backend.name(),
+        result.per_second(),
+        result.generations,
+        result.millis,
+    )
+}
+
+/// The "Benchmark" button, and the report of the last run as it comes in.
+#[component]
+pub(crate) fn BenchmarkPanel() -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut benchmark = use_signal(|| None::<String>);
+    let mut benchmarking = use_signal(|| false);
+    rsx! {
+        div { class: "controls",
+            button {
+                title: (text.benchmark_hint)(SIZE, DURATION_MS / 1000.0),
+                disabled: benchmarking(),
+                onclick: move |_| async move {
+                    benchmarking.set(true);
+                    benchmark.set(Some(String::new()));
+                    run_in_page(|line| {
+                        if let Some(report) = benchmark.write().as_mut() {
+                            report.push_str(&line);
+                            report.push('\n');
+                        }
+                    })
+                    .await;
+                    benchmarking.set(false);
+                },
+                "{text.benchmark}"
+            }
+            if let Some(report) = benchmark() {
+                pre { class: "benchmark", "{report}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Plain board view
+//
+// Boards are drawn as one `div` per cell, grouped into one `BoardRow` per
+// row. A row only re-renders when its hash changes, so a tick that touches a
+// few rows leaves the rest of the cells alone instead of diffing every one
+// of them; the rows are `display: contents`, so the cells still sit in the
+// board's own grid.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use life_engine::universe::Universe;
+
+use crate::render::{GridLines, RenderOptions};
+use crate::viewport::Viewport;
+
+/// How one cell is drawn.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CellLook {
+    pub class: String,
+    pub style: String,
+    /// Number printed on the cell, if any.
+    pub count: Option<u8>,
+}
+
+/// A row of cells and their hash; rows compare by hash alone.
+#[derive(Clone, Debug)]
+pub struct RowLook {
+    hash: u64,
+    cells: Rc<[CellLook]>,
+}
+
+impl RowLook {
+    pub fn new(cells: Vec<CellLook>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        cells.hash(&mut hasher);
+        Self { hash: hasher.finish(), cells: cells.into() }
+    }
+}
+
+impl PartialEq for RowLook {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+/// One row of a board, memoized on its hash.
+#[component]
+pub fn BoardRow(look: RowLook) -> Element {
+    rsx! {
+        div { class: "board-row", style: "display: contents;",
+            for (col, cell) in look.cells.iter().enumerate() {
+                div { key: "{col}", class: "{cell.class}", style: "{cell.style}",
+                    if let Some(count) = cell.count {
+                        span { class: "count", "{count}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Alive/dead rendering of `universe` through `viewport`, without overlays or
+/// input handling. Used for secondary boards such as the rule comparison.
+#[component]
+pub fn BoardView(universe: Universe, viewport: Viewport, cell_size: usize, render: RenderOptions) -> Element {
+    rsx! {
+        div {
+            class: render.board_class(),
+            style: render.board_style(&viewport, cell_size),
+            for row in viewport.row..viewport.row + viewport.rows {
+                BoardRow {
+                    key: "{row}",
+                    look: RowLook::new(
+                        (viewport.col..viewport.col + viewport.cols)
+                            .map(|col| CellLook {
+                                class: if universe.cells[row * universe.width + col] { "cell alive" } else { "cell dead" }.to_string(),
+                                style: format!("width: {cell_size}px; height: {cell_size}px;"),
+                                count: None,
+                            })
+                            .collect(),
+                    ),
+                }
+            }
+            if let Some(spacing) = render.grid {
+                GridLines { spacing, viewport, cell_size }
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Session bundles
+//
+// "Export session" writes everything needed to pick a session up on another
+// machine into one JSON file: what the autosave keeps (board, generation,
+// comparison rule, overlay), the whole history store with its keyframes, the
+// drawing and speed settings, the snapshot board, the starred rules and the
+// pattern library. "Import session" reads such a file back in.
+//
+// Files are `{"version": n, "bundle": {...}}`; bump `BUNDLE_VERSION` whenever
+// the layout of `Bundle` (or anything inside it) changes. Version 1 bundles
+// had no bookmarks or annotations and still load, with none; neither
+// version 1 nor 2 had starred rules, and none before 4 had the library.
+
+use dioxus::prelude::*;
+use life_engine::rule::Rule;
+use life_engine::universe::Universe;
+use life_formats::packed;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::autosave::Session;
+use crate::history::History;
+use crate::library::Entry;
+use crate::render::RenderOptions;
+use crate::speed::Speed;
+
+/// Layout version written by this build.
+pub const BUNDLE_VERSION: u64 = 4;
+/// Name exported bundles are saved under.
+pub const FILE_NAME: &str = "game-of-life-session.json";
+
+/// A whole session, ready to move between machines.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub session: Session,
+    pub history: History,
+    pub render: RenderOptions,
+    pub speed: Speed,
+    #[serde(with = "packed_option")]
+    pub snapshot: Option<Universe>,
+    #[serde(default)]
+    pub starred_rules: Vec<Rule>,
+    #[serde(default)]
+    pub library: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct BundleFile<'a> {
+    version: u64,
+    bundle: &'a Bundle,
+}
+
+impl Bundle {
+    pub fn encode(&self) -> String {
+        let file = BundleFile { version: BUNDLE_VERSION, bundle: self };
+        serde_json::to_string(&file).expect("bundles always serialize")
+    }
+
+    /// Read a bundle back, with its history ready to use.
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let mut file: Value = serde_json::from_str(text).map_err(|err| format!("not a session file: {err}"))?;
+        match file.get("version").and_then(Value::as_u64) {
+            Some(1) => {
+                let session = file.pointer_mut("/bundle/session").and_then(Value::as_object_mut).ok_or("not a session file: no session")?;
+                session.insert("annotations".to_string(), Value::Array(Vec::new()));
+            }
+            Some(2 | 3 | BUNDLE_VERSION) => {}
+            Some(version) => return Err(format!("unsupported session file version {version}")),
+            None => return Err("not a session file: no version".to_string()),
+        }
+        let bundle = file.get("bundle").cloned().ok_or("not a session file: no bundle")?;
+        let mut bundle: Self = serde_json::from_value(bundle).map_err(|err| format!("broken session file: {err}"))?;
+        if !bundle.history.restore(bundle.session.universe.cells.len()) {
+            return Err("broken session file: the history doesn't match the board".to_string());
+        }
+        Ok(bundle)
+    }
+}
+
+/// Offer `text` to the user as a download named `name`, of MIME type `mime`,
+/// through a link clicked from `document::eval`: a page has no other way to
+/// save a file, and the webview asks where to put it as a browser would.
+pub fn download(name: &str, mime: &str, text: &str) -> Result<(), String> {
+    let eval = document::eval(
+        r#"
+        const [name, mime, text] = await dioxus.recv();
+        const url = URL.createObjectURL(new Blob([text], { type: mime }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = name;
+        link.click();
+        URL.revokeObjectURL(url);
+        "#,
+    );
+    eval.send((name, mime, text)).map_err(|err| err.to_string())
+}
+
+/// An optional board as packed text, `null` when absent.
+mod packed_option {
+    use super::{packed, Universe};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(universe: &Option<Universe>, serializer: S) -> Result<S::Ok, S::Error> {
+        universe.as_ref().map(packed::to_text).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Universe>, D::Error> {
+        let text = Option::<String>::deserialize(deserializer)?;
+        text.map(|text| packed::from_text(&text).map_err(serde::de::Error::custom)).transpose()
+    }
+}
@@ -0,0 +1,412 @@
+// Object census
+//
+// Splits the board into objects and names them. Live cells within two cells
+// of each other (Chebyshev distance) form a cluster, which keeps sparse but
+// connected-in-spirit shapes such as the LWSS or a beacon together. A cluster
+// that isn't recognised is split again into 8-connected components, in case
+// it was really two objects sitting close together.
+//
+// Recognition is a lookup of the normalised shape in a small database of
+// common Conway objects (every phase, every orientation). Anything else is
+// run in isolation on the infinite plane to at least tell still lifes,
+// oscillators and spaceships apart.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+use life_engine::sparse::SparsePlane;
+use life_engine::universe::Universe;
+
+use crate::apgcode;
+use crate::i18n::{Language, Strings};
+
+/// Longest period looked for when classifying by simulation.
+const MAX_PERIOD: u32 = 30;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Kind {
+    StillLife,
+    Oscillator { period: u32 },
+    /// Moves by `(d_row, d_col)` every `period` generations.
+    Spaceship { period: u32, d_row: i32, d_col: i32 },
+    /// Not periodic within `MAX_PERIOD` when run alone.
+    Unstable,
+}
+
+/// What an object is called, independent of the UI language.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Label {
+    /// A database name, e.g. "glider". These are the community's names and
+    /// aren't translated.
+    Named(&'static str),
+    StillLife { cells: usize },
+    Oscillator { period: u32 },
+    Spaceship { period: u32 },
+    Unstable,
+}
+
+impl Label {
+    pub fn text(self, text: &Strings) -> String {
+        match self {
+            Label::Named(name) => name.to_string(),
+            Label::StillLife { cells } => (text.still_life)(cells),
+            Label::Oscillator { period } => (text.oscillator)(period),
+            Label::Spaceship { period } => (text.spaceship)(period),
+            Label::Unstable => text.unstable.to_string(),
+        }
+    }
+}
+
+/// One object found on the board.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Object {
+    /// Database name, if the shape is a known Conway object.
+    pub name: Option<&'static str>,
+    pub kind: Kind,
+    /// Cells as `(row, col)`. Coordinates are unwrapped across the torus
+    /// edges, so they may fall outside `0..height` / `0..width`.
+    pub cells: Vec<(i32, i32)>,
+}
+
+impl Object {
+    /// The database name, or failing that a description of the kind.
+    pub fn label(&self) -> Label {
+        if let Some(name) = self.name {
+            return Label::Named(name);
+        }
+        match self.kind {
+            Kind::StillLife => Label::StillLife { cells: self.cells.len() },
+            Kind::Oscillator { period } => Label::Oscillator { period },
+            Kind::Spaceship { period, .. } => Label::Spaceship { period },
+            Kind::Unstable => Label::Unstable,
+        }
+    }
+
+    /// `(min_row, min_col, max_row, max_col)` of the cells.
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        bounds(&self.cells)
+    }
+}
+
+impl Kind {
+    /// Speed in the usual `c/n` notation plus heading, for spaceships.
+    pub fn speed(&self, text: &Strings) -> Option<String> {
+        let Kind::Spaceship { period, d_row, d_col } = *self else { return None };
+        let distance = d_row.unsigned_abs().max(d_col.unsigned_abs());
+        let divisor = gcd(distance, period);
+        let (distance, period) = (distance / divisor, period / divisor);
+        let heading = match (d_row, d_col) {
+            (0, _) | (_, 0) => text.orthogonal,
+            (r, c) if r.abs() == c.abs() => text.diagonal,
+            _ => text.oblique,
+        };
+        let speed = match (distance, period) {
+            (1, 1) => "c".to_string(),
+            (1, p) => format!("c/{p}"),
+            (d, 1) => format!("{d}c"),
+            (d, p) => format!("{d}c/{p}"),
+        };
+        Some(format!("{speed} {heading}"))
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Known objects as plaintext rows (`O` alive, `.` dead, `/` between rows).
+const DATABASE: [(&str, &str); 21] = [
+    ("block", "OO/OO"),
+    ("beehive", ".OO./O..O/.OO."),
+    ("loaf", ".OO./O..O/.O.O/..O."),
+    ("boat", "OO./O.O/.O."),
+    ("ship", "OO./O.O/.OO"),
+    ("tub", ".O./O.O/.O."),
+    ("pond", ".OO./O..O/O..O/.OO."),
+    ("long boat", "OO../O.O./.O.O/..O."),
+    ("barge", ".O../O.O./.O.O/..O."),
+    ("snake", "OO.O/O.OO"),
+    ("eater 1", "OO../O.O./..O./..OO"),
+    ("aircraft carrier", "OO../O..O/..OO"),
+    ("blinker", "OOO"),
+    ("toad", ".OOO/OOO."),
+    ("beacon", "OO../OO../..OO/..OO"),
+    ("pulsar", "..OOO...OOO../............./O....O.O....O/O....O.O....O/O....O.O....O/..OOO...OOO../............./..OOO...OOO../O....O.O....O/O....O.O....O/O....O.O....O/............./..OOO...OOO.."),
+    ("pentadecathlon", "..O....O../OO.OOOO.OO/..O....O.."),
+    ("glider", ".O./..O/OOO"),
+    ("LWSS", ".O..O/O..../O...O/OOOO."),
+    ("MWSS", "...O../.O...O/O...../O....O/OOOOO."),
+    ("HWSS", "...OO../.O....O/O....../O.....O/OOOOOO."),
+];
+
+/// Every normalised phase/orientation of every database object → its index.
+fn database() -> &'static HashMap<Vec<(i32, i32)>, usize> {
+    static DB: OnceLock<HashMap<Vec<(i32, i32)>, usize>> = OnceLock::new();
+    DB.get_or_init(|| {
+        let mut db = HashMap::new();
+        for (index, (_, rows)) in DATABASE.iter().enumerate() {
+            let mut plane = SparsePlane::new(parse_rows(rows), Rule::CONWAY);
+            for _ in 0..MAX_PERIOD {
+                let phase: Vec<_> = plane.cells().collect();
+                for shape in orientations(&phase) {
+                    db.entry(shape).or_insert(index);
+                }
+                plane.step();
+            }
+        }
+        db
+    })
+}
+
+/// `O`/`.` rows separated by `/` → live cells.
+fn parse_rows(rows: &str) -> Vec<(i32, i32)> {
+    rows.split('/')
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == 'O')
+                .map(move |(col, _)| (row as i32, col as i32))
+        })
+        .collect()
+}
+
+/// Cells shifted to start at `(0, 0)`, sorted – a translation-free key.
+pub fn normalize(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
+    let (min_row, min_col, _, _) = bounds(cells);
+    let mut shape: Vec<_> = cells.iter().map(|&(r, c)| (r - min_row, c - min_col)).collect();
+    shape.sort_unstable();
+    shape
+}
+
+/// The eight rotations/reflections of `cells`, each normalised.
+pub fn orientations(cells: &[(i32, i32)]) -> Vec<Vec<(i32, i32)>> {
+    type Transform = fn((i32, i32)) -> (i32, i32);
+    let transforms: [Transform; 8] = [
+        |(r, c)| (r, c),
+        |(r, c)| (c, -r),
+        |(r, c)| (-r, -c),
+        |(r, c)| (-c, r),
+        |(r, c)| (r, -c),
+        |(r, c)| (-r, c),
+        |(r, c)| (c, r),
+        |(r, c)| (-c, -r),
+    ];
+    transforms
+        .iter()
+        .map(|transform| normalize(&cells.iter().map(|&cell| transform(cell)).collect::<Vec<_>>()))
+        .collect()
+}
+
+fn bounds(cells: &[(i32, i32)]) -> (i32, i32, i32, i32) {
+    cells.iter().fold((i32::MAX, i32::MAX, i32::MIN, i32::MIN), |(r0, c0, r1, c1), &(r, c)| {
+        (r0.min(r), c0.min(c), r1.max(r), c1.max(c))
+    })
+}
+
+/// Run `cells` alone on the infinite plane to see what it does.
+pub fn classify(cells: &[(i32, i32)], rule: Rule) -> Kind {
+    let start = normalize(cells);
+    let (row0, col0, _, _) = bounds(cells);
+    let mut plane = SparsePlane::new(cells.iter().copied(), rule);
+    for period in 1..=MAX_PERIOD {
+        plane.step();
+        let now: Vec<_> = plane.cells().collect();
+        if now.is_empty() {
+            return Kind::Unstable;
+        }
+        if normalize(&now) == start {
+            let (row1, col1, _, _) = bounds(&now);
+            return match (period, row1 - row0, col1 - col0) {
+                (1, 0, 0) => Kind::StillLife,
+                (period, 0, 0) => Kind::Oscillator { period },
+                (period, d_row, d_col) => Kind::Spaceship { period, d_row, d_col },
+            };
+        }
+    }
+    Kind::Unstable
+}
+
+/// Find and name every object on the board.
+pub fn objects(universe: &Universe) -> Vec<Object> {
+    let db = database();
+    let use_db = universe.rule == Rule::CONWAY;
+    let identify = |cells: &[(i32, i32)]| {
+        let name = use_db.then(|| db.get(&normalize(cells))).flatten().map(|&index| DATABASE[index].0);
+        let kind = classify(cells, universe.rule);
+        Object { name, kind, cells: cells.to_vec() }
+    };
+
+    let mut found = Vec::new();
+    for cluster in clusters(universe, 2) {
+        let object = identify(&cluster);
+        if object.name.is_some() || object.kind != Kind::Unstable {
+            found.push(object);
+            continue;
+        }
+        // Maybe several objects close together: retry each 8-connected part.
+        found.extend(components(&cluster).iter().map(|part| identify(part)));
+    }
+    found
+}
+
+/// Bounding boxes of the objects on the board, as [`objects`] would group
+/// them, without classifying anything. Cheap enough to run as the mouse moves.
+pub fn outlines(universe: &Universe) -> Vec<(i32, i32, i32, i32)> {
+    clusters(universe, 2).iter().map(|cluster| bounds(cluster)).collect()
+}
+
+/// Only the spaceships. Cheaper than [`objects`]: large clusters are skipped
+/// outright, since no ship in the database comes close to `MAX_SHIP_CELLS`.
+pub fn spaceships(universe: &Universe) -> Vec<Object> {
+    const MAX_SHIP_CELLS: usize = 64;
+    clusters(universe, 2)
+        .into_iter()
+        .filter(|cluster| cluster.len() <= MAX_SHIP_CELLS)
+        .map(|cells| Object { name: None, kind: classify(&cells, universe.rule), cells })
+        .filter(|object| matches!(object.kind, Kind::Spaceship { .. }))
+        .map(|mut object| {
+            if universe.rule == Rule::CONWAY {
+                object.name = database().get(&normalize(&object.cells)).map(|&index| DATABASE[index].0);
+            }
+            object
+        })
+        .collect()
+}
+
+/// One line of the census: how many of one kind of object there are.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tally {
+    pub label: Label,
+    /// Canonical apgcode, for cross-referencing with Catagolue.
+    pub apgcode: Option<String>,
+    pub count: usize,
+}
+
+/// Objects grouped by apgcode (by label when there is none), most common first.
+pub fn census(universe: &Universe) -> Vec<Tally> {
+    let mut tallies: HashMap<Result<String, Label>, Tally> = HashMap::new();
+    for object in objects(universe) {
+        let label = object.label();
+        let apgcode = apgcode::encode(&object, universe.rule);
+        let key = apgcode.clone().ok_or(label);
+        tallies.entry(key).or_insert(Tally { label, apgcode, count: 0 }).count += 1;
+    }
+    let mut tallies: Vec<_> = tallies.into_values().collect();
+    tallies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+    tallies
+}
+
+/// Group live cells lying within `reach` cells of each other, across the
+/// torus edges. Coordinates are unwrapped relative to each cluster's first cell.
+fn clusters(universe: &Universe, reach: i32) -> Vec<Vec<(i32, i32)>> {
+    let (width, height) = (universe.width as i32, universe.height as i32);
+    let mut seen = vec![false; universe.cells.len()];
+    let mut clusters = Vec::new();
+
+    for start in 0..universe.cells.len() {
+        if !universe.cells[start] || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut cluster = Vec::new();
+        let mut queue = VecDeque::from([((start / universe.width) as i32, (start % universe.width) as i32)]);
+        while let Some((row, col)) = queue.pop_front() {
+            cluster.push((row, col));
+            for d_row in -reach..=reach {
+                for d_col in -reach..=reach {
+                    let (r, c) = (row + d_row, col + d_col);
+                    let idx = (r.rem_euclid(height) * width + c.rem_euclid(width)) as usize;
+                    if universe.cells[idx] && !seen[idx] {
+                        seen[idx] = true;
+                        queue.push_back((r, c));
+                    }
+                }
+            }
+        }
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+/// Split `cells` into 8-connected components.
+fn components(cells: &[(i32, i32)]) -> Vec<Vec<(i32, i32)>> {
+    let mut remaining: HashSet<_> = cells.iter().copied().collect();
+    let mut parts = Vec::new();
+    while let Some(&start) = remaining.iter().next() {
+        remaining.remove(&start);
+        let mut part = vec![start];
+        let mut next = 0;
+        while next < part.len() {
+            let (row, col) = part[next];
+            next += 1;
+            for d_row in -1..=1 {
+                for d_col in -1..=1 {
+                    if remaining.remove(&(row + d_row, col + d_col)) {
+                        part.push((row + d_row, col + d_col));
+                    }
+                }
+            }
+        }
+        parts.push(part);
+    }
+    parts
+}
+
+/// "Analyze" for a census of the board, linking each object to Catagolue,
+/// and a box to decode an apgcode and place it via `on_place`.
+#[component]
+pub fn CensusPanel(universe: Signal<Universe>, on_place: EventHandler<Pattern>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    // Result of the last "Analyze", most common first.
+    let mut tallies = use_signal(|| None::<Vec<Tally>>);
+    // apgcode typed in to be decoded and placed, and why it failed, if it did.
+    let mut apgcode_input = use_signal(String::new);
+    let mut apgcode_error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "controls",
+            button { onclick: move |_| tallies.set(Some(census(&universe.read()))), "{text.analyze}" }
+            if let Some(found) = tallies() {
+                ul { class: "census",
+                    for tally in found {
+                        li {
+                            "{tally.count} × {tally.label.text(text)}"
+                            if let Some(code) = tally.apgcode {
+                                " "
+                                a {
+                                    href: apgcode::catagolue_url(&code, universe.read().rule),
+                                    target: "_blank",
+                                    code { "{code}" }
+                                }
+                            }
+                        }
+                    }
+                }
+                button { onclick: move |_| tallies.set(None), "{text.hide}" }
+            }
+            input {
+                placeholder: text.apgcode_placeholder,
+                aria_label: text.apgcode_placeholder,
+                value: "{apgcode_input}",
+                oninput: move |evt| apgcode_input.set(evt.value()),
+            }
+            button {
+                onclick: move |_| match apgcode::decode(&apgcode_input()) {
+                    Ok(pattern) => {
+                        on_place.call(pattern);
+                        apgcode_error.set(None);
+                    }
+                    Err(err) => apgcode_error.set(Some(err)),
+                },
+                "{text.place_apgcode}"
+            }
+            if let Some(err) = apgcode_error() {
+                span { class: "error", role: "alert", "{err}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// Tiny SVG charts
+use dioxus::prelude::*;
+
+const WIDTH: f64 = 200.0;
+const HEIGHT: f64 = 40.0;
+
+/// Line plot of `values` scaled so `max` touches the top edge, with the latest
+/// value printed next to the label.
+#[component]
+pub fn Sparkline(label: String, values: Vec<f64>, max: f64) -> Element {
+    let step = WIDTH / values.len().saturating_sub(1).max(1) as f64;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let y = HEIGHT - (value / max).clamp(0.0, 1.0) * HEIGHT;
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let latest = values.last().copied().unwrap_or_default();
+
+    rsx! {
+        figure { class: "sparkline",
+            figcaption { "{label}: {latest:.3}" }
+            // The caption already reads out the latest value.
+            svg {
+                "aria-hidden": "true",
+                width: "{WIDTH}",
+                height: "{HEIGHT}",
+                view_box: "0 0 {WIDTH} {HEIGHT}",
+                polyline { points, fill: "none", style: "stroke: var(--gol-chart-line)", stroke_width: "1.5" }
+            }
+        }
+    }
+}
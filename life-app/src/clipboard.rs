@@ -0,0 +1,92 @@
+// System clipboard
+//
+// On the desktop the clipboard is the OS's own, through `arboard`: webviews
+// there gate `navigator.clipboard` behind permissions and user gestures,
+// and some (WebKitGTK) refuse to read it at all. In a browser, where there
+// is nothing else, both directions go through the async Clipboard API
+// (`navigator.clipboard`) via `document::eval`.
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod backend {
+    use std::cell::RefCell;
+
+    use arboard::Clipboard;
+
+    thread_local! {
+        // Kept open: on X11 what was copied lasts only as long as the
+        // clipboard that copied it.
+        static CLIPBOARD: RefCell<Option<Clipboard>> = const { RefCell::new(None) };
+    }
+
+    fn with<T>(run: impl FnOnce(&mut Clipboard) -> Result<T, arboard::Error>) -> Result<T, String> {
+        CLIPBOARD.with_borrow_mut(|clipboard| {
+            let clipboard = match clipboard {
+                Some(clipboard) => clipboard,
+                None => clipboard.insert(Clipboard::new().map_err(|err| err.to_string())?),
+            };
+            run(clipboard).map_err(|err| err.to_string())
+        })
+    }
+
+    pub async fn read_text() -> Result<String, String> {
+        with(|clipboard| clipboard.get_text()).map_err(|err| format!("could not read the clipboard: {err}"))
+    }
+
+    pub async fn write_text(text: &str) -> Result<(), String> {
+        with(|clipboard| clipboard.set_text(text)).map_err(|err| format!("could not write the clipboard: {err}"))
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+mod backend {
+    use dioxus::prelude::*;
+
+    pub async fn read_text() -> Result<String, String> {
+        let mut eval = document::eval(
+            r#"
+            try {
+                dioxus.send([true, await navigator.clipboard.readText()]);
+            } catch (error) {
+                dioxus.send([false, String(error)]);
+            }
+            "#,
+        );
+        let (ok, text): (bool, String) = eval.recv().await.map_err(|err| err.to_string())?;
+        if ok {
+            Ok(text)
+        } else {
+            Err(format!("could not read the clipboard: {text}"))
+        }
+    }
+
+    pub async fn write_text(text: &str) -> Result<(), String> {
+        let mut eval = document::eval(
+            r#"
+            const text = await dioxus.recv();
+            try {
+                await navigator.clipboard.writeText(text);
+                dioxus.send([true, ""]);
+            } catch (error) {
+                dioxus.send([false, String(error)]);
+            }
+            "#,
+        );
+        eval.send(text).map_err(|err| err.to_string())?;
+        let (ok, error): (bool, String) = eval.recv().await.map_err(|err| err.to_string())?;
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("could not write the clipboard: {error}"))
+        }
+    }
+}
+
+/// Text currently on the clipboard.
+pub async fn read_text() -> Result<String, String> {
+    backend::read_text().await
+}
+
+/// Replace the clipboard contents with `text`.
+pub async fn write_text(text: &str) -> Result<(), String> {
+    backend::write_text(text).await
+}
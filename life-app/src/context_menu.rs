@@ -0,0 +1,136 @@
+// Board context menu
+//
+// Right-clicking a cell opens a small menu at the pointer with actions for
+// that one cell: toggle it, stamp the last placed pattern or the clipboard
+// centred on it, centre the view on it, or look at its neighbourhood.
+// Clicking anywhere else, or Escape, closes the menu.
+
+use dioxus::prelude::*;
+use life_engine::universe::Universe;
+
+use crate::i18n::{Language, Strings};
+
+/// What the menu can do with its cell.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CellAction {
+    Toggle,
+    /// Place the last placed pattern again, centred here.
+    Stamp,
+    /// Place the clipboard's pattern, centred here.
+    Paste,
+    CenterView,
+    Inspect,
+}
+
+/// A cell and the 3×3 block round it, as the rule sees them.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Neighborhood {
+    pub row: usize,
+    pub col: usize,
+    /// Row by row, the cell itself in the middle. Cells past a dead edge are
+    /// dead.
+    pub cells: [bool; 9],
+    pub live_neighbors: u8,
+    /// Whether the cell is alive next generation.
+    pub next: bool,
+}
+
+impl Neighborhood {
+    pub fn of(board: &Universe, row: usize, col: usize) -> Self {
+        let mut cells = [false; 9];
+        for (cell, at) in cells.iter_mut().zip(block(board, row, col)) {
+            *cell = at.is_some_and(|(r, c)| board.cells[r * board.width + c]);
+        }
+        let (live_neighbors, next) = match board.inspect(row, col) {
+            Ok(info) => (info.live_neighbors, info.next.is_alive()),
+            Err(_) => (0, false),
+        };
+        Self { row, col, cells, live_neighbors, next }
+    }
+
+    pub fn summary(&self, text: &Strings) -> String {
+        (text.neighborhood_summary)(self.row, self.col, self.live_neighbors, self.cells[4], self.next)
+    }
+}
+
+/// The board positions of the 3×3 block round a cell, row by row; `None`
+/// past a dead edge.
+pub fn block(board: &Universe, row: usize, col: usize) -> [Option<(usize, usize)>; 9] {
+    std::array::from_fn(|i| {
+        let (r, c) = (row as isize + i as isize / 3 - 1, col as isize + i as isize % 3 - 1);
+        board.boundary.wrap(r, c, board.width, board.height)
+    })
+}
+
+/// The menu for `cell`, its top-left corner at page position `at`.
+/// `stamp` names the pattern "Stamp" would place, if there is one.
+#[component]
+pub fn ContextMenu(at: (f64, f64), stamp: Option<String>, on_pick: EventHandler<CellAction>, on_close: EventHandler<()>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let (x, y) = at;
+
+    rsx! {
+        div {
+            class: "menu-backdrop",
+            onclick: move |_| on_close.call(()),
+            oncontextmenu: move |evt| {
+                evt.prevent_default();
+                on_close.call(());
+            },
+            ul {
+                class: "context-menu",
+                role: "menu",
+                style: "left: {x}px; top: {y}px;",
+                onclick: move |evt| evt.stop_propagation(),
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Escape {
+                        on_close.call(());
+                    }
+                },
+                li {
+                    button {
+                        role: "menuitem",
+                        onmounted: move |evt| async move {
+                            let _ = evt.set_focus(true).await;
+                        },
+                        onclick: move |_| on_pick.call(CellAction::Toggle),
+                        "{text.menu_toggle}"
+                    }
+                }
+                if let Some(name) = stamp {
+                    li {
+                        button { role: "menuitem", onclick: move |_| on_pick.call(CellAction::Stamp),
+                            {(text.menu_stamp)(&name)}
+                        }
+                    }
+                }
+                li {
+                    button { role: "menuitem", onclick: move |_| on_pick.call(CellAction::Paste), "{text.menu_paste}" }
+                }
+                li {
+                    button { role: "menuitem", onclick: move |_| on_pick.call(CellAction::CenterView), "{text.menu_center}" }
+                }
+                li {
+                    button { role: "menuitem", onclick: move |_| on_pick.call(CellAction::Inspect), "{text.menu_inspect}" }
+                }
+            }
+        }
+    }
+}
+
+/// The inspected cell's 3×3 block with a one-line reading of it.
+#[component]
+pub fn NeighborhoodView(neighborhood: Neighborhood, on_close: EventHandler<()>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    rsx! {
+        figure { class: "neighborhood",
+            div { class: "neighborhood-grid", "aria-hidden": "true",
+                for (i, alive) in neighborhood.cells.into_iter().enumerate() {
+                    div { class: if alive { "cell alive" } else { "cell dead" }, class: if i == 4 { "center" } }
+                }
+            }
+            figcaption { {neighborhood.summary(text)} }
+            button { onclick: move |_| on_close.call(()), "{text.close}" }
+        }
+    }
+}
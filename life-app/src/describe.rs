@@ -0,0 +1,39 @@
+// Text descriptions of the board
+//
+// The board is a grid of empty divs, which means nothing to a screen reader.
+// This reads it out as text instead: the extent of the live cells, then each
+// visible row that has any, as runs of columns ("4–6, 9"). Rows and columns
+// are counted from 1, as people count them.
+
+use crate::viewport::Viewport;
+use life_engine::universe::Universe;
+
+/// Column runs of the live cells in `cells`, e.g. `"4–6, 9"`, numbered from
+/// `first_col`.
+fn runs(cells: &[bool], first_col: usize) -> String {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (offset, &alive) in cells.iter().chain([&false]).enumerate() {
+        match (alive, start) {
+            (true, None) => start = Some(offset),
+            (false, Some(from)) => {
+                let (from, to) = (first_col + from, first_col + offset - 1);
+                runs.push(if from == to { format!("{from}") } else { format!("{from}–{to}") });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs.join(", ")
+}
+
+/// `(row, runs)` for each row in `viewport` holding live cells, both 1-based.
+pub fn rows(universe: &Universe, viewport: &Viewport) -> Vec<(usize, String)> {
+    (viewport.row..viewport.row + viewport.rows)
+        .filter_map(|row| {
+            let start = row * universe.width + viewport.col;
+            let cells = &universe.cells[start..start + viewport.cols];
+            cells.contains(&true).then(|| (row + 1, runs(cells, viewport.col + 1)))
+        })
+        .collect()
+}
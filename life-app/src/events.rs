@@ -0,0 +1,38 @@
+// Generation events
+//
+// Each board's `GenerationHooks` are shared through the context with every
+// component under it, so a widget that follows the run subscribes with
+// `use_on_generation` and is called once per generation, rather than
+// polling the board's signals for a change; a renderer can take just the
+// cells each generation changed with `use_on_changes`. A batch stepped in one
+// go comes as one call, with totals as `Universe::step_n` gives them: from
+// the CPU with the cells the whole batch changed, from the GPU with no
+// changes. The subscription ends when the component is dropped.
+
+use dioxus::prelude::*;
+use life_engine::events::GenerationHooks;
+use life_engine::universe::{Changes, TickStats};
+
+/// The board's hooks, as provided by `GameOfLife`.
+pub type Hooks = CopyValue<GenerationHooks>;
+
+/// Call `hook` with the generation number and its stats after every
+/// generation of the board this component is under. Hooks mustn't
+/// subscribe or unsubscribe from inside the call.
+pub fn use_on_generation(hook: impl FnMut(u64, TickStats) + 'static) {
+    let mut hooks = use_context::<Hooks>();
+    let id = use_hook(|| hooks.write().on_generation(hook));
+    use_drop(move || {
+        hooks.write().remove(id);
+    });
+}
+
+/// Call `hook` with the generation number and the cells it changed after
+/// every generation of the board this component is under.
+pub fn use_on_changes(hook: impl FnMut(u64, &Changes) + 'static) {
+    let mut hooks = use_context::<Hooks>();
+    let id = use_hook(|| hooks.write().on_changes(hook));
+    use_drop(move || {
+        hooks.write().remove(id);
+    });
+}
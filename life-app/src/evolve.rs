@@ -0,0 +1,284 @@
+// Evolutionary pattern search
+//
+// Experimental: breeds small seed patterns towards a goal – living long,
+// growing big, or reaching a far-off target cell. Each round every seed in
+// the pool is run headlessly on the infinite plane and scored; the best few
+// survive unchanged and the rest of the next pool is bred from them by
+// crossover and mutation. Like the soup search, work is done in slices, from
+// its own timer, so it carries on while the board is paused.
+
+use std::cmp::Reverse;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_time::use_interval;
+use life_engine::rng::Rng;
+use life_engine::rule::Rule;
+use life_engine::sparse::SparsePlane;
+use life_engine::universe::{random_seed, CellState, Universe};
+
+use crate::i18n::{Language, Strings};
+use crate::search::{settled, SETTLE_WINDOW};
+
+/// Edge length of the seed patterns; a seed is one bit per cell of a `u64`.
+pub const EDGE: usize = 8;
+/// Seeds per round.
+const POOL: usize = 24;
+/// Best seeds carried over into the next round unchanged.
+const ELITE: usize = 6;
+/// Chance of each cell of a bred seed flipping.
+const MUTATION_RATE: f64 = 1.5 / 64.0;
+/// Generations a seed may run before it is scored anyway.
+const MAX_GENERATIONS: u64 = 1000;
+/// Generations of work per slice, and how often slices run.
+const BUDGET: u64 = 200;
+const SLICE_MS: u64 = 50;
+
+/// What the search breeds for.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Goal {
+    /// Longest time before settling or dying out.
+    Longevity,
+    /// Highest population reached.
+    Growth,
+    /// A live cell at `(row, col)`, counted from the seed's top-left
+    /// corner, as early as possible.
+    Reach { row: i32, col: i32 },
+}
+
+impl Goal {
+    pub fn key(self) -> &'static str {
+        match self {
+            Goal::Longevity => "longevity",
+            Goal::Growth => "growth",
+            Goal::Reach { .. } => "reach",
+        }
+    }
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Goal::Longevity => text.goal_longevity,
+            Goal::Growth => text.goal_growth,
+            Goal::Reach { .. } => text.goal_reach,
+        }
+    }
+}
+
+/// A scored seed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Candidate {
+    pub genome: u64,
+    /// Higher is better; what it counts depends on the goal.
+    pub fitness: i64,
+}
+
+/// The seed being run right now.
+#[derive(Clone, PartialEq, Debug)]
+struct Trial {
+    genome: u64,
+    plane: SparsePlane,
+    generation: u64,
+    populations: Vec<usize>,
+    peak: usize,
+    /// Chebyshev distance from the target to the nearest live cell so far.
+    closest: i32,
+    reached: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Evolution {
+    rng: Rng,
+    rule: Rule,
+    pub goal: Goal,
+    pool: Vec<u64>,
+    scored: Vec<Candidate>,
+    current: Option<Trial>,
+    /// Rounds bred so far.
+    pub round: u64,
+    pub evaluated: u64,
+    pub best: Option<Candidate>,
+}
+
+impl Evolution {
+    pub fn new(seed: u64, rule: Rule, goal: Goal) -> Self {
+        let mut rng = Rng::new(seed);
+        let pool = (0..POOL).map(|_| rng.next_u64()).collect();
+        Self { rng, rule, goal, pool, scored: Vec::new(), current: None, round: 0, evaluated: 0, best: None }
+    }
+
+    /// The cells of `genome`, as `(row, col)` from its top-left corner.
+    pub fn cells(genome: u64) -> impl Iterator<Item = (i32, i32)> {
+        (0..EDGE * EDGE).filter(move |i| genome >> i & 1 == 1).map(|i| ((i / EDGE) as i32, (i % EDGE) as i32))
+    }
+
+    /// Replace `universe` with `genome`, centred on the board.
+    pub fn load(genome: u64, universe: &mut Universe) {
+        universe.cells_mut().fill(false);
+        let top = universe.height.saturating_sub(EDGE) / 2;
+        let left = universe.width.saturating_sub(EDGE) / 2;
+        for (row, col) in Self::cells(genome) {
+            let _ = universe.set(top + row as usize, left + col as usize, CellState::Alive);
+        }
+    }
+
+    /// Spend up to `budget` generations of work, breeding a new round
+    /// whenever the pool has been scored.
+    pub fn advance(&mut self, mut budget: u64) {
+        while budget > 0 {
+            if self.current.is_none() && self.scored.len() == self.pool.len() {
+                self.breed();
+            }
+            let (rule, goal) = (self.rule, self.goal);
+            let genome = self.pool[self.scored.len()];
+            let trial = self.current.get_or_insert_with(|| Trial {
+                genome,
+                plane: SparsePlane::new(Self::cells(genome), rule),
+                generation: 0,
+                populations: Vec::new(),
+                peak: 0,
+                closest: i32::MAX,
+                reached: None,
+            });
+
+            trial.plane.step();
+            trial.generation += 1;
+            budget -= 1;
+            let population = trial.plane.population();
+            trial.populations.push(population);
+            trial.peak = trial.peak.max(population);
+            if let Goal::Reach { row, col } = goal {
+                let nearest = trial.plane.cells().map(|(r, c)| (r - row).abs().max((c - col).abs())).min();
+                trial.closest = trial.closest.min(nearest.unwrap_or(i32::MAX));
+                if trial.closest == 0 && trial.reached.is_none() {
+                    trial.reached = Some(trial.generation);
+                }
+            }
+
+            let extinct = population == 0;
+            let done = extinct
+                || settled(&trial.populations)
+                || trial.reached.is_some()
+                || trial.generation >= MAX_GENERATIONS;
+            if done {
+                let fitness = match goal {
+                    Goal::Longevity if extinct || trial.generation >= MAX_GENERATIONS => trial.generation as i64,
+                    Goal::Longevity => trial.generation.saturating_sub(SETTLE_WINDOW as u64) as i64,
+                    Goal::Growth => trial.peak as i64,
+                    // Any hit beats every miss; earlier hits beat later ones.
+                    Goal::Reach { .. } => match trial.reached {
+                        Some(generation) => 1 + (MAX_GENERATIONS - generation) as i64,
+                        None => -(trial.closest.min(i32::MAX - 1) as i64),
+                    },
+                };
+                let candidate = Candidate { genome: trial.genome, fitness };
+                self.current = None;
+                self.evaluated += 1;
+                self.scored.push(candidate);
+                if self.best.is_none_or(|best| fitness > best.fitness) {
+                    self.best = Some(candidate);
+                }
+            }
+        }
+    }
+
+    /// Next round: the elite, plus children of random pairs of them.
+    fn breed(&mut self) {
+        self.scored.sort_by_key(|candidate| Reverse(candidate.fitness));
+        let elite: Vec<u64> = self.scored.iter().take(ELITE).map(|candidate| candidate.genome).collect();
+        let mut pool = elite.clone();
+        while pool.len() < POOL {
+            let a = elite[self.rng.next_u64() as usize % elite.len()];
+            let b = elite[self.rng.next_u64() as usize % elite.len()];
+            let mask = self.rng.next_u64();
+            let mut child = (a & mask) | (b & !mask);
+            for bit in 0..EDGE * EDGE {
+                if self.rng.gen_bool(MUTATION_RATE) {
+                    child ^= 1 << bit;
+                }
+            }
+            pool.push(child);
+        }
+        self.pool = pool;
+        self.scored.clear();
+        self.round += 1;
+    }
+}
+
+/// Controls for an evolutionary search under `rule`. "Load best" hands the
+/// best seed so far to `on_load`.
+#[component]
+pub fn EvolutionPanel(rule: Rule, on_load: EventHandler<u64>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut evolution = use_signal(|| None::<Evolution>);
+    let mut goal = use_signal(|| Goal::Longevity);
+    let mut target = use_signal(|| (0, 40));
+
+    use_interval(Duration::from_millis(SLICE_MS), move |_| {
+        if let Some(evolution) = evolution.write().as_mut() {
+            evolution.advance(BUDGET);
+        }
+    });
+
+    let running = evolution.read().is_some();
+    let (target_row, target_col) = target();
+
+    rsx! {
+        div { class: "controls",
+            strong { "{text.evolve_patterns}" }
+            select {
+                aria_label: text.goal,
+                disabled: running,
+                onchange: move |evt| {
+                    let (row, col) = target();
+                    goal.set(match evt.value().as_str() {
+                        "growth" => Goal::Growth,
+                        "reach" => Goal::Reach { row, col },
+                        _ => Goal::Longevity,
+                    });
+                },
+                for option in [Goal::Longevity, Goal::Growth, Goal::Reach { row: target_row, col: target_col }] {
+                    option { value: option.key(), selected: goal().key() == option.key(), {option.label(text)} }
+                }
+            }
+            if let Goal::Reach { .. } = goal() {
+                label {
+                    "{text.target_cell} "
+                    input {
+                        r#type: "number",
+                        aria_label: text.target_row,
+                        disabled: running,
+                        value: "{target_row}",
+                        oninput: move |evt| {
+                            if let Ok(row) = evt.value().parse() {
+                                target.write().0 = row;
+                                goal.set(Goal::Reach { row, col: target().1 });
+                            }
+                        },
+                    }
+                    input {
+                        r#type: "number",
+                        aria_label: text.target_col,
+                        disabled: running,
+                        value: "{target_col}",
+                        oninput: move |evt| {
+                            if let Ok(col) = evt.value().parse() {
+                                target.write().1 = col;
+                                goal.set(Goal::Reach { row: target().0, col });
+                            }
+                        },
+                    }
+                }
+            }
+            button {
+                onclick: move |_| evolution.set((!running).then(|| Evolution::new(random_seed(), rule, goal()))),
+                if running { "{text.stop_evolution}" } else { "{text.start_evolution}" }
+            }
+            if let Some(evolution) = evolution() {
+                span { {(text.evolution_status)(evolution.round, evolution.evaluated, evolution.best.map(|best| best.fitness))} }
+                if let Some(best) = evolution.best {
+                    button { onclick: move |_| on_load.call(best.genome), "{text.load_best}" }
+                }
+            }
+        }
+    }
+}
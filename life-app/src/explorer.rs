@@ -0,0 +1,223 @@
+// Rule explorer
+//
+// "Surprise me" picks a random B/S rule, reseeds the soup and runs it. Every
+// rule tried is remembered, so ←/→ walk back and forth through them like a
+// browser's history, and ☆ stars the current rule as a keeper. Starred rules
+// are saved and listed for one-click revisiting.
+//
+// Each surprise rule is also scored on a headless test soup: rules whose
+// soups die out, fill the board, freeze solid or boil into noise score low,
+// ones that keep a moderate amount of activity going score high. The best
+// scorers of the session make a leaderboard.
+
+use dioxus::prelude::*;
+use life_engine::rng::Rng;
+use life_engine::rule::Rule;
+use life_engine::universe::{random_seed, UniverseBuilder};
+
+use crate::autosave;
+use crate::i18n::Language;
+
+/// Edge length of the test soup rules are scored on, in cells.
+const TEST_SIZE: usize = 64;
+/// Generations the test soup is run for.
+const TEST_GENERATIONS: usize = 256;
+/// Final generations whose activity is averaged.
+const TEST_TAIL: usize = 64;
+/// Same soup for every rule, so scores compare.
+const TEST_SEED: u64 = 0x5EED;
+/// Entries kept on the leaderboard.
+const TOP: usize = 10;
+
+/// How a rule's test soup ended up.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Verdict {
+    Extinct,
+    /// Most of the board alive.
+    Exploded,
+    /// (Almost) nothing changing any more.
+    Frozen,
+    /// So much flipping that it is effectively noise.
+    Chaotic,
+    Lively,
+}
+
+/// A rule's score, 0–100, with the verdict behind it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Score {
+    pub points: u8,
+    pub verdict: Verdict,
+}
+
+/// Run `rule` on the test soup and score it. Activity is the share of the
+/// board flipping per generation over the last `TEST_TAIL` generations; it
+/// scores best between 5 % and 15 % and tails off towards frozen or noisy.
+/// The final density scores best below 40 %, falling to nothing at 80 %.
+pub fn score(rule: Rule) -> Score {
+    let mut board = UniverseBuilder::new().size(TEST_SIZE, TEST_SIZE).seed(TEST_SEED).rule(rule).build();
+    // Only the tail's activity counts, so the rest goes in one run.
+    board.step_n((TEST_GENERATIONS - TEST_TAIL) as u64);
+    let mut flips = 0;
+    for _ in 0..TEST_TAIL {
+        let stats = board.tick();
+        flips += stats.births + stats.deaths;
+    }
+    let cells = (TEST_SIZE * TEST_SIZE) as f64;
+    let activity = flips as f64 / (TEST_TAIL as f64 * cells);
+    let density = board.population() as f64 / cells;
+
+    let verdict = if density == 0.0 {
+        Verdict::Extinct
+    } else if density > 0.6 {
+        Verdict::Exploded
+    } else if activity < 0.001 {
+        Verdict::Frozen
+    } else if activity > 0.3 {
+        Verdict::Chaotic
+    } else {
+        Verdict::Lively
+    };
+    let activity_score = match activity {
+        a if a < 0.05 => a / 0.05,
+        a if a <= 0.15 => 1.0,
+        a => (1.0 - (a - 0.15) / 0.25).max(0.0),
+    };
+    let density_score = if density <= 0.4 { 1.0 } else { (1.0 - (density - 0.4) / 0.4).max(0.0) };
+    let points = if verdict == Verdict::Extinct { 0 } else { (100.0 * activity_score * density_score).round() as u8 };
+    Score { points, verdict }
+}
+
+/// The rules tried so far, which one is on the board, and the best scorers.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Explorer {
+    tried: Vec<(Rule, Score)>,
+    at: usize,
+    /// Highest score first, each rule once.
+    pub leaderboard: Vec<(Rule, Score)>,
+}
+
+impl Explorer {
+    /// Try a fresh random rule. Rules "ahead" of the current one are
+    /// dropped, as they would be after going back in a browser.
+    pub fn surprise(&mut self, rng: &mut Rng) -> Rule {
+        let rule = Rule::random(rng);
+        let score = score(rule);
+        self.tried.truncate(self.at + 1);
+        self.tried.push((rule, score));
+        self.at = self.tried.len() - 1;
+        if !self.leaderboard.iter().any(|&(ranked, _)| ranked == rule) {
+            let at = self.leaderboard.iter().position(|(_, other)| other.points < score.points).unwrap_or(self.leaderboard.len());
+            self.leaderboard.insert(at, (rule, score));
+            self.leaderboard.truncate(TOP);
+        }
+        rule
+    }
+
+    pub fn back(&mut self) -> Option<Rule> {
+        self.at = self.at.checked_sub(1)?;
+        Some(self.tried[self.at].0)
+    }
+
+    pub fn forward(&mut self) -> Option<Rule> {
+        let next = self.at + 1;
+        let (rule, _) = *self.tried.get(next)?;
+        self.at = next;
+        Some(rule)
+    }
+
+    /// The score of `rule`, if it was tried here.
+    pub fn score_of(&self, rule: Rule) -> Option<Score> {
+        self.tried.iter().find(|&&(tried, _)| tried == rule).map(|&(_, score)| score)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.at > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.at + 1 < self.tried.len()
+    }
+}
+
+/// Explorer controls for a board running `rule`. Rules to try go to
+/// `on_try`, which should reseed and run the board with them; `starred` is
+/// the starred rules, which starring saves.
+#[component]
+pub fn RuleExplorer(rule: Rule, starred: Signal<Vec<Rule>>, on_try: EventHandler<Rule>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut explorer = use_signal(Explorer::default);
+    let is_starred = starred.read().contains(&rule);
+    let current_score = explorer.read().score_of(rule);
+    let leaderboard = explorer.read().leaderboard.clone();
+
+    rsx! {
+        span { class: "rule-explorer",
+            button {
+                onclick: move |_| {
+                    let rule = explorer.write().surprise(&mut Rng::new(random_seed()));
+                    on_try.call(rule);
+                },
+                "{text.surprise_me}"
+            }
+            button {
+                aria_label: text.previous_rule,
+                disabled: !explorer.read().can_go_back(),
+                onclick: move |_| {
+                    if let Some(rule) = explorer.write().back() {
+                        on_try.call(rule);
+                    }
+                },
+                "←"
+            }
+            button {
+                aria_label: text.next_rule,
+                disabled: !explorer.read().can_go_forward(),
+                onclick: move |_| {
+                    if let Some(rule) = explorer.write().forward() {
+                        on_try.call(rule);
+                    }
+                },
+                "→"
+            }
+            button {
+                aria_label: text.star_rule,
+                aria_pressed: is_starred,
+                onclick: move |_| {
+                    let mut rules = starred.write();
+                    match rules.iter().position(|&kept| kept == rule) {
+                        Some(position) => {
+                            rules.remove(position);
+                        }
+                        None => rules.push(rule),
+                    }
+                    autosave::save_starred_rules(&rules);
+                },
+                if is_starred { "★" } else { "☆" }
+            }
+            if let Some(score) = current_score {
+                span { {(text.rule_score)(score.points, score.verdict)} }
+            }
+            if !starred.read().is_empty() {
+                span { class: "starred-rules", "{text.starred_rules} "
+                    for kept in starred() {
+                        button { key: "{kept}", disabled: kept == rule, onclick: move |_| on_try.call(kept), "{kept}" }
+                    }
+                }
+            }
+        }
+        if !leaderboard.is_empty() {
+            details { class: "rule-leaderboard",
+                summary { "{text.promising_rules}" }
+                ol {
+                    for (ranked, score) in leaderboard {
+                        li { key: "{ranked}",
+                            button { disabled: ranked == rule, onclick: move |_| on_try.call(ranked), "{ranked}" }
+                            " "
+                            {(text.rule_score)(score.points, score.verdict)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
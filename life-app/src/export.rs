@@ -0,0 +1,198 @@
+// Import and export panels
+//
+// Getting the board in and out as text: plaintext (`.`/`O`) and the packed
+// format through one box, and whole sessions as JSON files. Image seeds come
+// in beside them, with the settings for how dropped and pasted patterns land.
+// The panels hold only their own input and errors; what loading, placing or
+// exporting does to the board is left to the board through their handlers.
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+use life_engine::universe::{MergeMode, Universe};
+use life_formats::{packed, PatternText};
+
+use crate::i18n::{Language, Localized};
+use crate::keys::{Binding, Command, Keymap};
+
+/// The text box with plaintext and packed export, loading and placing, and
+/// session export and import. `ascii` is shared with the board, whose
+/// palette actions export into it too; `bundle_error` is why the last
+/// session export or import failed.
+#[component]
+pub fn ExportPanel(
+    mut ascii: Signal<String>,
+    mut ascii_trim: Signal<bool>,
+    universe: Signal<Universe>,
+    bundle_error: Option<String>,
+    on_export_text: EventHandler,
+    on_load_board: EventHandler<Universe>,
+    on_export_session: EventHandler,
+    on_import_session: EventHandler<String>,
+    on_place: EventHandler<Pattern>,
+) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut ascii_error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "controls",
+            textarea {
+                class: "ascii",
+                rows: 6,
+                cols: 40,
+                placeholder: text.ascii_placeholder,
+                aria_label: text.pattern_text,
+                value: "{ascii}",
+                oninput: move |evt| ascii.set(evt.value()),
+            }
+            button { onclick: move |_| on_export_text.call(()), "{text.export_text}" }
+            button { onclick: move |_| ascii.set(packed::to_text(&universe.read())), "{text.export_packed}" }
+            button {
+                onclick: move |_| match packed::from_text(&ascii()) {
+                    Ok(board) => {
+                        on_load_board.call(board);
+                        ascii_error.set(None);
+                    }
+                    Err(err) => ascii_error.set(Some(err)),
+                },
+                "{text.load_packed}"
+            }
+            button { onclick: move |_| on_export_session.call(()), "{text.export_session}" }
+            label {
+                "{text.import_session} "
+                input {
+                    r#type: "file",
+                    accept: ".json",
+                    onchange: move |evt| async move {
+                        let Some(files) = evt.files() else { return };
+                        for name in files.files() {
+                            if let Some(text) = files.read_file_to_string(&name).await {
+                                on_import_session.call(text);
+                            }
+                        }
+                    },
+                }
+            }
+            if let Some(err) = bundle_error {
+                span { class: "error", role: "alert", "{err}" }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: ascii_trim(),
+                    onchange: move |evt| ascii_trim.set(evt.checked()),
+                }
+                " {text.trim}"
+            }
+            button {
+                onclick: move |_| match Pattern::parse_plaintext(&ascii()) {
+                    Ok(pattern) => {
+                        on_place.call(pattern);
+                        ascii_error.set(None);
+                    }
+                    Err(err) => ascii_error.set(Some(err)),
+                },
+                "{text.place_text}"
+            }
+            if let Some(err) = ascii_error() {
+                span { class: "error", role: "alert", "{err}" }
+            }
+        }
+    }
+}
+
+/// Image seeding with its threshold, and how placed and pasted patterns
+/// combine with the board. `on_image` gets a chosen image's bytes and
+/// `on_threshold` rethresholds the kept one; `image_error` and `file_error`
+/// are why the last image, or the last dropped or pasted pattern, couldn't be
+/// read.
+#[component]
+pub fn ImportPanel(
+    mut image_luma: Signal<Option<Vec<u8>>>,
+    mut image_level: Signal<u8>,
+    mut image_invert: Signal<bool>,
+    image_error: Option<String>,
+    mut merge_mode: Signal<MergeMode>,
+    mut snap_parity: Signal<bool>,
+    pasting: bool,
+    file_error: Option<String>,
+    on_image: EventHandler<Vec<u8>>,
+    on_threshold: EventHandler,
+) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let keymap = use_context::<Signal<Keymap>>();
+    let shortcut = move |command| keymap.read().binding(command).map(Binding::to_string);
+
+    rsx! {
+        div { class: "controls",
+            label {
+                "{text.seed_from_image} "
+                input {
+                    r#type: "file",
+                    accept: ".png,.jpg,.jpeg",
+                    onchange: move |evt| async move {
+                        let Some(files) = evt.files() else { return };
+                        for name in files.files() {
+                            if let Some(bytes) = files.read_file(&name).await {
+                                on_image.call(bytes);
+                            }
+                        }
+                    },
+                }
+            }
+            if image_luma.read().is_some() {
+                label {
+                    "{text.threshold} "
+                    input {
+                        r#type: "range",
+                        min: 0,
+                        max: 255,
+                        value: "{image_level}",
+                        oninput: move |evt| {
+                            if let Ok(level) = evt.value().parse() {
+                                image_level.set(level);
+                                on_threshold.call(());
+                            }
+                        },
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: image_invert(),
+                        onchange: move |evt| {
+                            image_invert.set(evt.checked());
+                            on_threshold.call(());
+                        },
+                    }
+                    " {text.dark_cells_live}"
+                }
+                button { onclick: move |_| image_luma.set(None), "{text.forget_image}" }
+            }
+            if let Some(err) = image_error {
+                span { class: "error", role: "alert", "{err}" }
+            }
+            span { {(text.drop_hint)(shortcut(Command::Paste).as_deref())} }
+            select {
+                aria_label: text.paste_mode,
+                onchange: move |evt| merge_mode.set(MergeMode::from_key(&evt.value())),
+                for option in MergeMode::ALL {
+                    option { value: option.key(), selected: merge_mode() == option, {option.label(text)} }
+                }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: snap_parity(),
+                    onchange: move |evt| snap_parity.set(evt.checked()),
+                }
+                " {text.snap_parity} "
+            }
+            if pasting {
+                span { {(text.paste_hint)(shortcut(Command::Cancel).as_deref())} }
+            }
+            if let Some(err) = file_error {
+                span { class: "error", role: "alert", "{err}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+// Community gallery
+//
+// With the `fullstack` feature the app talks to its own server, which keeps a
+// shared gallery of patterns: upload the current selection under a name, browse
+// and search what others uploaded, like the good ones and load any of them to
+// stamp like a paste. The server functions below run on the `server` build
+// and are plain HTTP calls from the client.
+//
+// Submissions are checked twice with the same `validate`: on the client, for
+// an immediate message, and on the server, which trusts nothing it is sent.
+// The server stores the re-encoded RLE rather than the text it received, and
+// keeps the gallery in `gallery.json` in its working directory, up to
+// `MAX_SUBMISSIONS` of them. The file is written by a thread of its own a
+// moment after a change, so requests never wait on the disk and a burst of
+// likes costs one write.
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+use life_engine::thumbnail::Thumbnail;
+use life_formats::PatternText;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Language;
+use crate::library::{self, THUMBNAIL_SIZE};
+
+/// Longest name or author name accepted, in characters.
+const MAX_NAME: usize = 60;
+/// Largest RLE upload accepted, in bytes.
+const MAX_RLE_BYTES: usize = 32 * 1024;
+/// Largest pattern accepted, per side.
+const MAX_SIDE: usize = 256;
+/// Most submissions the gallery keeps; uploads are refused once it's full.
+#[cfg(feature = "server")]
+const MAX_SUBMISSIONS: usize = 10_000;
+
+/// One pattern in the gallery.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Submission {
+    pub id: u64,
+    pub name: String,
+    pub author: String,
+    pub rle: String,
+    pub likes: u32,
+}
+
+/// Check an upload, returning the pattern it describes.
+pub fn validate(name: &str, author: &str, rle: &str) -> Result<Pattern, String> {
+    if name.trim().is_empty() {
+        return Err("the pattern needs a name".to_string());
+    }
+    if name.chars().count() > MAX_NAME || author.chars().count() > MAX_NAME {
+        return Err(format!("names are limited to {MAX_NAME} characters"));
+    }
+    if rle.len() > MAX_RLE_BYTES {
+        return Err(format!("RLE is limited to {} KiB", MAX_RLE_BYTES / 1024));
+    }
+    // `parse_rle` refuses anything too big to allocate; the gallery's own
+    // limit is much smaller.
+    let pattern = Pattern::parse_rle(rle)?;
+    if pattern.width > MAX_SIDE || pattern.height > MAX_SIDE {
+        return Err(format!("patterns are limited to {MAX_SIDE}×{MAX_SIDE} cells"));
+    }
+    let pattern = pattern.trimmed();
+    if !pattern.cells.contains(&true) {
+        return Err("the pattern is empty".to_string());
+    }
+    Ok(pattern)
+}
+
+#[cfg(feature = "server")]
+mod store {
+    use std::sync::mpsc::{self, Sender};
+    use std::sync::{LazyLock, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::Submission;
+
+    const FILE: &str = "gallery.json";
+    /// Submissions returned by one browse.
+    pub const PAGE: usize = 50;
+    /// How long the saver waits after a change for more to come, so they're
+    /// written together.
+    const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+    pub static GALLERY: LazyLock<Mutex<Vec<Submission>>> =
+        LazyLock::new(|| Mutex::new(std::fs::read_to_string(FILE).ok().and_then(|text| serde_json::from_str(&text).ok()).unwrap_or_default()));
+
+    /// The saver thread, woken once per change.
+    static SAVER: LazyLock<Sender<()>> = LazyLock::new(|| {
+        let (changed, changes) = mpsc::channel();
+        thread::spawn(move || {
+            while changes.recv().is_ok() {
+                thread::sleep(SAVE_DELAY);
+                while changes.try_recv().is_ok() {}
+                save();
+            }
+        });
+        changed
+    });
+
+    /// Have the gallery written out shortly, with every change made so far.
+    pub fn save_soon() {
+        let _ = SAVER.send(());
+    }
+
+    /// Write the gallery out; written then renamed, like the autosave. The
+    /// lock is held only while it's serialized, not while it's written.
+    fn save() {
+        let Ok(text) = GALLERY.lock().map(|gallery| serde_json::to_string(&*gallery).expect("submissions always serialize")) else { return };
+        let partial = format!("{FILE}.tmp");
+        if std::fs::write(&partial, text).is_ok() {
+            let _ = std::fs::rename(partial, FILE);
+        }
+    }
+
+    /// Whether every word of `query` is in the name or the author's name.
+    pub fn matches(submission: &Submission, query: &str) -> bool {
+        let (name, author) = (submission.name.to_lowercase(), submission.author.to_lowercase());
+        query.to_lowercase().split_whitespace().all(|word| name.contains(word) || author.contains(word))
+    }
+}
+
+/// The most liked submissions matching `query`, newest first among equals.
+#[server]
+pub async fn browse(query: String) -> Result<Vec<Submission>, ServerFnError> {
+    let gallery = store::GALLERY.lock().map_err(|_| ServerFnError::new("gallery unavailable"))?;
+    let mut found: Vec<Submission> = gallery.iter().filter(|submission| store::matches(submission, &query)).cloned().collect();
+    found.sort_by(|a, b| b.likes.cmp(&a.likes).then(b.id.cmp(&a.id)));
+    found.truncate(store::PAGE);
+    Ok(found)
+}
+
+/// Add a pattern to the gallery.
+#[server]
+pub async fn upload(name: String, author: String, rle: String) -> Result<Submission, ServerFnError> {
+    let mut pattern = validate(&name, &author, &rle).map_err(ServerFnError::new)?;
+    pattern.name = Some(name.trim().to_string());
+    let mut gallery = store::GALLERY.lock().map_err(|_| ServerFnError::new("gallery unavailable"))?;
+    if gallery.len() >= MAX_SUBMISSIONS {
+        return Err(ServerFnError::new("the gallery is full"));
+    }
+    let id = gallery.iter().map(|submission| submission.id + 1).max().unwrap_or(0);
+    let submission = Submission { id, name: name.trim().to_string(), author: author.trim().to_string(), rle: pattern.to_rle(), likes: 0 };
+    gallery.push(submission.clone());
+    store::save_soon();
+    Ok(submission)
+}
+
+/// Like submission `id`, returning its new count.
+#[server]
+pub async fn like(id: u64) -> Result<u32, ServerFnError> {
+    let mut gallery = store::GALLERY.lock().map_err(|_| ServerFnError::new("gallery unavailable"))?;
+    let submission = gallery.iter_mut().find(|submission| submission.id == id).ok_or_else(|| ServerFnError::new("no such pattern"))?;
+    submission.likes = submission.likes.saturating_add(1);
+    let likes = submission.likes;
+    store::save_soon();
+    Ok(likes)
+}
+
+/// Gallery controls. `selection` is what "Upload" would share; loaded
+/// patterns go to `on_load`.
+#[component]
+pub fn GalleryPanel(selection: Option<Pattern>, on_load: EventHandler<Pattern>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut name = use_signal(String::new);
+    let mut author = use_signal(String::new);
+    let mut query = use_signal(String::new);
+    // Outcome of the last upload or like.
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    // Submissions liked this session, so each gets one like.
+    let mut liked = use_signal(Vec::<u64>::new);
+    let mut listing = use_resource(move || async move { browse(query()).await.map_err(|err| err.to_string()) });
+
+    let can_share = selection.is_some();
+    let share = move |_| {
+        let Some(pattern) = &selection else { return };
+        let (name, author) = (name.peek().trim().to_string(), author.peek().trim().to_string());
+        let rle = pattern.trimmed().to_rle();
+        if let Err(err) = validate(&name, &author, &rle) {
+            status.set(Some(Err(err)));
+            return;
+        }
+        spawn(async move {
+            match upload(name, author, rle).await {
+                Ok(submission) => {
+                    status.set(Some(Ok((text.gallery_uploaded)(&submission.name))));
+                    listing.restart();
+                }
+                Err(err) => status.set(Some(Err(err.to_string()))),
+            }
+        });
+    };
+
+    rsx! {
+        details { class: "library",
+            summary { "{text.gallery}" }
+            div { class: "controls",
+                input {
+                    placeholder: text.library_name,
+                    aria_label: text.library_name,
+                    value: "{name}",
+                    oninput: move |evt| name.set(evt.value()),
+                }
+                input {
+                    placeholder: text.gallery_author,
+                    aria_label: text.gallery_author,
+                    value: "{author}",
+                    oninput: move |evt| author.set(evt.value()),
+                }
+                button { disabled: !can_share, title: text.library_needs_selection, onclick: share, "{text.gallery_upload}" }
+                input {
+                    r#type: "search",
+                    placeholder: text.gallery_search,
+                    aria_label: text.gallery_search,
+                    value: "{query}",
+                    oninput: move |evt| query.set(evt.value()),
+                }
+                match status() {
+                    Some(Ok(message)) => rsx! { span { role: "status", "{message}" } },
+                    Some(Err(err)) => rsx! { span { class: "error", role: "alert", "{err}" } },
+                    None => rsx! {},
+                }
+            }
+            match &*listing.read() {
+                None => rsx! { p { "{text.downloading}" } },
+                Some(Err(err)) => rsx! { p { class: "error", role: "alert", "{err}" } },
+                Some(Ok(found)) if found.is_empty() => rsx! { p { "{text.gallery_empty}" } },
+                Some(Ok(found)) => rsx! {
+                    ul { class: "library-entries",
+                        for submission in found.clone() {
+                            li { key: "{submission.id}",
+                                if let Ok(pattern) = Pattern::parse_rle(&submission.rle) {
+                                    button {
+                                        title: text.stamp_from_library,
+                                        onclick: {
+                                            let pattern = pattern.clone();
+                                            move |_| on_load.call(pattern.clone())
+                                        },
+                                        img {
+                                            class: "thumbnail",
+                                            src: library::thumbnail_url(&Thumbnail::of_pattern(&pattern, THUMBNAIL_SIZE)),
+                                            alt: "",
+                                        }
+                                        " {submission.name}"
+                                    }
+                                }
+                                if !submission.author.is_empty() {
+                                    span { {(text.pattern_author)(&submission.author)} }
+                                }
+                                button {
+                                    aria_label: text.gallery_like,
+                                    disabled: liked.read().contains(&submission.id),
+                                    onclick: move |_| {
+                                        let id = submission.id;
+                                        liked.write().push(id);
+                                        spawn(async move {
+                                            match like(id).await {
+                                                Ok(_) => listing.restart(),
+                                                Err(err) => status.set(Some(Err(err.to_string()))),
+                                            }
+                                        });
+                                    },
+                                    "♥ {submission.likes}"
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,1920 @@
+// Game of Life component
+//
+// The whole app – board, controls and panels – as one component. `main.rs`
+// only wraps it, and other Dioxus apps can drop it into their own rsx:
+//
+//     GameOfLife { width: 64, height: 64, rule: Rule::CONWAY, cell_size: 6, autoplay: false }
+//
+// Apart from `cell_size`, the props only seed the initial state; the board's
+// own controls take over from there, and an embedding app that wants to
+// start over with new ones gives the component a new `key`, as the seed grid
+// does when its rule changes.
+//
+// The board owns the state a generation or an edit touches. The panels round
+// it (history, tools, analysis, import and export, races and searches) live
+// with their features as child components, handed the signals they show and
+// handlers for anything that has to go through the board.
+
+use dioxus::html::geometry::ElementPoint;
+use dioxus::html::input_data::MouseButton;
+use dioxus::html::{FileEngine, HasFileData};
+use dioxus::prelude::*;
+use dioxus_time::{use_interval};
+use life_engine::events::GenerationHooks;
+use life_engine::pattern::Pattern;
+use life_engine::rule::{Rule, PRESETS};
+use life_engine::stepper::Stepper;
+use life_engine::thumbnail::Thumbnail;
+use life_engine::universe::{random_seed, Boundary, CellState, Changes, MergeMode, SetOp, TickStats, Universe, UniverseBuilder};
+use life_formats::{packed, PatternText};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{autosave, bundle, census, clipboard, describe, image_import, motion, patterns, power, speed, theme};
+use crate::annotations::{Annotation, AnnotationLayer, AnnotationList};
+use crate::audio::{self, Sonifier};
+use crate::autosave::Session;
+use crate::bench::BenchmarkPanel;
+use crate::board::{BoardRow, BoardView, CellLook, RowLook};
+use crate::bundle::Bundle;
+use crate::census::CensusPanel;
+use crate::chart::Sparkline;
+use crate::context_menu::{self, CellAction, ContextMenu, Neighborhood, NeighborhoodView};
+use crate::events::{use_on_changes, use_on_generation, Hooks};
+use crate::evolve::{Evolution, EvolutionPanel};
+use crate::export::{ExportPanel, ImportPanel};
+use crate::explorer::RuleExplorer;
+use crate::history::{Change, History, HistoryPanel};
+use crate::i18n::{Language, Localized};
+use crate::inspector::InspectorPanel;
+use crate::keys::{Binding, Command, Keymap, ShortcutSettings};
+#[cfg(feature = "fullstack")]
+use crate::gallery::GalleryPanel;
+use crate::gpu::GpuRun;
+use crate::guides::{self, GuideLayer, Guides};
+use crate::library::{self, LibraryPanel};
+use crate::logs::LogConsole;
+use crate::measure::{Ruler, RulerLayer, Stopwatch};
+use crate::minimap::Minimap;
+use crate::overlay::{diff_color, Heatmap, Overlay, Trails};
+use crate::palette::{Action, CommandPalette};
+use crate::patterns::PatternSearch;
+use crate::predecessor::Outcome;
+use crate::profile::Profile;
+use crate::race::{Race, RacePanel};
+use crate::render::{CellShape, GridLines, RenderOptions, CELL_GAPS, FADE_DURATIONS, GRID_SPACINGS, TRAIL_LENGTHS};
+use crate::rule_picker::RulePicker;
+use crate::search::{SearchPanel, SoupSearch};
+use crate::seed_grid::{SeedGrid, GRID_COUNTS};
+use crate::spacetime::{SpaceTime, SpaceTimeView};
+use crate::speed::{Pacer, Speed};
+use crate::stats::RunStats;
+use crate::tools::{Selection, Tool, ToolsPanel};
+use crate::tooltip::CellTooltip;
+use crate::torus::TorusView;
+use crate::tracker::{Tracker, TrackerPanel, TrackingLayer};
+use crate::viewport::Viewport;
+
+// Static assets bundled by `asset!`
+pub const MAIN_CSS: Asset = asset!("/assets/main.css");
+
+/// Mouse drag in progress on the board.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Drag {
+    /// Drawing: every cell touched is set to this state.
+    Paint(bool),
+    /// Selecting, anchored at this cell.
+    Select((usize, usize)),
+    /// Placing a label at the first cell, its arrow (if the cells differ)
+    /// pointing to the second.
+    Annotate((usize, usize), (usize, usize)),
+    /// Stretching the ruler.
+    Measure,
+}
+
+/// Generations of soup search work done per tick of the UI loop.
+const SEARCH_BUDGET: u64 = 100;
+
+/// Default number of frames the history keeps.
+const HISTORY_FRAMES: usize = 2000;
+/// Default memory cap of the history, in KiB.
+const HISTORY_BUDGET_KIB: usize = 8 * 1024;
+/// Frames between full keyframes in the history.
+const KEYFRAME_EVERY: usize = 64;
+
+/// While running, the screen-reader status line is refreshed this often, in
+/// generations, so it doesn't talk over everything else.
+const ANNOUNCE_EVERY: u64 = 100;
+
+/// Seconds between autosaves.
+const AUTOSAVE_SECS: u64 = 10;
+
+/// Selectable board edge lengths, in cells.
+const BOARD_SIZES: [usize; 3] = [128, 256, 512];
+/// Dead cells kept round the live ones by "Crop to content".
+const CROP_PADDING: usize = 8;
+/// Cells added to a side of a dead-edged board when life reaches it, while
+/// "Grow at the edges" is on.
+const GROW_MARGIN: usize = 32;
+/// Boards don't grow past this many cells across.
+const MAX_GROWN_SIDE: usize = 2048;
+/// Generations per round trip to the GPU when stepping there.
+const GPU_BATCH: u64 = 16;
+/// How often GPU stepping brings the board back to draw, in ms.
+const GPU_SYNC_MS: f64 = 250.0;
+/// Edge length of the rendered window, in cells.
+const VIEW_CELLS: usize = 128;
+
+/// A `width × height` soup under `rule`, drawn at `cell_size` pixels a cell
+/// and running from the start if `autoplay` is set. `slot` picks the autosave
+/// slot, so several boards on one page don't overwrite each other's saves,
+/// and keeps their element ids apart; every board on a page needs its own,
+/// even one that isn't saved because `autosave` is off. With `panels` off the
+/// board comes with its playback, tools and stats only: no seed grid of its
+/// own, no rule explorer, and none of the analysis, pattern, import and
+/// export, race or search panels, as seed-grid boards are shown.
+///
+/// Every prop but `cell_size` is an initial value, read on the first render
+/// only: changing it later does nothing short of a new `key`.
+#[component]
+pub fn GameOfLife(
+    width: usize,
+    height: usize,
+    rule: Rule,
+    cell_size: usize,
+    autoplay: bool,
+    #[props(default)] slot: u64,
+    #[props(default = true)] autosave: bool,
+    #[props(default = true)] panels: bool,
+) -> Element {
+    let _render = tracing::trace_span!("render", slot).entered();
+    // reactive state
+    let mut board_size = use_signal(|| (width, height));
+    let mut universe = use_signal(|| UniverseBuilder::new().size(width, height).rule(rule).build());
+
+    // UI language, shared with child components through context. Inside a
+    // `Workspace` every tab shares the workspace's.
+    let mut language = use_hook(|| {
+        try_consume_context::<Signal<Language>>().unwrap_or_else(|| provide_context(Signal::new(Language::detect())))
+    });
+    // Keyboard shortcuts, likewise shared with the rest of the workspace.
+    let keymap = use_hook(|| {
+        try_consume_context::<Signal<Keymap>>().unwrap_or_else(|| provide_context(Signal::new(Keymap::load())))
+    });
+
+    // Window of cells actually rendered; boards larger than this are panned.
+    let mut viewport = use_signal(|| Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+
+    let delay = use_signal(|| speed::BASE_DELAY);
+
+    // Mouse editing: the active tool, the drag in progress and the selection.
+    let tool = use_signal(Tool::default);
+    let mut annotations = use_signal(Vec::<Annotation>::new);
+    // Text for the next label the annotate tool places.
+    let note_text = use_signal(String::new);
+    let mut ruler = use_signal(|| None::<Ruler>);
+    let stopwatch = use_signal(Stopwatch::default);
+    let mut drag = use_signal(|| None::<Drag>);
+    let mut selection = use_signal(|| None::<Selection>);
+    // Last predecessor search, with the selection it was run on.
+    let mut predecessor = use_signal(|| None::<(Selection, Outcome)>);
+
+    // Split-screen comparison: a second universe from the same seed under `rival_rule`.
+    let mut rival = use_signal(|| None::<Universe>);
+    let mut rival_rule = use_signal(|| Rule::parse("B36/S23").unwrap_or_default());
+    // Whether the command palette is open.
+    let mut palette = use_signal(|| false);
+    // Number of independent seed boards shown under the main one.
+    let mut grid_count = use_signal(|| 0usize);
+
+    // Soup race: two seeded halves compete for territory over `race_length` generations.
+    let mut race = use_signal(|| None::<Race>);
+    let race_length = use_signal(|| 500u64);
+
+    // Background methuselah hunt on the infinite plane.
+    let mut search = use_signal(|| None::<SoupSearch>);
+
+    // Plaintext (`.`/`O`) export and paste-in box.
+    let mut ascii = use_signal(String::new);
+    let ascii_trim = use_signal(|| true);
+    // Why the last session export or import failed.
+    let mut bundle_error = use_signal(|| None::<String>);
+
+    // Image seed: per-pixel luminance scaled to the board, and how it is
+    // thresholded into live cells.
+    let mut image_luma = use_signal(|| None::<Vec<u8>>);
+    let image_level = use_signal(|| 128u8);
+    let image_invert = use_signal(|| false);
+    let mut image_error = use_signal(|| None::<String>);
+    // Why the last dropped pattern file or paste couldn't be read.
+    let mut file_error = use_signal(|| None::<String>);
+    // The last pattern placed, for the context menu's "Stamp here".
+    let mut last_placed = use_signal(|| None::<Pattern>);
+    // Open context menu: its cell and where on the page it was opened.
+    let mut menu = use_signal(|| None::<((usize, usize), (f64, f64))>);
+    // Hover readout and inspector mode, and the cell under the pointer with
+    // the pointer's page position while either is on.
+    let mut show_cell_info = use_signal(|| false);
+    let mut inspector = use_signal(|| false);
+    let mut pointer = use_signal(|| None::<((usize, usize), (f64, f64))>);
+    // Cell whose neighbourhood is on show.
+    let mut inspected = use_signal(|| None::<(usize, usize)>);
+    // Pasted pattern following the mouse, and the cell the mouse is over.
+    let mut floating = use_signal(|| None::<Pattern>);
+    // How placed and pasted patterns combine with the board.
+    let merge_mode = use_signal(MergeMode::default);
+    // Keep placed patterns' corners on even rows and columns.
+    let snap_parity = use_signal(|| false);
+    let mut hover = use_signal(|| None::<(usize, usize)>);
+    // Keyboard editing cursor, when shown.
+    let mut cursor = use_signal(|| None::<(usize, usize)>);
+
+    // Spaceship tracker, and the track the camera follows (by id).
+    let mut tracker = use_signal(|| None::<Tracker>);
+    let mut followed = use_signal(|| None::<u32>);
+
+    // Births/deaths counters for the stats panel.
+    let mut stats = use_signal(RunStats::default);
+
+    // Cosmetic board settings.
+    let mut render = use_signal(RenderOptions::default);
+
+    // Overlay selection and the per-cell flip counts behind the heatmap.
+    let mut overlay = use_signal(Overlay::default);
+    let mut heatmap = use_signal(Heatmap::default);
+    // How long each cell has been dead, behind the trail rendering.
+    let mut trails = use_signal(Trails::default);
+    // Steps the board, skipping tiles that stood still last generation.
+    let mut stepper = use_signal(Stepper::default);
+    // Grow a dead-edged board when its live cells reach an edge.
+    let mut grow_board = use_signal(|| false);
+    // Battery saver: on by default on mobile or battery power.
+    let mut saver = use_signal(|| false);
+    use_hook(|| {
+        spawn(async move {
+            if power::on_battery().await {
+                saver.set(true);
+            }
+        })
+    });
+    // Saved board the diff overlay compares against (falls back to the rival).
+    let mut snapshot = use_signal(|| None::<Universe>);
+    let mut starred_rules = use_signal(|| autosave::load_starred_rules().unwrap_or_default());
+    let mut library_entries = use_signal(library::load);
+
+    // Ambient soundtrack, muted until the user opts in.
+    let mut sonifier = use_signal(Sonifier::default);
+    // No audio in this build, or no device to play it on.
+    let mut no_sound = use_signal(|| !audio::AVAILABLE);
+    // "Life sequencer": live cells of one row play notes each generation.
+    let mut sequencer = use_signal(|| false);
+    let mut sequencer_row = use_signal(|| 64);
+
+    // Paused runs only advance on "Step". The history backs "Back", the
+    // scrubber and replay: while rewound, stepping replays stored frames.
+    let mut running = use_signal(|| autoplay);
+    // `(generation, population)` last read out to screen readers while running.
+    let mut announced = use_signal(|| (0u64, 0usize));
+    // Show the board as text as well, for screen readers.
+    let mut describing = use_signal(|| false);
+    // Show the wrapped board as a 3D torus too.
+    let mut torus_view = use_signal(|| false);
+    // Recent generations stacked in space-time, while that view is on.
+    let mut space_time = use_signal(|| None::<SpaceTime>);
+    let mut history = use_signal(|| {
+        History::new(&universe.peek().cells, 0, KEYFRAME_EVERY, HISTORY_FRAMES, HISTORY_BUDGET_KIB * 1024)
+    });
+
+    // Everything that follows the run without being part of the step below
+    // subscribes here, from this component or any under it.
+    let mut generation_hooks: Hooks = use_context_provider(|| CopyValue::new(GenerationHooks::default()));
+    use_on_generation(move |generation, tick| {
+        if generation % ANNOUNCE_EVERY == 0 {
+            announced.set((generation, tick.population));
+        }
+        sonifier.peek().update(tick.population, tick.births, tick.deaths, universe.peek().cells.len());
+    });
+    use_on_changes(move |_, changes| heatmap.write().record(changes, universe.peek().cells.len()));
+
+    // The bookkeeping after `generations` run in one go, ending on `totals`:
+    // the rival catches up and the counters and hooks move once for the
+    // lot, with the cells that changed between `before` and now.
+    let mut batched = move |generations: u64, totals: TickStats, before: Option<Vec<bool>>| {
+        if let Some(rival) = rival.write().as_mut() {
+            rival.step_n(generations);
+        }
+        stats.write().skip(generations, totals);
+        if *saver.peek() && totals.births + totals.deaths == 0 {
+            running.set(false);
+        }
+        if let Some(search) = search.write().as_mut() {
+            search.advance(SEARCH_BUDGET);
+        }
+        let generation = stats.peek().generation;
+        generation_hooks.write().emit(generation, totals);
+        if let Some(before) = before {
+            let changes = Changes::between(&before, &universe.peek().cells);
+            generation_hooks.write().emit_changes(generation, &changes);
+        }
+    };
+
+    // One generation of everything that follows the board.
+    let mut step = move || {
+        let _tick = tracing::trace_span!("tick", generation = stats.peek().generation + 1).entered();
+        let wants_changes = generation_hooks.peek().wants_changes();
+        let before = wants_changes.then(|| universe.peek().cells.clone());
+        let mut changes = Changes::default();
+        // Only a rewound board has anything to replay; touching the cells
+        // otherwise would throw away what the stepper knows about them.
+        let replayed = if history.peek().is_rewound() { history.write().redo(universe.write().cells_mut()) } else { None };
+        let tick = match replayed {
+            Some(Change::Tick(tick)) => {
+                if let Some(before) = before {
+                    changes = Changes::between(&before, &universe.peek().cells);
+                }
+                tick
+            }
+            Some(Change::Ticks(generations, totals)) => return batched(generations, totals, before),
+            // Replayed an edit: no generation passed.
+            Some(_) => return,
+            None => {
+                let tick = if wants_changes {
+                    stepper.write().tick_with_changes(&mut universe.write(), &mut changes)
+                } else {
+                    stepper.write().tick(&mut universe.write())
+                };
+                let generation = stats.peek().generation + 1;
+                let width_before = universe.peek().width;
+                let grown = if *grow_board.peek() { universe.write().grow_to_fit(GROW_MARGIN, MAX_GROWN_SIDE) } else { None };
+                match grown {
+                    // The frames before no longer fit the board.
+                    Some((top, left)) => {
+                        let (width, height) = (universe.peek().width, universe.peek().height);
+                        changes.moved(width_before, width, top, left);
+                        board_size.set((width, height));
+                        let old = *viewport.peek();
+                        let mut view = Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height);
+                        view.pan((old.row + top) as isize, (old.col + left) as isize, width, height);
+                        viewport.set(view);
+                        rival.set(None);
+                        selection.set(None);
+                        cursor.set(None);
+                        heatmap.set(Heatmap::default());
+                        trails.set(Trails::default());
+                        if space_time.peek().is_some() {
+                            space_time.set(Some(SpaceTime::default()));
+                        }
+                        if tracker.peek().is_some() {
+                            tracker.set(Some(Tracker::default()));
+                        }
+                        followed.set(None);
+                        history.write().reset(&universe.peek().cells, generation);
+                    }
+                    None => history.write().record(&universe.peek().cells, Change::Tick(tick), generation),
+                }
+                tick
+            }
+        };
+        if let Some(rival) = rival.write().as_mut() {
+            rival.tick();
+        }
+        stats.write().record(tick, &universe.peek());
+        // Battery saver: a board that stopped changing has nothing left to draw.
+        if *saver.peek() && tick.births + tick.deaths == 0 {
+            running.set(false);
+        }
+        if render.peek().trail > 0 {
+            trails.write().record(&universe.peek().cells);
+        }
+        if let Some(race) = race.write().as_mut() {
+            race.record(&universe.peek());
+        }
+        if let Some(search) = search.write().as_mut() {
+            search.advance(SEARCH_BUDGET);
+        }
+        if let Some(space_time) = space_time.write().as_mut() {
+            space_time.record(&universe.peek());
+        }
+        if let Some(tracker) = tracker.write().as_mut() {
+            let board = universe.peek();
+            let generation = stats.peek().generation;
+            tracker.observe(&board, generation);
+            let followed_track = followed.peek().and_then(|id| tracker.tracks.iter().find(|track| track.id == id));
+            match followed_track {
+                Some(track) => {
+                    let (row, col) = track.center(generation, board.width, board.height);
+                    viewport.write().center_on(row as usize, col as usize, board.width, board.height);
+                }
+                // The ship was destroyed: stop following.
+                None => followed.set(None),
+            }
+        }
+
+        if *sequencer.peek() {
+            let universe = universe.peek();
+            let start = *sequencer_row.peek() * universe.width;
+            // A row past the bottom of a board cut down since plays nothing.
+            if let Some(row) = universe.cells.get(start..start + universe.width) {
+                sonifier.peek().play_row(row, *delay.peek() as f64 / 1000.0 * 2.0);
+            }
+        }
+        let generation = stats.peek().generation;
+        generation_hooks.write().emit(generation, tick);
+        if wants_changes {
+            generation_hooks.write().emit_changes(generation, &changes);
+        }
+    };
+
+    // `generations` generations: a `step` at a time while anything follows
+    // every one of them (or there are frames to replay), otherwise in one
+    // `Universe::step_n` recorded as a single frame.
+    let mut advance = move |generations: u64| {
+        let one_by_one = generations == 1
+            || history.peek().is_rewound()
+            || *grow_board.peek()
+            || *sequencer.peek()
+            || render.peek().trail > 0
+            || race.peek().is_some()
+            || space_time.peek().is_some()
+            || tracker.peek().is_some();
+        if one_by_one {
+            for _ in 0..generations {
+                if !*running.peek() {
+                    break;
+                }
+                step();
+            }
+            return;
+        }
+        let _ticks = tracing::trace_span!("ticks", generations).entered();
+        let before = generation_hooks.peek().wants_changes().then(|| universe.peek().cells.clone());
+        let totals = universe.write().step_n(generations);
+        let generation = stats.peek().generation + generations;
+        history.write().record(&universe.peek().cells, Change::Ticks(generations, totals), generation);
+        batched(generations, totals, before);
+    };
+
+    // Whether the debug HUD (state hash and internals) is shown.
+    let mut debug_hud = use_signal(|| false);
+    // Whether the log console is open.
+    let mut log_console = use_signal(|| false);
+
+    // Playback speed, as a multiple of the base rate.
+    let mut speed = use_signal(Speed::default);
+    let mut pacer = use_signal(Pacer::default);
+    // Experimental GPU stepping, and why it last failed, if it did.
+    let mut gpu = use_signal(|| false);
+    let mut gpu_error = use_signal(|| None::<String>);
+    // Bumped each time it's switched on, so a loop from before winds down.
+    let mut gpu_runs = use_signal(|| 0u64);
+    // Keep generations coming while the tab is hidden and its timer throttled.
+    let mut background = use_signal(|| false);
+    // Reduced motion: on by default when the OS asks for it.
+    let mut calm = use_signal(|| false);
+    use_hook(|| {
+        spawn(async move {
+            if motion::prefers_reduced_motion().await {
+                calm.set(true);
+            }
+        })
+    });
+
+    // The lowest of the calm mode and battery saver caps, if either is on.
+    let rate_cap = |calm: bool, saver: bool, delay: u64| {
+        let caps = [calm.then(|| motion::calm_rate(delay)), saver.then(|| power::saver_rate(delay))];
+        caps.into_iter().flatten().reduce(f64::min)
+    };
+    let rate = move || speed.peek().rate(rate_cap(*calm.peek(), *saver.peek(), *delay.peek()));
+    // Rolling tick and render times for the debug HUD. Timing the render
+    // mustn't trigger another, so this isn't a signal.
+    let mut profile = use_hook(|| CopyValue::new(Profile::default()));
+    use_before_render(move || profile.write().start_render(speed::now_ms()));
+    use_after_render(move || profile.write().end_render(speed::now_ms()));
+    // Frames come every `FRAME_MS`; each runs the generations the time since
+    // the last one is worth at the speed (and calm mode) asked for.
+    use_interval(Duration::from_millis(speed::FRAME_MS), move |_| {
+        let started = speed::now_ms();
+        // While paused, or while the GPU loop does the stepping, no lag builds up.
+        if !*running.peek() || *gpu.peek() {
+            pacer.write().idle(started);
+            return;
+        }
+        match rate() {
+            Some(rate) => {
+                let period = *delay.peek() as f64 / rate;
+                let keep_up = *background.peek();
+                let budget = if keep_up { speed::CATCH_UP_MS } else { speed::MAX_BATCH_MS };
+                let due = pacer.write().due(started, period, keep_up);
+                let mut done = 0;
+                while done < due && *running.peek() && (done == 0 || speed::now_ms() - started < budget) {
+                    let elapsed = speed::now_ms() - started;
+                    let generations = speed::batch(done, elapsed, budget - elapsed, due - done);
+                    advance(generations);
+                    done += generations;
+                }
+                pacer.write().defer(due - done, period);
+            }
+            None => {
+                let mut done = 0;
+                while *running.peek() && speed::now_ms() - started < speed::MAX_BATCH_MS {
+                    let elapsed = speed::now_ms() - started;
+                    let generations = speed::batch(done, elapsed, speed::MAX_BATCH_MS - elapsed, u64::MAX);
+                    advance(generations);
+                    done += generations;
+                }
+            }
+        }
+        profile.write().record_tick(speed::now_ms() - started);
+    });
+
+    // Move the board to history frame `target`, one frame at a time so the
+    // counters follow along.
+    let mut seek = move |target: usize| {
+        let mut history = history.write();
+        while history.cursor() > target {
+            let undone = history.undo(universe.write().cells_mut());
+            match undone {
+                Some(Change::Tick(tick)) => stats.write().unrecord(tick, history.last_tick(history.cursor())),
+                Some(Change::Ticks(generations, totals)) => stats.write().unskip(generations, totals, history.last_tick(history.cursor())),
+                _ => {}
+            }
+        }
+        while history.cursor() < target {
+            let replayed = history.redo(universe.write().cells_mut());
+            match replayed {
+                Some(Change::Tick(tick)) => stats.write().record(tick, &universe.peek()),
+                Some(Change::Ticks(generations, totals)) => stats.write().skip(generations, totals),
+                _ => {}
+            }
+        }
+    };
+
+    // Undo the latest generation or edit; pauses so it sticks.
+    let mut step_back = move || {
+        running.set(false);
+        let cursor = history.peek().cursor();
+        seek(cursor.saturating_sub(1));
+    };
+
+    // Store a hand edit as its own frame, so it can be undone too.
+    let mut commit_edit = move || {
+        let generation = stats.peek().generation;
+        history.write().record(&universe.peek().cells, Change::Edit, generation);
+    };
+
+    // While `gpu` is on, runs advance on the GPU as fast as it goes, in
+    // batches of `GPU_BATCH`. The board comes back every `GPU_SYNC_MS` and
+    // on pausing, starting the history afresh; in between only the counters
+    // move, and overlays that follow every generation miss the batches. An
+    // edit to the board sends it to the GPU again.
+    let mut run_on_gpu = move || {
+        *gpu_runs.write() += 1;
+        let id = *gpu_runs.peek();
+        spawn(async move {
+            let result: Result<(), String> = async {
+                let mut run = GpuRun::open().await?;
+                // The board as last sent to or read back from the GPU.
+                let mut synced = None::<Universe>;
+                // Whether the GPU holds generations the board doesn't.
+                let mut ahead = false;
+                let mut last_sync = speed::now_ms();
+                loop {
+                    let (on, running) = (*gpu.peek() && *gpu_runs.peek() == id, *running.peek());
+                    if ahead && (!on || !running || speed::now_ms() - last_sync >= GPU_SYNC_MS) {
+                        let cells = run.cells().await?;
+                        *universe.write().cells_mut() = cells;
+                        let generation = stats.peek().generation;
+                        history.write().reset(&universe.peek().cells, generation);
+                        synced = Some(universe.peek().clone());
+                        (ahead, last_sync) = (false, speed::now_ms());
+                    }
+                    if !on {
+                        break;
+                    }
+                    if synced.as_ref() != Some(&*universe.peek()) {
+                        let board = universe.peek().clone();
+                        run.load(&board).await?;
+                        synced = Some(board);
+                    }
+                    if !running {
+                        dioxus_time::sleep(Duration::from_millis(*delay.peek())).await;
+                        continue;
+                    }
+                    let tick = run.step(GPU_BATCH).await?;
+                    stats.write().skip(GPU_BATCH, tick);
+                    let generation = stats.peek().generation;
+                    generation_hooks.write().emit(generation, tick);
+                    ahead = true;
+                }
+                run.close();
+                Ok(())
+            }
+            .await;
+            if let Err(err) = result {
+                tracing::warn!("GPU stepping stopped: {err}");
+                gpu_error.set(Some(err));
+                if *gpu_runs.peek() == id {
+                    gpu.set(false);
+                }
+            }
+        });
+    };
+
+    let current_session = move || Session {
+        universe: universe.peek().clone(),
+        generation: stats.peek().generation,
+        rival_rule: *rival_rule.peek(),
+        overlay: *overlay.peek(),
+        annotations: annotations.peek().clone(),
+    };
+
+    // A session left by a previous run, offered for restore until dismissed.
+    let mut saved_session = use_signal(|| if autosave { autosave::load(slot) } else { None });
+    let saved_thumbnail = use_memo(move || {
+        let session = saved_session.read();
+        session.as_ref().map(|session| library::thumbnail_url(&Thumbnail::of_universe(&session.universe, library::THUMBNAIL_SIZE)))
+    });
+    use_interval(Duration::from_secs(AUTOSAVE_SECS), move |_| {
+        // Don't overwrite the old session before the user has decided on it.
+        if !autosave || saved_session.peek().is_some() {
+            return;
+        }
+        autosave::save(slot, &current_session());
+    });
+
+    // Forget everything derived from the previous board.
+    let mut reset_counters = move || {
+        stats.set(RunStats::default());
+        heatmap.set(Heatmap::default());
+        trails.set(Trails::default());
+        race.set(None);
+        history.write().reset(&universe.peek().cells, 0);
+        if space_time.peek().is_some() {
+            space_time.set(Some(SpaceTime::default()));
+        }
+        if tracker.peek().is_some() {
+            tracker.set(Some(Tracker::default()));
+        }
+        followed.set(None);
+        // The board may have shrunk under the sequencer.
+        let last_row = universe.peek().height.saturating_sub(1);
+        if *sequencer_row.peek() > last_row {
+            sequencer_row.set(last_row);
+        }
+    };
+
+    let mut restart = move || {
+        let (width, height) = board_size();
+        let (rule, boundary) = (universe.peek().rule, universe.peek().boundary);
+        let soup = UniverseBuilder::new().size(width, height).seed(random_seed()).boundary(boundary);
+        universe.set(soup.rule(rule).build());
+        if rival.peek().is_some() {
+            rival.set(Some(soup.rule(rival_rule()).build()));
+        }
+        viewport.set(Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+        reset_counters();
+    };
+
+    let mut restore_session = move |session: Session| {
+        let (width, height) = (session.universe.width, session.universe.height);
+        board_size.set((width, height));
+        viewport.set(Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+        universe.set(session.universe);
+        rival.set(None);
+        reset_counters();
+        stats.write().generation = session.generation;
+        history.write().reset(&universe.peek().cells, session.generation);
+        rival_rule.set(session.rival_rule);
+        overlay.set(session.overlay);
+        annotations.set(session.annotations);
+    };
+
+    // The session, its history and settings as one file, for moving to
+    // another machine.
+    let mut export_session = move || {
+        let bundle = Bundle {
+            session: current_session(),
+            history: history.peek().clone(),
+            render: *render.peek(),
+            speed: *speed.peek(),
+            snapshot: snapshot.peek().clone(),
+            starred_rules: starred_rules.peek().clone(),
+            library: library_entries.peek().clone(),
+        };
+        bundle_error.set(bundle::download(bundle::FILE_NAME, "application/json", &bundle.encode()).err());
+    };
+
+    let import_session = move |text: String| match Bundle::decode(&text) {
+        Ok(bundle) => {
+            restore_session(bundle.session);
+            history.set(bundle.history);
+            render.set(bundle.render);
+            speed.set(bundle.speed);
+            snapshot.set(bundle.snapshot);
+            autosave::save_starred_rules(&bundle.starred_rules);
+            starred_rules.set(bundle.starred_rules);
+            let mut entries = library_entries.write();
+            let saved = match library::add_missing(&mut entries, bundle.library) {
+                0 => Ok(()),
+                _ => autosave::save_library(&entries),
+            };
+            bundle_error.set(saved.err().map(|err| (language.peek().strings().library_not_saved)(&err)));
+        }
+        Err(err) => bundle_error.set(Some(err)),
+    };
+
+    // Rebuild the board around its live cells, keeping the generation count.
+    let mut crop_board = move || {
+        if !universe.write().crop_to_bounding_box(CROP_PADDING) {
+            return;
+        }
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        let generation = stats.peek().generation;
+        board_size.set((width, height));
+        viewport.set(Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+        rival.set(None);
+        selection.set(None);
+        cursor.set(None);
+        reset_counters();
+        stats.write().generation = generation;
+        history.write().reset(&universe.peek().cells, generation);
+    };
+
+    // Combine the board with the snapshot, as one undoable edit.
+    let mut combine_snapshot = move |op: SetOp| {
+        let Some(combined) = snapshot.peek().as_ref().and_then(|shot| universe.peek().combine(shot, op)) else { return };
+        *universe.write().cells_mut() = combined.cells;
+        commit_edit();
+    };
+
+    let mut center_board = move || {
+        if universe.write().center() {
+            commit_edit();
+        }
+    };
+
+    // Replace the board with a whole new one, whatever its size.
+    let load_board = move |board: Universe| {
+        let (width, height) = (board.width, board.height);
+        board_size.set((width, height));
+        viewport.set(Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+        universe.set(board);
+        rival.set(None);
+        selection.set(None);
+        cursor.set(None);
+        reset_counters();
+    };
+
+    // Re-threshold the image seed onto the board.
+    let mut apply_image = move || {
+        let Some(luma) = image_luma() else { return };
+        if luma.len() != universe.peek().cells.len() {
+            return;
+        }
+        *universe.write().cells_mut() = image_import::threshold(&luma, image_level(), image_invert());
+        reset_counters();
+    };
+
+    let import_image = move |bytes: Vec<u8>| {
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        spawn(async move {
+            match image_import::luminance(bytes, width, height).await {
+                Ok(luma) => {
+                    image_luma.set(Some(luma));
+                    image_error.set(None);
+                    apply_image();
+                }
+                Err(err) => image_error.set(Some(err)),
+            }
+        });
+    };
+
+    let load_soup = move |seed: u64| {
+        SoupSearch::load(seed, &mut universe.write());
+        reset_counters();
+    };
+
+    let mut start_race = move || {
+        restart();
+        let (width, height) = board_size();
+        let rule = universe.peek().rule;
+        let (mut board, scoreboard) = Race::start(width, height, random_seed(), rule, race_length());
+        board.boundary = universe.peek().boundary;
+        universe.set(board);
+        reset_counters();
+        race.set(Some(scoreboard));
+    };
+
+    let mut pan = move |d_row: isize, d_col: isize| {
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        viewport.write().pan(d_row, d_col, width, height);
+    };
+    // Merge a pattern centred on `(row, col)`, wrapping round the edges.
+    let mut place_at = move |pattern: &Pattern, (row, col): (usize, usize)| {
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        let (top, left) = guides::snap(pattern.origin_centered_on((row, col), width, height), *snap_parity.peek());
+        universe.write().overlay(pattern, top, left, *merge_mode.peek());
+        commit_edit();
+        last_placed.set(Some(pattern.clone()));
+    };
+
+    // Stamp a pattern in the middle of the view.
+    let mut place = move |pattern: &Pattern| {
+        let view = *viewport.peek();
+        place_at(pattern, (view.row + view.rows / 2, view.col + view.cols / 2));
+    };
+
+    // Ctrl+V: the clipboard's RLE/plaintext floats under the mouse until a
+    // click drops it (Escape cancels).
+    let paste_clipboard = move || {
+        spawn(async move {
+            match clipboard::read_text().await.and_then(|text| life_formats::parse_text(&text)) {
+                Ok(pattern) => {
+                    floating.set(Some(pattern));
+                    file_error.set(None);
+                }
+                Err(err) => file_error.set(Some(err)),
+            }
+        });
+    };
+
+    // Ctrl+C: the selection goes to the clipboard as RLE.
+    let copy_selection = move || {
+        let Some(area) = *selection.peek() else { return };
+        let rle = Pattern::from_region(&universe.peek(), area.top, area.left, area.rows(), area.cols()).to_rle();
+        spawn(async move {
+            match clipboard::write_text(&rle).await {
+                Ok(()) => file_error.set(None),
+                Err(err) => file_error.set(Some(err)),
+            }
+        });
+    };
+
+    // Board cell under the mouse. Cells ignore pointer events, so the
+    // coordinates are always relative to the board itself.
+    let cell_at = move |point: ElementPoint| {
+        let view = viewport.peek();
+        let board = universe.peek();
+        let row = view.row + (point.y.max(0.0) as usize / cell_size).min(view.rows - 1);
+        let col = view.col + (point.x.max(0.0) as usize / cell_size).min(view.cols - 1);
+        (row.min(board.height - 1), col.min(board.width - 1))
+    };
+
+    let mut paint = move |(row, col): (usize, usize), alive: bool| {
+        let _ = universe.write().set(row, col, alive.into());
+    };
+
+    // Show the cursor in the middle of the view, or hide it.
+    let mut toggle_cursor = move || {
+        let view = *viewport.peek();
+        let shown = cursor.peek().is_none().then_some((view.row + view.rows / 2, view.col + view.cols / 2));
+        cursor.set(shown);
+        hover.set(shown);
+    };
+    // Move the cursor one cell, wrapping round the torus; the view scrolls
+    // to keep it visible. A floating paste moves with it.
+    let mut move_cursor = move |d_row: isize, d_col: isize| {
+        let Some((row, col)) = *cursor.peek() else { return };
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        let row = (row as isize + d_row).rem_euclid(height as isize) as usize;
+        let col = (col as isize + d_col).rem_euclid(width as isize) as usize;
+        cursor.set(Some((row, col)));
+        hover.set(Some((row, col)));
+        viewport.write().reveal(row, col);
+    };
+    // Toggle the cell under the cursor, or drop a floating paste there.
+    let mut cursor_action = move || {
+        let Some((row, col)) = *cursor.peek() else { return };
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        // The board may have shrunk since the cursor was placed.
+        let cell = (row.min(height - 1), col.min(width - 1));
+        if let Some(pattern) = floating.take() {
+            place_at(&pattern, cell);
+            return;
+        }
+        let _ = universe.write().toggle(cell.0, cell.1);
+        commit_edit();
+    };
+
+    // Move the whole board by one cell, e.g. to pull a drifting pattern back.
+    let mut shift_board = move |dx: isize, dy: isize| {
+        universe.write().shift(dx, dy);
+        commit_edit();
+    };
+
+    // Pan by a quarter of the view per key press. With the cursor shown the
+    // arrows move it instead (Shift still pans) and Space/Enter edit.
+    // Alt+arrows shift the board itself.
+    let pan_step = (VIEW_CELLS / 4) as isize;
+    let mut run_command = move |command: Command| {
+        let editing = cursor.peek().is_some();
+        match command {
+            Command::ShiftUp => shift_board(0, -1),
+            Command::ShiftDown => shift_board(0, 1),
+            Command::ShiftLeft => shift_board(-1, 0),
+            Command::ShiftRight => shift_board(1, 0),
+            Command::MoveUp if editing => move_cursor(-1, 0),
+            Command::MoveDown if editing => move_cursor(1, 0),
+            Command::MoveLeft if editing => move_cursor(0, -1),
+            Command::MoveRight if editing => move_cursor(0, 1),
+            Command::MoveUp | Command::PanUp => pan(-pan_step, 0),
+            Command::MoveDown | Command::PanDown => pan(pan_step, 0),
+            Command::MoveLeft | Command::PanLeft => pan(0, -pan_step),
+            Command::MoveRight | Command::PanRight => pan(0, pan_step),
+            Command::CursorAction => cursor_action(),
+            Command::PlayPause if editing => cursor_action(),
+            Command::PlayPause => running.toggle(),
+            Command::Step => step(),
+            Command::StepBack => step_back(),
+            Command::Restart => restart(),
+            Command::KeyboardCursor => toggle_cursor(),
+            Command::Paste => paste_clipboard(),
+            Command::Copy => copy_selection(),
+            Command::Cancel if floating.peek().is_some() => floating.set(None),
+            Command::Cancel => cursor.set(None),
+            Command::OpenPalette => palette.toggle(),
+        }
+    };
+
+    // The palette's shortcut is left to `on_page_key`, further up.
+    let on_key = move |evt: KeyboardEvent| {
+        let Some(binding) = Binding::from_event(&evt) else { return };
+        match keymap.peek().command(&binding) {
+            Some(Command::OpenPalette) | None => {}
+            Some(command) => run_command(command),
+        }
+    };
+
+    // The palette opens wherever the focus is, not just on the board.
+    let on_page_key = move |evt: KeyboardEvent| {
+        let Some(binding) = Binding::from_event(&evt) else { return };
+        if keymap.peek().command(&binding) == Some(Command::OpenPalette) {
+            evt.prevent_default();
+            // A seed-grid board sits inside this one; only it opens.
+            evt.stop_propagation();
+            palette.toggle();
+        }
+    };
+
+    let mut export_text = move || {
+        let pattern = Pattern::from_universe(&universe.peek());
+        let pattern = if *ascii_trim.peek() { pattern.trimmed() } else { pattern };
+        ascii.set(pattern.to_plaintext());
+    };
+
+    let run_action = move |action: Action| {
+        palette.set(false);
+        match action {
+            Action::Command(command) => run_command(command),
+            Action::Pattern(name) => {
+                if let Some(pattern) = patterns::bundled(name) {
+                    place(&pattern);
+                }
+            }
+            Action::Rule(index) => {
+                if let Ok(rule) = Rule::parse(PRESETS[index].1) {
+                    universe.write().rule = rule;
+                }
+            }
+            Action::Overlay(choice) => overlay.set(choice),
+            Action::ExportText => export_text(),
+            Action::ExportPacked => ascii.set(packed::to_text(&universe.peek())),
+            Action::ExportSession => export_session(),
+        }
+    };
+
+    let on_mouse_down = move |evt: MouseEvent| {
+        // Other buttons are left to the context menu.
+        if evt.trigger_button() != Some(MouseButton::Primary) {
+            return;
+        }
+        let cell = cell_at(evt.element_coordinates());
+        if cursor.peek().is_some() {
+            cursor.set(Some(cell));
+        }
+        if let Some(pattern) = floating.take() {
+            place_at(&pattern, cell);
+            return;
+        }
+        match tool() {
+            Tool::Draw => {
+                // The drag paints whatever state the first cell flipped to.
+                let alive = universe.write().toggle(cell.0, cell.1).is_ok_and(CellState::is_alive);
+                drag.set(Some(Drag::Paint(alive)));
+            }
+            Tool::Select => {
+                selection.set(Some(Selection::spanning(cell, cell)));
+                drag.set(Some(Drag::Select(cell)));
+            }
+            Tool::Annotate => drag.set(Some(Drag::Annotate(cell, cell))),
+            Tool::Measure => {
+                ruler.set(Some(Ruler { from: cell, to: cell }));
+                drag.set(Some(Drag::Measure));
+            }
+        }
+    };
+
+    let on_context_menu = move |evt: MouseEvent| {
+        evt.prevent_default();
+        let point = evt.client_coordinates();
+        menu.set(Some((cell_at(evt.element_coordinates()), (point.x, point.y))));
+    };
+
+    let run_cell_action = move |action: CellAction| {
+        let Some(((row, col), _)) = menu.take() else { return };
+        match action {
+            CellAction::Toggle => {
+                if universe.write().toggle(row, col).is_ok() {
+                    commit_edit();
+                }
+            }
+            CellAction::Stamp => {
+                if let Some(pattern) = last_placed() {
+                    place_at(&pattern, (row, col));
+                }
+            }
+            CellAction::Paste => {
+                spawn(async move {
+                    match clipboard::read_text().await.and_then(|text| life_formats::parse_text(&text)) {
+                        Ok(pattern) => {
+                            place_at(&pattern, (row, col));
+                            file_error.set(None);
+                        }
+                        Err(err) => file_error.set(Some(err)),
+                    }
+                });
+            }
+            CellAction::CenterView => {
+                let (width, height) = (universe.peek().width, universe.peek().height);
+                viewport.write().center_on(row, col, width, height);
+            }
+            CellAction::Inspect => inspected.set(Some((row, col))),
+        }
+    };
+
+    // A paint stroke is one edit in the history.
+    let mut end_drag = move || {
+        match *drag.peek() {
+            Some(Drag::Paint(_)) => commit_edit(),
+            Some(Drag::Annotate(at, to)) => {
+                let text = match note_text.peek().trim() {
+                    "" => language.peek().strings().annotation_default.to_string(),
+                    text => text.to_string(),
+                };
+                annotations.write().push(Annotation { row: at.0, col: at.1, text, arrow: (to != at).then_some(to) });
+            }
+            _ => {}
+        }
+        drag.set(None);
+    };
+
+    let on_mouse_move = move |evt: MouseEvent| {
+        let cell = cell_at(evt.element_coordinates());
+        if *show_cell_info.peek() || *inspector.peek() {
+            let point = evt.client_coordinates();
+            pointer.set(Some((cell, (point.x, point.y))));
+        }
+        if floating.peek().is_some() {
+            hover.set(Some(cell));
+        }
+        let Some(current) = *drag.peek() else { return };
+        match current {
+            Drag::Paint(alive) => paint(cell, alive),
+            Drag::Select(anchor) => selection.set(Some(Selection::spanning(anchor, cell))),
+            Drag::Annotate(at, _) => drag.set(Some(Drag::Annotate(at, cell))),
+            Drag::Measure => {
+                if let Some(ruler) = &mut *ruler.write() {
+                    ruler.to = cell;
+                }
+            }
+        }
+    };
+
+    // Write a found predecessor over the selection and its one-cell ring.
+    let apply_predecessor = move |_| {
+        let Some((area, Outcome::Found(window))) = predecessor() else { return };
+        let (width, height) = (universe.peek().width, universe.peek().height);
+        let (top, left) = ((area.top + height - 1) % height, (area.left + width - 1) % width);
+        window.stamp(&mut universe.write(), top, left);
+        commit_edit();
+        predecessor.set(None);
+    };
+
+    // Dropped files: images become the seed, pattern files are placed at
+    // the drop point (`None` = middle of the view).
+    let drop_files = move |files: Option<Arc<dyn FileEngine>>, at: Option<(usize, usize)>| {
+        let Some(files) = files else { return };
+        spawn(async move {
+            for name in files.files() {
+                if image_import::is_image(&name) {
+                    if let Some(bytes) = files.read_file(&name).await {
+                        import_image(bytes);
+                    }
+                    continue;
+                }
+                let Some(text) = files.read_file_to_string(&name).await else { continue };
+                match life_formats::parse_file(&name, &text) {
+                    Ok(pattern) => {
+                        match at {
+                            Some(cell) => place_at(&pattern, cell),
+                            None => place(&pattern),
+                        }
+                        file_error.set(None);
+                    }
+                    Err(err) => file_error.set(Some(err)),
+                }
+            }
+        });
+    };
+
+    let toggle_sound = move |_| {
+        let muted = sonifier.read().is_muted();
+        sonifier.write().set_muted(!muted);
+        if muted && sonifier.read().is_muted() {
+            no_sound.set(true);
+        }
+    };
+
+    let board = universe.read();
+    let (board_width, board_height) = (board.width, board.height);
+    let view = viewport();
+    let highlighted_row = sequencer().then(|| *sequencer_row.read());
+    let heat = heatmap.read();
+    // What the board is actually drawn with; the selects keep showing the
+    // user's own choices while calm mode overrides them.
+    let period = running().then(|| speed().rate(rate_cap(calm(), saver(), delay()))).flatten().map(|rate| delay() as f64 / rate);
+    let drawn = if calm() || saver() { render().calmed() } else { render().crossfaded(period) };
+    let trail_length = drawn.trail;
+    let trail_ages = trails.read();
+    let active_overlay = overlay();
+    let reference = snapshot().or_else(|| rival.read().clone());
+    let diff = match active_overlay {
+        Overlay::Diff => reference.as_ref().and_then(|other| board.diff(other)),
+        _ => None,
+    };
+    let differing = diff.as_ref().map(|mask| mask.iter().filter(|&&d| d).count());
+    let scoreboard = race.read();
+    let selected = selection();
+    let pasting = floating.read();
+    let paste_origin = pasting
+        .as_ref()
+        .zip(hover())
+        .map(|(pattern, cell)| guides::snap(pattern.origin_centered_on(cell, board_width, board_height), snap_parity()));
+    // The selection as a pattern, for saving or sharing; `None` when empty.
+    let selected_pattern = selected
+        .map(|area| Pattern::from_region(&board, area.top, area.left, area.rows(), area.cols()))
+        .filter(|pattern| pattern.cells.contains(&true));
+    #[cfg(feature = "fullstack")]
+    let gallery = rsx! {
+        GalleryPanel { selection: selected_pattern.clone(), on_load: move |pattern| floating.set(Some(pattern)) }
+    };
+    #[cfg(not(feature = "fullstack"))]
+    let gallery = rsx! {};
+    let alignment = pasting.as_ref().zip(paste_origin).map(|(pattern, origin)| {
+        Guides::find(pattern, origin, &census::outlines(&universe.read()), board_width, board_height)
+    });
+
+    let text = language().strings();
+    let hint = move |command| keymap.read().hint(command);
+    let hovered = pointer().map(|(cell, _)| cell).filter(|&(row, col)| row < board_height && col < board_width);
+    let inspecting = hovered.filter(|_| inspector()).map(|(row, col)| context_menu::block(&board, row, col));
+    let cursor_cell = cursor().map(|(row, col)| (row.min(board_height - 1), col.min(board_width - 1)));
+
+    // view
+    rsx! {
+        document::Style { {theme::stylesheet()} }
+        document::Link { rel: "stylesheet", href: MAIN_CSS }
+
+        div {
+            class: "container",
+            lang: language().code(),
+            onkeydown: on_page_key,
+            // Files dropped outside the board land in the middle of the view.
+            ondragover: move |evt| evt.prevent_default(),
+            ondrop: move |evt| {
+                evt.prevent_default();
+                drop_files(evt.files(), None);
+            },
+            h1 { "{text.title}" }
+            if let Some(session) = saved_session() {
+                div { class: "controls restore",
+                    if let Some(src) = saved_thumbnail() {
+                        img { class: "thumbnail", src, alt: "" }
+                    }
+                    span {
+                        {(text.restore_prompt)(
+                            &format!("{}×{}", session.universe.width, session.universe.height),
+                            &session.universe.rule.to_string(),
+                            session.generation,
+                        )}
+                    }
+                    button {
+                        onclick: move |_| {
+                            if let Some(session) = saved_session.take() {
+                                restore_session(session);
+                            }
+                        },
+                        "{text.restore}"
+                    }
+                    button {
+                        onclick: move |_| {
+                            autosave::clear(slot);
+                            saved_session.set(None);
+                        },
+                        "{text.discard}"
+                    }
+                }
+            }
+            div { class: "controls",
+                label {
+                    "{text.language} "
+                    select {
+                        onchange: move |evt| language.set(Language::from_tag(&evt.value())),
+                        for option in Language::ALL {
+                            option { value: option.code(), selected: language() == option, "{option.name()}" }
+                        }
+                    }
+                }
+                button { onclick: move |_| restart(), "{text.restart}{hint(Command::Restart)}" }
+                select {
+                    aria_label: text.board_size,
+                    onchange: move |evt| {
+                        if let Ok(size) = evt.value().parse() {
+                            board_size.set((size, size));
+                            restart();
+                        }
+                    },
+                    for size in BOARD_SIZES {
+                        option { value: "{size}", selected: board_size() == (size, size), "{size}×{size}" }
+                    }
+                }
+                RulePicker {
+                    rule: board.rule,
+                    on_change: move |rule| universe.write().rule = rule,
+                }
+                if panels {
+                    RuleExplorer {
+                        rule: board.rule,
+                        starred: starred_rules,
+                        on_try: move |rule| {
+                            universe.write().rule = rule;
+                            restart();
+                            running.set(true);
+                        },
+                    }
+                }
+                select {
+                    aria_label: text.boundary,
+                    onchange: move |evt| {
+                        let boundary = Boundary::from_key(&evt.value());
+                        universe.write().boundary = boundary;
+                        if let Some(rival) = rival.write().as_mut() {
+                            rival.boundary = boundary;
+                        }
+                    },
+                    for option in Boundary::ALL {
+                        option { value: option.key(), selected: board.boundary.key() == option.key(), {option.label(text)} }
+                    }
+                }
+                if let Boundary::Shifted(shift) = board.boundary {
+                    input {
+                        r#type: "number",
+                        aria_label: text.boundary_shift,
+                        value: "{shift}",
+                        oninput: move |evt| {
+                            if let Ok(shift) = evt.value().parse::<i32>() {
+                                universe.write().boundary = Boundary::Shifted(shift);
+                                if let Some(rival) = rival.write().as_mut() {
+                                    rival.boundary = Boundary::Shifted(shift);
+                                }
+                            }
+                        },
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: grow_board(),
+                        disabled: board.boundary != Boundary::Dead,
+                        onchange: move |evt| grow_board.set(evt.checked()),
+                    }
+                    " {text.grow_board}"
+                }
+                button { onclick: move |_| crop_board(), "{text.crop_to_content}" }
+                button { onclick: move |_| center_board(), "{text.center_pattern}" }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: rival.read().is_some(),
+                        onchange: move |evt| {
+                            if evt.checked() {
+                                restart();
+                                let (width, height) = board_size();
+                                let (seed, boundary) = (universe.peek().seed, universe.peek().boundary);
+                                let soup = UniverseBuilder::new().size(width, height).seed(seed).boundary(boundary);
+                                rival.set(Some(soup.rule(rival_rule()).build()));
+                            } else {
+                                rival.set(None);
+                            }
+                        },
+                    }
+                    " {text.compare_with} "
+                }
+                RulePicker {
+                    rule: rival_rule(),
+                    on_change: move |rule| {
+                        rival_rule.set(rule);
+                        if let Some(rival) = rival.write().as_mut() {
+                            rival.rule = rule;
+                        }
+                    },
+                }
+                if panels {
+                    label {
+                        "{text.seed_grid} "
+                        select {
+                            onchange: move |evt| {
+                                if let Ok(count) = evt.value().parse() {
+                                    grid_count.set(count);
+                                }
+                            },
+                            for count in GRID_COUNTS {
+                                option { value: "{count}", selected: grid_count() == count,
+                                    if count == 0 { "{text.grid_off}" } else { "{count}" }
+                                }
+                            }
+                        }
+                    }
+                }
+                button { onclick: toggle_sound, disabled: no_sound(),
+                    if no_sound() {
+                        "{text.sound_unavailable}"
+                    } else if sonifier.read().is_muted() {
+                        "{text.unmute}"
+                    } else {
+                        "{text.mute}"
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: sequencer(),
+                        onchange: move |evt| sequencer.set(evt.checked()),
+                    }
+                    " {text.sequencer_row} "
+                }
+                input {
+                    r#type: "number",
+                    aria_label: text.sequencer_row,
+                    min: 0,
+                    max: board_height - 1,
+                    value: "{sequencer_row}",
+                    oninput: move |evt| {
+                        if let Ok(row) = evt.value().parse::<usize>() {
+                            sequencer_row.set(row.min(universe.peek().height - 1));
+                        }
+                    },
+                }
+                select {
+                    aria_label: text.overlay,
+                    onchange: move |evt| overlay.set(Overlay::from_key(&evt.value())),
+                    for option in Overlay::ALL {
+                        option { value: option.key(), selected: active_overlay == option, {option.label(text)} }
+                    }
+                }
+                select {
+                    aria_label: text.grid_lines,
+                    onchange: move |evt| render.write().grid = evt.value().parse().ok(),
+                    option { value: "off", selected: render.read().grid.is_none(), "{text.no_grid}" }
+                    for spacing in GRID_SPACINGS {
+                        option {
+                            value: "{spacing}",
+                            selected: render.read().grid == Some(spacing),
+                            {(text.grid_every)(spacing)}
+                        }
+                    }
+                }
+                select {
+                    aria_label: text.cell_shape,
+                    onchange: move |evt| {
+                        let index = evt.value().parse().unwrap_or(0);
+                        render.write().shape = CellShape::ALL[index % CellShape::ALL.len()];
+                    },
+                    for (index, shape) in CellShape::ALL.into_iter().enumerate() {
+                        option { value: "{index}", selected: render.read().shape == shape, {shape.label(text)} }
+                    }
+                }
+                select {
+                    aria_label: text.cell_gap,
+                    onchange: move |evt| render.write().gap = evt.value().parse().unwrap_or(0),
+                    for gap in CELL_GAPS {
+                        option {
+                            value: "{gap}",
+                            selected: render.read().gap == gap,
+                            if gap == 0 { "{text.no_gap}" } else { {(text.gap)(gap)} }
+                        }
+                    }
+                }
+                select {
+                    aria_label: text.fade_effect,
+                    onchange: move |evt| render.write().fade_ms = evt.value().parse().unwrap_or(0),
+                    for fade in FADE_DURATIONS {
+                        option {
+                            value: "{fade}",
+                            selected: render.read().fade_ms == fade,
+                            if fade == 0 { "{text.no_fade}" } else { {(text.fade)(fade)} }
+                        }
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: render.read().crossfade,
+                        onchange: move |evt| render.write().crossfade = evt.checked(),
+                    }
+                    " {text.crossfade}"
+                }
+                select {
+                    aria_label: text.trail_length,
+                    onchange: move |evt| {
+                        render.write().trail = evt.value().parse().unwrap_or(0);
+                        trails.set(Trails::default());
+                    },
+                    for length in TRAIL_LENGTHS {
+                        option {
+                            value: "{length}",
+                            selected: render.read().trail == length,
+                            if length == 0 { "{text.no_trails}" } else { {(text.trails)(length)} }
+                        }
+                    }
+                }
+                button { onclick: move |_| snapshot.set(Some(universe())), "{text.snapshot}" }
+                if snapshot.read().is_some() {
+                    button { onclick: move |_| snapshot.set(None), "{text.drop_snapshot}" }
+                }
+                if snapshot.read().as_ref().is_some_and(|shot| (shot.width, shot.height) == (board_width, board.height)) {
+                    for op in SetOp::ALL {
+                        button { onclick: move |_| combine_snapshot(op), {op.label(text)} }
+                    }
+                }
+                if let Some(count) = differing {
+                    span { {(text.cells_differ)(count)} }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: calm(),
+                        onchange: move |evt| calm.set(evt.checked()),
+                    }
+                    " {text.reduced_motion}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: background(),
+                        onchange: move |evt| background.set(evt.checked()),
+                    }
+                    " {text.run_in_background}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: saver(),
+                        onchange: move |evt| saver.set(evt.checked()),
+                    }
+                    " {text.battery_saver}"
+                }
+                for option in Speed::ALL {
+                    button {
+                        aria_pressed: speed() == option,
+                        onclick: move |_| speed.set(option),
+                        {option.label(text)}
+                    }
+                }
+                p {
+                    match speed().rate(rate_cap(calm(), saver(), delay())) {
+                        Some(rate) => (text.update_delay)((delay() as f64 / rate).round() as u64),
+                        None => text.update_delay_max.to_string(),
+                    }
+                }
+            }
+
+            HistoryPanel {
+                history,
+                running,
+                // The rival and the race scoreboard can't be rewound with the board.
+                locked: rival.read().is_some() || race.read().is_some(),
+                slot,
+                on_step: move |_| step(),
+                on_step_back: move |_| step_back(),
+                on_seek: seek,
+            }
+
+            ToolsPanel {
+                tool,
+                note_text,
+                ruler,
+                stopwatch,
+                generation: stats.read().generation,
+                universe,
+                cursor: cursor_cell,
+                selection,
+                predecessor,
+                on_toggle_cursor: move |_| toggle_cursor(),
+                on_copy: move |_| copy_selection(),
+                on_apply_predecessor: apply_predecessor,
+            }
+            AnnotationList { annotations }
+
+            if panels {
+                CensusPanel { universe, on_place: move |pattern: Pattern| place(&pattern) }
+
+                PatternSearch { on_place: move |pattern: Pattern| place(&pattern) }
+
+                LibraryPanel {
+                    entries: library_entries,
+                    selection: selected_pattern.clone(),
+                    on_stamp: move |pattern| floating.set(Some(pattern)),
+                }
+                {gallery}
+
+                ExportPanel {
+                    ascii,
+                    ascii_trim,
+                    universe,
+                    bundle_error: bundle_error(),
+                    on_export_text: move |_| export_text(),
+                    on_load_board: load_board,
+                    on_export_session: move |_| export_session(),
+                    on_import_session: import_session,
+                    on_place: move |pattern: Pattern| place(&pattern),
+                }
+
+                ImportPanel {
+                    image_luma,
+                    image_level,
+                    image_invert,
+                    image_error: image_error(),
+                    merge_mode,
+                    snap_parity,
+                    pasting: pasting.is_some(),
+                    file_error: file_error(),
+                    on_image: import_image,
+                    on_threshold: move |_| apply_image(),
+                }
+
+                TrackerPanel { tracker, followed }
+
+                RacePanel { race, race_length, on_start: move |_| start_race() }
+
+                SearchPanel { search, rule: board.rule, on_load: load_soup }
+                EvolutionPanel {
+                    rule: board.rule,
+                    on_load: move |genome| {
+                        Evolution::load(genome, &mut universe.write());
+                        reset_counters();
+                    },
+                }
+
+                BenchmarkPanel {}
+
+                ShortcutSettings {}
+            }
+
+            div { class: "stats",
+                span { {(text.seed_value)(board.seed)} }
+                span { {(text.generation)(stats.read().generation)} }
+                span { {(text.population)(stats.read().last.population)} }
+                span { {(text.births)(stats.read().total_births, stats.read().birth_rate())} }
+                span { {(text.deaths)(stats.read().total_deaths, stats.read().death_rate())} }
+                if let Some(cycle) = stats.read().cycle {
+                    span { {(text.cycle)(cycle.period, cycle.since)} }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: debug_hud(),
+                        onchange: move |evt| debug_hud.set(evt.checked()),
+                    }
+                    " {text.debug_info}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: log_console(),
+                        onchange: move |evt| log_console.set(evt.checked()),
+                    }
+                    " {text.log_console}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: show_cell_info(),
+                        onchange: move |evt| {
+                            show_cell_info.set(evt.checked());
+                            pointer.set(None);
+                        },
+                    }
+                    " {text.cell_info}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: inspector(),
+                        onchange: move |evt| {
+                            inspector.set(evt.checked());
+                            pointer.set(None);
+                        },
+                    }
+                    " {text.inspector}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: gpu(),
+                        onchange: move |evt| {
+                            gpu_error.set(None);
+                            gpu.set(evt.checked());
+                            if evt.checked() {
+                                run_on_gpu();
+                            }
+                        },
+                    }
+                    " {text.gpu_stepping}"
+                }
+                if let Some(err) = gpu_error() {
+                    span { class: "error", role: "alert", "{err}" }
+                }
+            }
+            if debug_hud() {
+                pre { class: "hud",
+                    {(text.state_hash)(board.state_hash())}
+                    "\n"
+                    {(text.board_dimensions)(board_width, board_height)}
+                    "\n"
+                    {(text.frame_times)(profile.read().tick_ms(), profile.read().render_ms())}
+                    "\n"
+                    "{board.rule}"
+                }
+            }
+            if log_console() {
+                LogConsole {}
+            }
+            div { class: "stats",
+                Sparkline {
+                    label: text.activity,
+                    values: Vec::from(stats.read().activity.clone()),
+                    max: 0.25,
+                }
+                Sparkline {
+                    label: text.entropy,
+                    values: Vec::from(stats.read().entropy.clone()),
+                    max: 1.0,
+                }
+            }
+
+            div { class: "controls",
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: describing(),
+                        onchange: move |evt| describing.set(evt.checked()),
+                    }
+                    " {text.describe_board}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: torus_view(),
+                        disabled: board.boundary != Boundary::Torus,
+                        onchange: move |evt| torus_view.set(evt.checked()),
+                    }
+                    " {text.torus_view}"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: space_time.read().is_some(),
+                        onchange: move |evt| space_time.set(evt.checked().then(SpaceTime::default)),
+                    }
+                    " {text.space_time_view}"
+                }
+            }
+            // Paused, the status follows every step, edit and scrub; running,
+            // it only moves every `ANNOUNCE_EVERY` generations.
+            div { class: "visually-hidden", role: "status", aria_live: "polite",
+                {
+                    let (generation, population) = if running() {
+                        announced()
+                    } else {
+                        (stats.read().generation, stats.read().last.population)
+                    };
+                    (text.status)(generation, population)
+                }
+            }
+            if describing() {
+                section { id: "board-description-{slot}", class: "description",
+                    p {
+                        {(text.board_summary)(board_width, board_height, board.population())}
+                        " "
+                        match board.bounding_box() {
+                            Some((top, left, bottom, right)) => (text.live_extent)(top + 1, bottom + 1, left + 1, right + 1),
+                            None => text.empty_board.to_string(),
+                        }
+                    }
+                    ul {
+                        for (row, runs) in describe::rows(&board, &view) {
+                            li { key: "{row}", {(text.row_runs)(row, &runs)} }
+                        }
+                    }
+                }
+            }
+
+            div { class: "board-area",
+                div {
+                    class: drawn.board_class(),
+                    role: "application",
+                    aria_label: text.board,
+                    aria_describedby: describing().then(|| format!("board-description-{slot}")),
+                    tabindex: 0,
+                    onkeydown: on_key,
+                    onmousedown: on_mouse_down,
+                    oncontextmenu: on_context_menu,
+                    onmousemove: on_mouse_move,
+                    onmouseup: move |_| end_drag(),
+                    onmouseleave: move |_| {
+                        end_drag();
+                        pointer.set(None);
+                    },
+                    ondragover: move |evt| evt.prevent_default(),
+                    ondrop: move |evt| {
+                        evt.prevent_default();
+                        evt.stop_propagation();
+                        drop_files(evt.files(), Some(cell_at(evt.element_coordinates())));
+                    },
+                    style: drawn.board_style(&view, cell_size),
+
+                    for row in view.row..view.row + view.rows {
+                        BoardRow {
+                            key: "{row}",
+                            look: RowLook::new((view.col..view.col + view.cols).map(|col| {
+                                let idx = row * board_width + col;
+                                let alive = board.cells[idx];
+                                let state = if alive { "alive" } else { "dead" };
+                                let on_row = highlighted_row == Some(row);
+                                let in_selection = selected.is_some_and(|area| area.contains(row, col));
+                                let under_cursor = cursor_cell == Some((row, col));
+                                let neighbor = inspecting.as_ref().and_then(|block| block.iter().position(|&at| at == Some((row, col))));
+                                let pasted = pasting.as_ref().zip(paste_origin).and_then(|(pattern, origin)| {
+                                    pattern.cell_over(origin, (row, col), board_width, board_height)
+                                });
+                                let count = (active_overlay == Overlay::Neighbors)
+                                    .then(|| board.inspect(row, col).map_or(0, |info| info.live_neighbors))
+                                    .filter(|&count| count > 0);
+                                let fill = match active_overlay {
+                                    Overlay::None => scoreboard
+                                        .as_ref()
+                                        .and_then(|race| race.owner(idx))
+                                        .map(|side| side.color().to_string())
+                                        .or_else(|| (trail_length > 0).then(|| trail_ages.color(idx, trail_length)).flatten())
+                                        .map(|color| format!(" background-color: {color};"))
+                                        .unwrap_or_default(),
+                                    Overlay::Heatmap => format!(" background-color: {};", heat.color(idx)),
+                                    Overlay::Diff => diff
+                                        .as_ref()
+                                        .and_then(|mask| diff_color(mask[idx], alive))
+                                        .map(|color| format!(" background-color: {color};"))
+                                        .unwrap_or_default(),
+                                    Overlay::Neighbors => format!(" font-size: {}px; line-height: {cell_size}px;", cell_size * 3 / 4),
+                                };
+                                CellLook {
+                                    class: format!(
+                                        "cell {state}{}{}{}{}{}",
+                                        if on_row { " sequencer-row" } else { "" },
+                                        if in_selection { " selected" } else { "" },
+                                        if under_cursor { " cursor" } else { "" },
+                                        match neighbor {
+                                            Some(4) => " inspected",
+                                            Some(_) => " neighbor",
+                                            None => "",
+                                        },
+                                        match pasted {
+                                            Some(true) => " paste-live",
+                                            Some(false) => " paste-dead",
+                                            None => "",
+                                        },
+                                    ),
+                                    style: format!("width: {cell_size}px; height: {cell_size}px;{fill}"),
+                                    count,
+                                }
+                            }).collect()),
+                        }
+                    }
+
+                    if let Some(spacing) = render.read().grid {
+                        GridLines { spacing, viewport: view, cell_size }
+                    }
+
+                    if !annotations.read().is_empty() || matches!(drag(), Some(Drag::Annotate(..))) {
+                        {
+                            let mut shown = annotations();
+                            // The label being placed, as it will land.
+                            if let Some(Drag::Annotate(at, to)) = drag() {
+                                let text = note_text();
+                                shown.push(Annotation { row: at.0, col: at.1, text, arrow: (to != at).then_some(to) });
+                            }
+                            rsx! {
+                                AnnotationLayer { annotations: shown, viewport: view, cell_size, slot }
+                            }
+                        }
+                    }
+
+                    if let Some(guides) = alignment.filter(|guides| !guides.is_empty()) {
+                        GuideLayer { guides, viewport: view, cell_size }
+                    }
+
+                    if let Some(ruler) = ruler().filter(|_| tool() == Tool::Measure) {
+                        RulerLayer { ruler, viewport: view, cell_size }
+                    }
+
+                    if let Some(tracker) = tracker() {
+                        TrackingLayer {
+                            tracks: tracker.tracks,
+                            generation: stats.read().generation,
+                            viewport: view,
+                            width: board_width,
+                            height: board_height,
+                            cell_size,
+                            followed: followed(),
+                        }
+                    }
+                }
+
+                if let Some((row, col)) = hovered.filter(|_| inspector()) {
+                    InspectorPanel { neighborhood: Neighborhood::of(&board, row, col), rule: board.rule }
+                }
+
+                if let Some((row, col)) = inspected().filter(|&(row, col)| row < board_height && col < board_width) {
+                    NeighborhoodView {
+                        neighborhood: Neighborhood::of(&board, row, col),
+                        on_close: move |_| inspected.set(None),
+                    }
+                }
+
+                if torus_view() && board.boundary == Boundary::Torus {
+                    TorusView { universe: board.clone() }
+                }
+
+                if let Some(space_time) = space_time() {
+                    SpaceTimeView { space_time, viewport: view }
+                }
+
+                if let Some(rival) = rival() {
+                    figure { class: "rival",
+                        BoardView { universe: rival.clone(), viewport: view, cell_size, render: drawn }
+                        figcaption { {(text.versus)(&board.rule.to_string(), &rival.rule.to_string())} }
+                    }
+                }
+
+                if !view.covers(board_width, board_height) {
+                    div { class: "navigator",
+                        Minimap {
+                            universe: board.clone(),
+                            viewport: view,
+                            on_jump: move |(row, col)| viewport.write().center_on(row, col, board_width, board_height),
+                        }
+                        div { class: "pan-buttons",
+                            button { aria_label: text.pan_up, onclick: move |_| pan(-pan_step, 0), "▲" }
+                            button { aria_label: text.pan_down, onclick: move |_| pan(pan_step, 0), "▼" }
+                            button { aria_label: text.pan_left, onclick: move |_| pan(0, -pan_step), "◀" }
+                            button { aria_label: text.pan_right, onclick: move |_| pan(0, pan_step), "▶" }
+                        }
+                    }
+                }
+            }
+
+            if let Some(((row, col), at)) = pointer().filter(|_| show_cell_info()) {
+                if let Ok(info) = board.inspect(row, col) {
+                    CellTooltip { info, at }
+                }
+            }
+
+            if let Some((_, at)) = menu() {
+                ContextMenu {
+                    at,
+                    stamp: last_placed.read().as_ref().map(|pattern| pattern.name.clone().unwrap_or_else(|| text.last_pattern.to_string())),
+                    on_pick: run_cell_action,
+                    on_close: move |_| menu.set(None),
+                }
+            }
+
+            if palette() {
+                CommandPalette { on_run: run_action, on_close: move |_| palette.set(false) }
+            }
+
+            if panels && grid_count() > 0 {
+                SeedGrid { count: grid_count(), rule: board.rule, slot }
+            }
+        }
+    }
+}
@@ -0,0 +1,427 @@
+// GPU stepping (experimental)
+//
+// For million-cell boards the CPU loop is the bottleneck, so this backend
+// runs the generation update as a compute shader instead. Two copies of the
+// board live in GPU memory and each generation steps from one to the other;
+// each batch only reads back the population, births and deaths. The cells
+// come back on request, which the app does a few times a second to redraw.
+// Only wrapping and dead edges are supported.
+//
+// In the browser the page's WebGPU (`navigator.gpu`) runs it, driven through
+// `document::eval`. Native builds take the GPU through `wgpu` instead, since
+// desktop webviews mostly have no WebGPU; the same WGSL shader runs on
+// Vulkan, Metal or DirectX 12.
+//
+// One cell is one `u32` on the GPU. Boards travel to and from the page packed
+// 32 cells to a word, low bit first.
+
+use life_engine::universe::{Boundary, Universe};
+
+pub use backend::GpuRun;
+
+/// One generation of a life-like rule, one invocation per cell.
+const SHADER: &str = r#"
+struct Params { width: u32, height: u32, birth: u32, survival: u32, wrap: u32 }
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells: array<u32>;
+@group(0) @binding(2) var<storage, read_write> next: array<u32>;
+// Population, births, deaths.
+@group(0) @binding(3) var<storage, read_write> counts: array<atomic<u32>, 3>;
+
+fn alive(row: i32, col: i32) -> u32 {
+    let width = i32(params.width);
+    let height = i32(params.height);
+    var r = row;
+    var c = col;
+    if (params.wrap == 1u) {
+        r = (r + height) % height;
+        c = (c + width) % width;
+    } else if (r < 0 || c < 0 || r >= height || c >= width) {
+        return 0u;
+    }
+    return cells[u32(r) * params.width + u32(c)];
+}
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+    var neighbours = 0u;
+    for (var d_row = -1; d_row <= 1; d_row++) {
+        for (var d_col = -1; d_col <= 1; d_col++) {
+            if (d_row != 0 || d_col != 0) {
+                neighbours += alive(i32(id.y) + d_row, i32(id.x) + d_col);
+            }
+        }
+    }
+    let idx = id.y * params.width + id.x;
+    let was = cells[idx];
+    let now = (select(params.birth, params.survival, was == 1u) >> neighbours) & 1u;
+    next[idx] = now;
+    if (now == 1u) {
+        atomicAdd(&counts[0], 1u);
+    }
+    if (now == 1u && was == 0u) {
+        atomicAdd(&counts[1], 1u);
+    }
+    if (now == 0u && was == 1u) {
+        atomicAdd(&counts[2], 1u);
+    }
+}
+"#;
+
+/// Birth and survival masks of `universe`'s rule, bit `n` for `n`
+/// neighbours, and 1 if its edges wrap or 0 if they're dead.
+fn params(universe: &Universe) -> Result<[u32; 3], String> {
+    let wrap = match universe.boundary {
+        Boundary::Torus => 1,
+        Boundary::Dead => 0,
+        _ => return Err("GPU stepping only handles wrapping and dead edges".to_string()),
+    };
+    let mask = |alive| (0..=8u8).filter(|&count| universe.rule.next(alive, count)).fold(0u32, |mask, count| mask | 1 << count);
+    Ok([mask(false), mask(true), wrap])
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use dioxus::prelude::*;
+    use life_engine::universe::{TickStats, Universe};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::SHADER;
+
+    /// The page side: sets up the device and pipeline, then answers `load`,
+    /// `step`, `cells` and `stop` messages until stopped.
+    const SCRIPT: &str = r#"
+    (async () => {
+        const adapter = navigator.gpu && await navigator.gpu.requestAdapter();
+        const device = adapter && await adapter.requestDevice();
+        if (!device) {
+            dioxus.send({ error: "WebGPU isn't available here" });
+            return;
+        }
+        const module = device.createShaderModule({ code: SHADER });
+        const pipeline = device.createComputePipeline({ layout: "auto", compute: { module, entryPoint: "main" } });
+        dioxus.send({});
+        let board = null;
+        const release = () => board && board.buffers.forEach(buffer => buffer.destroy());
+        const read = async (source, size) => {
+            const encoder = device.createCommandEncoder();
+            encoder.copyBufferToBuffer(source, 0, board.readback, 0, size);
+            device.queue.submit([encoder.finish()]);
+            await board.readback.mapAsync(GPUMapMode.READ, 0, size);
+            const data = new Uint32Array(board.readback.getMappedRange(0, size).slice(0));
+            board.readback.unmap();
+            return data;
+        };
+        while (true) {
+            const msg = await dioxus.recv();
+            if (msg.op === "load") {
+                release();
+                const cells = msg.width * msg.height;
+                const initial = new Uint32Array(cells);
+                for (let i = 0; i < cells; i++) initial[i] = (msg.words[i >> 5] >>> (i & 31)) & 1;
+                const storage = GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST;
+                const grids = [0, 1].map(() => device.createBuffer({ size: cells * 4, usage: storage }));
+                device.queue.writeBuffer(grids[0], 0, initial);
+                const params = device.createBuffer({ size: 32, usage: GPUBufferUsage.UNIFORM | GPUBufferUsage.COPY_DST });
+                device.queue.writeBuffer(params, 0, new Uint32Array([msg.width, msg.height, msg.birth, msg.survival, msg.wrap, 0, 0, 0]));
+                const counts = device.createBuffer({ size: 16, usage: storage });
+                const readback = device.createBuffer({ size: Math.max(cells * 4, 16), usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST });
+                const groups = [0, 1].map(i => device.createBindGroup({
+                    layout: pipeline.getBindGroupLayout(0),
+                    entries: [
+                        { binding: 0, resource: { buffer: params } },
+                        { binding: 1, resource: { buffer: grids[i] } },
+                        { binding: 2, resource: { buffer: grids[1 - i] } },
+                        { binding: 3, resource: { buffer: counts } },
+                    ],
+                }));
+                board = { width: msg.width, height: msg.height, cells, grids, groups, counts, readback, current: 0, buffers: [...grids, params, counts, readback] };
+                dioxus.send({});
+            } else if (msg.op === "step") {
+                const encoder = device.createCommandEncoder();
+                encoder.clearBuffer(board.counts);
+                for (let i = 0; i < msg.n; i++) {
+                    // Births and deaths add up over the batch; the population is the last generation's.
+                    if (i === msg.n - 1) encoder.clearBuffer(board.counts, 0, 4);
+                    const pass = encoder.beginComputePass();
+                    pass.setPipeline(pipeline);
+                    pass.setBindGroup(0, board.groups[board.current]);
+                    pass.dispatchWorkgroups(Math.ceil(board.width / 8), Math.ceil(board.height / 8));
+                    pass.end();
+                    board.current = 1 - board.current;
+                }
+                device.queue.submit([encoder.finish()]);
+                const [population, births, deaths] = await read(board.counts, 12);
+                dioxus.send({ population, births, deaths });
+            } else if (msg.op === "cells") {
+                const cells = await read(board.grids[board.current], board.cells * 4);
+                const words = new Array(Math.ceil(board.cells / 32)).fill(0);
+                for (let i = 0; i < board.cells; i++) if (cells[i]) words[i >> 5] |= 1 << (i & 31);
+                dioxus.send({ words: words.map(word => word >>> 0) });
+            } else if (msg.op === "stop") {
+                release();
+                device.destroy();
+                return;
+            }
+        }
+    })();
+    "#;
+
+    /// What the page answers with.
+    #[derive(Deserialize)]
+    struct Reply {
+        error: Option<String>,
+        population: Option<usize>,
+        births: Option<usize>,
+        deaths: Option<usize>,
+        words: Option<Vec<u32>>,
+    }
+
+    /// A board living on the GPU.
+    pub struct GpuRun {
+        eval: document::Eval,
+        /// Cells on the board, to unpack readbacks.
+        cells: usize,
+    }
+
+    impl GpuRun {
+        /// Open the GPU; `Err` with the reason when the browser has no WebGPU.
+        pub async fn open() -> Result<Self, String> {
+            let shader = serde_json::to_string(SHADER).expect("strings always serialize");
+            let mut run = Self { eval: document::eval(&SCRIPT.replace("SHADER", &shader)), cells: 0 };
+            run.reply().await?;
+            Ok(run)
+        }
+
+        /// Replace whatever is on the GPU with `universe`.
+        pub async fn load(&mut self, universe: &Universe) -> Result<(), String> {
+            let [birth, survival, wrap] = super::params(universe)?;
+            let mut words = vec![0u32; universe.cells.len().div_ceil(32)];
+            for (i, _) in universe.cells.iter().enumerate().filter(|(_, &alive)| alive) {
+                words[i / 32] |= 1 << (i % 32);
+            }
+            self.cells = universe.cells.len();
+            self.send(json!({
+                "op": "load",
+                "width": universe.width,
+                "height": universe.height,
+                "birth": birth,
+                "survival": survival,
+                "wrap": wrap,
+                "words": words,
+            }))
+            .await
+            .map(drop)
+        }
+
+        /// Advance `n` generations. Births and deaths are totals over all `n`;
+        /// the population is the final one, as with `Universe::step_n`.
+        pub async fn step(&mut self, n: u64) -> Result<TickStats, String> {
+            let reply = self.send(json!({ "op": "step", "n": n })).await?;
+            Ok(TickStats {
+                births: reply.births.unwrap_or_default(),
+                deaths: reply.deaths.unwrap_or_default(),
+                population: reply.population.unwrap_or_default(),
+            })
+        }
+
+        /// The board as it stands on the GPU.
+        pub async fn cells(&mut self) -> Result<Vec<bool>, String> {
+            let words = self.send(json!({ "op": "cells" })).await?.words.unwrap_or_default();
+            Ok((0..self.cells).map(|i| words.get(i / 32).is_some_and(|word| word & (1 << (i % 32)) != 0)).collect())
+        }
+
+        /// Free the GPU buffers and the device.
+        pub fn close(self) {
+            _ = self.eval.send(json!({ "op": "stop" }));
+        }
+
+        async fn send(&mut self, message: serde_json::Value) -> Result<Reply, String> {
+            self.eval.send(message).map_err(|err| err.to_string())?;
+            self.reply().await
+        }
+
+        async fn reply(&mut self) -> Result<Reply, String> {
+            let reply: Reply = self.eval.recv().await.map_err(|err| err.to_string())?;
+            match reply.error {
+                Some(err) => Err(err),
+                None => Ok(reply),
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::sync::mpsc::{self, TryRecvError};
+    use std::time::Duration;
+
+    use life_engine::universe::{TickStats, Universe};
+    use wgpu::util::DeviceExt;
+
+    use super::SHADER;
+
+    /// How long to wait between looks at a readback in flight, in ms.
+    const POLL_MS: u64 = 1;
+
+    /// The buffers of the board on the GPU.
+    struct Board {
+        width: u32,
+        height: u32,
+        /// One bind group per direction: from grid 0 to 1, and back.
+        groups: [wgpu::BindGroup; 2],
+        grids: [wgpu::Buffer; 2],
+        counts: wgpu::Buffer,
+        readback: wgpu::Buffer,
+        /// The grid holding the latest generation.
+        current: usize,
+    }
+
+    /// A board living on the GPU.
+    pub struct GpuRun {
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        pipeline: wgpu::ComputePipeline,
+        board: Option<Board>,
+    }
+
+    impl GpuRun {
+        /// Open the GPU; `Err` with the reason when there's no adapter.
+        pub async fn open() -> Result<Self, String> {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+            let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await.map_err(|err| err.to_string())?;
+            let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor::default()).await.map_err(|err| err.to_string())?;
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("life"),
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            });
+            let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("life"),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+            Ok(Self { device, queue, pipeline, board: None })
+        }
+
+        /// Replace whatever is on the GPU with `universe`.
+        pub async fn load(&mut self, universe: &Universe) -> Result<(), String> {
+            let [birth, survival, wrap] = super::params(universe)?;
+            let (width, height) = (universe.width as u32, universe.height as u32);
+            let cells: Vec<u32> = universe.cells.iter().map(|&alive| alive as u32).collect();
+            let size = (cells.len() * 4) as u64;
+            let storage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST;
+            let grids = [
+                self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor { label: Some("cells"), contents: &bytes(&cells), usage: storage }),
+                self.device.create_buffer(&wgpu::BufferDescriptor { label: Some("cells"), size, usage: storage, mapped_at_creation: false }),
+            ];
+            let params = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("params"),
+                contents: &bytes(&[width, height, birth, survival, wrap, 0, 0, 0]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+            let counts = self.device.create_buffer(&wgpu::BufferDescriptor { label: Some("counts"), size: 16, usage: storage, mapped_at_creation: false });
+            let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("readback"),
+                size: size.max(16),
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let layout = self.pipeline.get_bind_group_layout(0);
+            let groups = [0, 1].map(|from| {
+                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("life"),
+                    layout: &layout,
+                    entries: &[
+                        wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                        wgpu::BindGroupEntry { binding: 1, resource: grids[from].as_entire_binding() },
+                        wgpu::BindGroupEntry { binding: 2, resource: grids[1 - from].as_entire_binding() },
+                        wgpu::BindGroupEntry { binding: 3, resource: counts.as_entire_binding() },
+                    ],
+                })
+            });
+            self.board = Some(Board { width, height, groups, grids, counts, readback, current: 0 });
+            Ok(())
+        }
+
+        /// Advance `n` generations. Births and deaths are totals over all `n`;
+        /// the population is the final one, as with `Universe::step_n`.
+        pub async fn step(&mut self, n: u64) -> Result<TickStats, String> {
+            let board = self.board.as_mut().ok_or("nothing is loaded on the GPU")?;
+            let mut encoder = self.device.create_command_encoder(&Default::default());
+            encoder.clear_buffer(&board.counts, 0, None);
+            for i in 0..n {
+                // Births and deaths add up over the batch; the population is the last generation's.
+                if i == n - 1 {
+                    encoder.clear_buffer(&board.counts, 0, Some(4));
+                }
+                let mut pass = encoder.begin_compute_pass(&Default::default());
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &board.groups[board.current], &[]);
+                pass.dispatch_workgroups(board.width.div_ceil(8), board.height.div_ceil(8), 1);
+                drop(pass);
+                board.current = 1 - board.current;
+            }
+            self.queue.submit([encoder.finish()]);
+            let counts = self.read(Read::Counts, 12).await?;
+            Ok(TickStats { population: counts[0] as usize, births: counts[1] as usize, deaths: counts[2] as usize })
+        }
+
+        /// The board as it stands on the GPU.
+        pub async fn cells(&mut self) -> Result<Vec<bool>, String> {
+            let board = self.board.as_ref().ok_or("nothing is loaded on the GPU")?;
+            let size = board.width as u64 * board.height as u64 * 4;
+            Ok(self.read(Read::Cells, size).await?.into_iter().map(|cell| cell != 0).collect())
+        }
+
+        /// Free the GPU buffers and the device.
+        pub fn close(self) {
+            self.device.destroy();
+        }
+
+        /// The first `size` bytes of the counts or the latest grid, as words,
+        /// yielding to the app while the GPU gets them ready.
+        async fn read(&self, what: Read, size: u64) -> Result<Vec<u32>, String> {
+            let board = self.board.as_ref().ok_or("nothing is loaded on the GPU")?;
+            let source = match what {
+                Read::Counts => &board.counts,
+                Read::Cells => &board.grids[board.current],
+            };
+            let mut encoder = self.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(source, 0, &board.readback, 0, size);
+            self.queue.submit([encoder.finish()]);
+            let slice = board.readback.slice(..size);
+            let (mapped, done) = mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| _ = mapped.send(result));
+            loop {
+                self.device.poll(wgpu::PollType::Poll).map_err(|err| err.to_string())?;
+                match done.try_recv() {
+                    Ok(result) => break result.map_err(|err| err.to_string())?,
+                    Err(TryRecvError::Empty) => dioxus_time::sleep(Duration::from_millis(POLL_MS)).await,
+                    Err(TryRecvError::Disconnected) => return Err("the GPU dropped a readback".to_string()),
+                }
+            }
+            let words = slice.get_mapped_range().chunks_exact(4).map(|word| u32::from_le_bytes(word.try_into().unwrap())).collect();
+            board.readback.unmap();
+            Ok(words)
+        }
+    }
+
+    /// What `GpuRun::read` brings back.
+    enum Read {
+        Counts,
+        Cells,
+    }
+
+    /// `words` as the GPU lays them out.
+    fn bytes(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+}
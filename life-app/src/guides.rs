@@ -0,0 +1,107 @@
+// Placement guides
+//
+// Help for lining up a pattern being pasted or stamped with what is already
+// on the board. While a pattern floats under the mouse, a dashed line marks
+// every row and column where one of its edges or its centre lines up with an
+// edge or the centre of an object on the board. Parity snapping keeps the
+// pattern's corner on even rows and columns, so two copies always land an
+// even number of cells apart: gliders only meet the same way when their
+// offsets agree in parity.
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+
+use crate::viewport::Viewport;
+
+/// `origin` moved up and left onto an even row and column if `parity` is on.
+pub fn snap(origin: (usize, usize), parity: bool) -> (usize, usize) {
+    if parity {
+        (origin.0 & !1, origin.1 & !1)
+    } else {
+        origin
+    }
+}
+
+/// Rows and columns where a placement lines up with something, in half
+/// cells so the centre of an even-sized object can fall between two.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Guides {
+    pub rows: Vec<usize>,
+    pub cols: Vec<usize>,
+}
+
+impl Guides {
+    /// Alignments between `pattern` placed at `origin` and the objects with
+    /// bounding boxes `outlines` on a `width × height` torus.
+    pub fn find(pattern: &Pattern, origin: (usize, usize), outlines: &[(i32, i32, i32, i32)], width: usize, height: usize) -> Self {
+        let (top, left) = (origin.0 as i32, origin.1 as i32);
+        let placed = (top, left, top + pattern.height as i32 - 1, left + pattern.width as i32 - 1);
+        let mut guides = Self::default();
+        for &outline in outlines {
+            // An object the pattern already covers isn't something to line up with.
+            if overlaps(outline, placed, width as i32, height as i32) {
+                continue;
+            }
+            guides.rows.extend(matches((placed.0, placed.2), (outline.0, outline.2), height));
+            guides.cols.extend(matches((placed.1, placed.3), (outline.1, outline.3), width));
+        }
+        guides.rows.sort_unstable();
+        guides.rows.dedup();
+        guides.cols.sort_unstable();
+        guides.cols.dedup();
+        guides
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.cols.is_empty()
+    }
+}
+
+/// Edges and centre of the span `first..=last`, in half cells round a torus
+/// `size` cells across.
+fn marks((first, last): (i32, i32), size: usize) -> [usize; 3] {
+    let size = 2 * size as i32;
+    [2 * first, first + last, 2 * last].map(|half| half.rem_euclid(size) as usize)
+}
+
+/// The marks two spans have in common.
+fn matches(span: (i32, i32), other: (i32, i32), size: usize) -> Vec<usize> {
+    let theirs = marks(other, size);
+    marks(span, size).into_iter().filter(|half| theirs.contains(half)).collect()
+}
+
+/// Whether two boxes share a cell, allowing for either wrapping round.
+fn overlaps(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32), width: i32, height: i32) -> bool {
+    let spans_meet = |(a0, a1): (i32, i32), (b0, b1): (i32, i32), size: i32| {
+        // Shift `b` to start at or just after `a`'s start, then compare.
+        let shift = (b0 - a0).rem_euclid(size);
+        shift <= a1 - a0 || shift + (b1 - b0) >= size
+    };
+    spans_meet((a.0, a.2), (b.0, b.2), height) && spans_meet((a.1, a.3), (b.1, b.3), width)
+}
+
+/// The guides as dashed lines across the view.
+#[component]
+pub fn GuideLayer(guides: Guides, viewport: Viewport, cell_size: usize) -> Element {
+    let scale = cell_size as f64;
+    let (view_width, view_height) = (viewport.cols * cell_size, viewport.rows * cell_size);
+    // Distance from the view's corner to the middle of a half-cell position.
+    let offset = move |half: usize, start: usize| (half as f64 / 2.0 - start as f64 + 0.5) * scale;
+    let rows: Vec<f64> = guides.rows.iter().map(|&half| offset(half, viewport.row)).filter(|&y| (0.0..view_height as f64).contains(&y)).collect();
+    let cols: Vec<f64> = guides.cols.iter().map(|&half| offset(half, viewport.col)).filter(|&x| (0.0..view_width as f64).contains(&x)).collect();
+
+    rsx! {
+        svg {
+            class: "annotations",
+            "aria-hidden": "true",
+            width: view_width,
+            height: view_height,
+            for y in rows {
+                line { x1: 0, y1: y, x2: view_width, y2: y, style: "stroke: var(--gol-guide)", stroke_dasharray: "6 4" }
+            }
+            for x in cols {
+                line { x1: x, y1: 0, x2: x, y2: view_height, style: "stroke: var(--gol-guide)", stroke_dasharray: "6 4" }
+            }
+        }
+    }
+}
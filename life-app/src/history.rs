@@ -0,0 +1,580 @@
+// History store
+//
+// Every generation (and every edit) is kept as a frame so the board can be
+// rewound, scrubbed and replayed:
+// * **Keyframes** – a full packed copy of the board, every `keyframe_every`
+//   frames, so no state is ever more than that many deltas away.
+// * **Deltas** – the XOR of a frame with the one before it, stored as a list
+//   of flipped indices when sparse, packed bits when dense.
+//
+// Memory is capped: when a budget is exceeded the oldest keyframe and its
+// deltas are evicted together, so the front of the store is always a keyframe.
+//
+// The cursor is the frame the board currently shows. Stepping back or
+// scrubbing moves it into the past without losing anything; recording a new
+// frame there truncates the future first.
+//
+// Bookmarks name frames worth coming back to. They go when their frame does,
+// whether evicted or cut off by recording over a rewound future.
+//
+// The store serializes as is, for session bundles, except for the head board,
+// which `restore` rebuilds from the frames after loading.
+
+use std::collections::VecDeque;
+
+use dioxus::prelude::*;
+use life_engine::universe::TickStats;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Language;
+use crate::keys::{Command, Keymap};
+use crate::stats;
+
+/// What produced a frame.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Change {
+    /// The first frame, or a board loaded wholesale.
+    Start,
+    /// A generation, with the stats its tick reported.
+    Tick(TickStats),
+    /// That many generations run in one go, with their totals.
+    Ticks(u64, TickStats),
+    /// Painting, pasting or any other change by hand.
+    Edit,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+enum Encoding {
+    Key(Vec<u64>),
+    SparseXor(Vec<u32>),
+    DenseXor(Vec<u64>),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct Frame {
+    change: Change,
+    generation: u64,
+    encoding: Encoding,
+}
+
+impl Frame {
+    /// Approximate heap + inline size.
+    fn bytes(&self) -> usize {
+        let heap = match &self.encoding {
+            Encoding::Key(bits) | Encoding::DenseXor(bits) => bits.len() * 8,
+            Encoding::SparseXor(flips) => flips.len() * 4,
+        };
+        std::mem::size_of::<Self>() + heap
+    }
+}
+
+/// A named frame.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub generation: u64,
+    /// Frames recorded before this one since the store started, evicted
+    /// ones included.
+    frame: u64,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct History {
+    frames: VecDeque<Frame>,
+    cursor: usize,
+    /// Frames evicted so far, which is the number of the front frame.
+    #[serde(default)]
+    evicted: u64,
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+    /// Board at the last frame, which new deltas are taken against.
+    #[serde(skip)]
+    head: Vec<bool>,
+    /// Frames between keyframes.
+    pub keyframe_every: usize,
+    /// Most frames kept.
+    pub capacity: usize,
+    /// Most bytes kept.
+    pub budget: usize,
+    bytes: usize,
+}
+
+impl History {
+    pub fn new(cells: &[bool], generation: u64, keyframe_every: usize, capacity: usize, budget: usize) -> Self {
+        let mut history = Self {
+            frames: VecDeque::new(),
+            cursor: 0,
+            evicted: 0,
+            bookmarks: Vec::new(),
+            head: cells.to_vec(),
+            keyframe_every: keyframe_every.max(1),
+            capacity,
+            budget,
+            bytes: 0,
+        };
+        history.push(Frame { change: Change::Start, generation, encoding: Encoding::Key(pack(cells)) });
+        history
+    }
+
+    /// Start over from `cells`, keeping the settings.
+    pub fn reset(&mut self, cells: &[bool], generation: u64) {
+        *self = Self::new(cells, generation, self.keyframe_every, self.capacity, self.budget);
+    }
+
+    /// Finish loading a deserialized store for a board of `cells` cells:
+    /// check every frame fits it and rebuild the head. False if the store
+    /// doesn't belong to such a board, in which case it must not be used.
+    pub fn restore(&mut self, cells: usize) -> bool {
+        let words = cells.div_ceil(64);
+        let fits = |frame: &Frame| match &frame.encoding {
+            Encoding::Key(bits) | Encoding::DenseXor(bits) => bits.len() == words,
+            Encoding::SparseXor(flips) => flips.iter().all(|&idx| (idx as usize) < cells),
+        };
+        let starts_with_key = self.frames.front().is_some_and(|frame| matches!(frame.encoding, Encoding::Key(_)));
+        let end = self.evicted + self.frames.len() as u64;
+        let marks_fit = self.bookmarks.iter().all(|bookmark| (self.evicted..end).contains(&bookmark.frame));
+        if !starts_with_key || self.cursor >= self.frames.len() || !self.frames.iter().all(fits) || !marks_fit {
+            return false;
+        }
+        self.head = vec![false; cells];
+        self.head = self.state_at(self.frames.len() - 1);
+        self.bytes = self.frames.iter().map(Frame::bytes).sum();
+        true
+    }
+
+    /// Frames stored.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the frame on the board.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// True while the board shows a frame from the past.
+    pub fn is_rewound(&self) -> bool {
+        self.cursor + 1 < self.frames.len()
+    }
+
+    /// Generation of frame `index`.
+    pub fn generation_at(&self, index: usize) -> u64 {
+        self.frames[index].generation
+    }
+
+    /// Memory held, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Bookmarks, oldest frame first.
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Index of the frame `bookmark` names.
+    pub fn bookmark_index(&self, bookmark: &Bookmark) -> usize {
+        (bookmark.frame - self.evicted) as usize
+    }
+
+    /// Bookmark the frame on the board as `name`, replacing any bookmark it
+    /// already has.
+    pub fn add_bookmark(&mut self, name: String) {
+        let frame = self.evicted + self.cursor as u64;
+        self.bookmarks.retain(|bookmark| bookmark.frame != frame);
+        let at = self.bookmarks.partition_point(|bookmark| bookmark.frame < frame);
+        self.bookmarks.insert(at, Bookmark { name, generation: self.generation_at(self.cursor), frame });
+    }
+
+    pub fn remove_bookmark(&mut self, index: usize) {
+        self.bookmarks.remove(index);
+    }
+
+    /// Record the board after `change`. Anything after the cursor is
+    /// discarded first, since it no longer follows from this board.
+    pub fn record(&mut self, cells: &[bool], change: Change, generation: u64) {
+        if self.is_rewound() {
+            self.head = self.state_at(self.cursor);
+            while self.frames.len() > self.cursor + 1 {
+                if let Some(frame) = self.frames.pop_back() {
+                    self.bytes -= frame.bytes();
+                }
+            }
+            let end = self.evicted + self.frames.len() as u64;
+            self.bookmarks.retain(|bookmark| bookmark.frame < end);
+        }
+        let since_key = self.frames.iter().rev().position(|frame| matches!(frame.encoding, Encoding::Key(_)));
+        let encoding = if since_key.is_none_or(|distance| distance + 1 >= self.keyframe_every) {
+            Encoding::Key(pack(cells))
+        } else {
+            xor(&self.head, cells)
+        };
+        self.head.copy_from_slice(cells);
+        self.push(Frame { change, generation, encoding });
+        self.trim();
+    }
+
+    /// Move the cursor one frame back, rewriting `cells` to match. Returns
+    /// the change that was undone, or `None` at the oldest frame.
+    pub fn undo(&mut self, cells: &mut [bool]) -> Option<Change> {
+        if self.cursor == 0 {
+            return None;
+        }
+        let change = self.frames[self.cursor].change;
+        match &self.frames[self.cursor].encoding {
+            Encoding::Key(_) => cells.copy_from_slice(&self.state_at(self.cursor - 1)),
+            delta => apply(delta, cells),
+        }
+        self.cursor -= 1;
+        Some(change)
+    }
+
+    /// Move the cursor one frame forward, rewriting `cells` to match. Returns
+    /// the change that was replayed, or `None` at the newest frame.
+    pub fn redo(&mut self, cells: &mut [bool]) -> Option<Change> {
+        let frame = self.frames.get(self.cursor + 1)?;
+        apply(&frame.encoding, cells);
+        self.cursor += 1;
+        Some(frame.change)
+    }
+
+    /// Stats of the latest generation at or before frame `index`.
+    pub fn last_tick(&self, index: usize) -> TickStats {
+        self.frames
+            .range(..=index)
+            .rev()
+            .find_map(|frame| match frame.change {
+                Change::Tick(tick) => Some(tick),
+                Change::Ticks(generations, totals) => Some(stats::per_generation(generations, totals)),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Drop the oldest keyframe and its deltas until both limits hold again.
+    /// The newest keyframe run is never evicted.
+    pub fn trim(&mut self) {
+        while self.frames.len() > self.capacity || self.bytes > self.budget {
+            let Some(next_key) = self.frames.iter().skip(1).position(|frame| matches!(frame.encoding, Encoding::Key(_))) else {
+                break;
+            };
+            let evicted = next_key + 1;
+            if evicted > self.cursor {
+                break;
+            }
+            for frame in self.frames.drain(..evicted) {
+                self.bytes -= frame.bytes();
+            }
+            self.cursor -= evicted;
+            self.evicted += evicted as u64;
+            let front = self.evicted;
+            self.bookmarks.retain(|bookmark| bookmark.frame >= front);
+        }
+    }
+
+    fn push(&mut self, frame: Frame) {
+        self.bytes += frame.bytes();
+        self.frames.push_back(frame);
+        self.cursor = self.frames.len() - 1;
+    }
+
+    /// Rebuild the board at frame `index` from the nearest keyframe.
+    fn state_at(&self, index: usize) -> Vec<bool> {
+        let key = (0..=index)
+            .rev()
+            .find(|&i| matches!(self.frames[i].encoding, Encoding::Key(_)))
+            .expect("the first frame is always a keyframe");
+        let mut cells = vec![false; self.head.len()];
+        for frame in self.frames.range(key..=index) {
+            apply(&frame.encoding, &mut cells);
+        }
+        cells
+    }
+}
+
+fn pack(cells: &[bool]) -> Vec<u64> {
+    cells
+        .chunks(64)
+        .map(|chunk| chunk.iter().enumerate().fold(0u64, |word, (bit, &alive)| word | (alive as u64) << bit))
+        .collect()
+}
+
+/// XOR of two boards, in whichever encoding is smaller.
+fn xor(before: &[bool], after: &[bool]) -> Encoding {
+    let flips: Vec<u32> = before
+        .iter()
+        .zip(after)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(idx, _)| idx as u32)
+        .collect();
+    // A flip costs 32 bits sparse; packed costs one bit per cell.
+    if flips.len() * 32 < before.len() {
+        Encoding::SparseXor(flips)
+    } else {
+        let (before, after) = (pack(before), pack(after));
+        Encoding::DenseXor(before.iter().zip(&after).map(|(a, b)| a ^ b).collect())
+    }
+}
+
+/// Overwrite (keyframe) or flip (delta) `cells` according to one frame.
+fn apply(encoding: &Encoding, cells: &mut [bool]) {
+    match encoding {
+        Encoding::Key(bits) => {
+            for (idx, cell) in cells.iter_mut().enumerate() {
+                *cell = bits[idx / 64] >> (idx % 64) & 1 == 1;
+            }
+        }
+        Encoding::SparseXor(flips) => {
+            for &idx in flips {
+                cells[idx as usize] = !cells[idx as usize];
+            }
+        }
+        Encoding::DenseXor(bits) => {
+            for (idx, cell) in cells.iter_mut().enumerate() {
+                *cell ^= bits[idx / 64] >> (idx % 64) & 1 == 1;
+            }
+        }
+    }
+}
+
+/// Playback buttons, the scrubber with its memory limits, and the bookmarks
+/// of the board in `slot`. Stepping and seeking go back to the board, which
+/// owns everything a generation touches; `locked` turns off rewinding while
+/// the rival or a race is on, as their own states can't follow the board
+/// back.
+#[component]
+pub fn HistoryPanel(
+    mut history: Signal<History>,
+    mut running: Signal<bool>,
+    locked: bool,
+    slot: u64,
+    on_step: EventHandler,
+    on_step_back: EventHandler,
+    on_seek: EventHandler<usize>,
+) -> Element {
+    let language = use_context::<Signal<Language>>();
+    let keymap = use_context::<Signal<Keymap>>();
+    let text = language().strings();
+    let hint = move |command| keymap.read().hint(command);
+
+    let mut bookmark_name = use_signal(String::new);
+    let mut add_bookmark = move || {
+        let mut history = history.write();
+        let name = match bookmark_name.peek().trim() {
+            "" => (language.peek().strings().bookmark_default)(history.generation_at(history.cursor())),
+            name => name.to_string(),
+        };
+        history.add_bookmark(name);
+        bookmark_name.set(String::new());
+    };
+
+    rsx! {
+        div { class: "controls",
+            button { onclick: move |_| running.toggle(),
+                if running() { "{text.pause}{hint(Command::PlayPause)}" } else { "{text.resume}{hint(Command::PlayPause)}" }
+            }
+            button { onclick: move |_| on_step.call(()), disabled: running(), "{text.step}{hint(Command::Step)}" }
+            button {
+                onclick: move |_| on_step_back.call(()),
+                disabled: history.read().cursor() == 0 || locked,
+                "{text.back}{hint(Command::StepBack)}"
+            }
+            button {
+                onclick: move |_| {
+                    on_seek.call(0);
+                    running.set(true);
+                },
+                disabled: history.read().len() < 2 || locked,
+                "{text.replay}"
+            }
+            input {
+                r#type: "range",
+                aria_label: text.history_scrubber,
+                min: 0,
+                max: history.read().len() - 1,
+                value: history.read().cursor(),
+                list: "bookmarks-{slot}",
+                disabled: locked,
+                oninput: move |evt| {
+                    if let Ok(target) = evt.value().parse::<usize>() {
+                        running.set(false);
+                        on_seek.call(target);
+                    }
+                },
+            }
+            // Bookmarks show as ticks on the scrubber.
+            datalist { id: "bookmarks-{slot}",
+                for bookmark in history.read().bookmarks() {
+                    option { value: history.read().bookmark_index(bookmark), label: "{bookmark.name}" }
+                }
+            }
+            span {
+                {
+                    let history = history.read();
+                    (text.history_position)(
+                        history.generation_at(history.cursor()),
+                        history.generation_at(history.len() - 1),
+                        history.len(),
+                        history.bytes() / 1024,
+                    )
+                }
+            }
+            label {
+                "{text.keep} "
+                input {
+                    r#type: "number",
+                    aria_label: text.frames_kept,
+                    min: 1,
+                    value: "{history.read().capacity}",
+                    oninput: move |evt| {
+                        if let Ok(frames) = evt.value().parse::<usize>() {
+                            history.write().capacity = frames.max(1);
+                            history.write().trim();
+                        }
+                    },
+                }
+                " {text.frames_up_to} "
+                input {
+                    r#type: "number",
+                    aria_label: text.memory_kept,
+                    min: 0,
+                    value: "{history.read().budget / 1024}",
+                    oninput: move |evt| {
+                        if let Ok(kib) = evt.value().parse::<usize>() {
+                            history.write().budget = kib * 1024;
+                            history.write().trim();
+                        }
+                    },
+                }
+                " {text.kib}"
+            }
+        }
+        div { class: "controls bookmarks",
+            input {
+                r#type: "text",
+                aria_label: text.bookmark_name,
+                placeholder: text.bookmark_name,
+                value: "{bookmark_name}",
+                oninput: move |evt| bookmark_name.set(evt.value()),
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Enter {
+                        add_bookmark();
+                    }
+                },
+            }
+            button { onclick: move |_| add_bookmark(), "{text.add_bookmark}" }
+            for (index, bookmark) in history.read().bookmarks().iter().cloned().enumerate() {
+                span { class: "bookmark",
+                    button {
+                        disabled: locked,
+                        onclick: move |_| {
+                            running.set(false);
+                            let target = history.peek().bookmark_index(&bookmark);
+                            on_seek.call(target);
+                        },
+                        {(text.bookmark_label)(&bookmark.name, bookmark.generation)}
+                    }
+                    button {
+                        aria_label: text.remove_bookmark,
+                        onclick: move |_| history.write().remove_bookmark(index),
+                        "×"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use life_engine::universe::UniverseBuilder;
+
+    /// A history of `generations` ticks of a soup, and every board it went
+    /// through.
+    fn run(generations: u64, keyframe_every: usize) -> (History, Vec<Vec<bool>>) {
+        let mut universe = UniverseBuilder::new().size(32, 32).seed(4).build();
+        let mut history = History::new(&universe.cells, 0, keyframe_every, 1000, usize::MAX);
+        let mut boards = vec![universe.cells.clone()];
+        for generation in 1..=generations {
+            let stats = universe.tick();
+            history.record(&universe.cells, Change::Tick(stats), generation);
+            boards.push(universe.cells.clone());
+        }
+        (history, boards)
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_frames() {
+        let (mut history, boards) = run(20, 8);
+        let mut cells = boards[20].clone();
+        for back in (0..20).rev() {
+            assert!(matches!(history.undo(&mut cells), Some(Change::Tick(_))));
+            assert_eq!(cells, boards[back], "back to {back}");
+        }
+        assert_eq!(history.undo(&mut cells), None);
+        for (forward, board) in boards.iter().enumerate().skip(1) {
+            history.redo(&mut cells);
+            assert_eq!(&cells, board, "forward to {forward}");
+        }
+        assert_eq!(history.redo(&mut cells), None);
+    }
+
+    #[test]
+    fn recording_over_a_rewound_future_drops_it() {
+        let (mut history, boards) = run(10, 4);
+        let mut cells = boards[10].clone();
+        for _ in 0..4 {
+            history.undo(&mut cells);
+        }
+        history.add_bookmark("six".to_string());
+        cells[0] = !cells[0];
+        history.record(&cells, Change::Edit, 6);
+        assert_eq!(history.len(), 8);
+        assert!(!history.is_rewound());
+        history.undo(&mut cells);
+        assert_eq!(cells, boards[6]);
+        assert_eq!(history.bookmarks().len(), 1);
+    }
+
+    #[test]
+    fn trimming_keeps_within_capacity_from_a_keyframe() {
+        let (mut history, boards) = run(50, 5);
+        history.capacity = 12;
+        history.trim();
+        assert!(history.len() <= 12);
+        // The oldest frame left still reads back right.
+        let mut cells = boards[50].clone();
+        while history.undo(&mut cells).is_some() {}
+        assert_eq!(cells, boards[history.generation_at(0) as usize]);
+    }
+
+    #[test]
+    fn restores_after_a_round_trip_through_serde() {
+        let (history, boards) = run(12, 5);
+        let mut restored: History = serde_json::from_str(&serde_json::to_string(&history).unwrap()).unwrap();
+        assert!(restored.restore(32 * 32));
+        assert!(!restored.clone().restore(10));
+        let mut cells = boards[12].clone();
+        restored.undo(&mut cells);
+        assert_eq!(cells, boards[11]);
+    }
+
+    #[test]
+    fn a_batch_undoes_as_one_frame_with_its_per_generation_stats() {
+        let mut universe = UniverseBuilder::new().size(32, 32).seed(4).build();
+        let mut history = History::new(&universe.cells, 0, 8, 1000, usize::MAX);
+        let start = universe.cells.clone();
+        let tick = universe.tick();
+        history.record(&universe.cells, Change::Tick(tick), 1);
+        let totals = universe.step_n(10);
+        history.record(&universe.cells, Change::Ticks(10, totals), 11);
+        assert_eq!(history.last_tick(history.cursor()), stats::per_generation(10, totals));
+        let mut cells = universe.cells.clone();
+        assert_eq!(history.undo(&mut cells), Some(Change::Ticks(10, totals)));
+        assert_eq!(history.last_tick(history.cursor()), tick);
+        history.undo(&mut cells);
+        assert_eq!(cells, start);
+    }
+}
@@ -0,0 +1,1308 @@
+// UI languages
+//
+// Every string the UI shows is a field of `Strings`, and each language is one
+// `Strings` value, so a missing translation is a compile error rather than an
+// English word in a Spanish classroom. Strings with values in them are plain
+// functions, which lets each language put the values wherever its grammar
+// wants them.
+//
+// Parser diagnostics (a bad rule, a malformed RLE) come from the format
+// modules and stay in English; they quote the offending input back anyway.
+//
+// The starting language follows the browser (web) or `LANG` (native).
+
+use crate::explorer::Verdict;
+use life_engine::automata::sand::Material;
+use life_engine::automata::Mode;
+use life_engine::rule::Clause;
+use life_engine::universe::{Boundary, MergeMode, SetOp};
+
+/// A language the UI can be shown in.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// BCP 47 primary tag.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// The language's own name for itself, for the picker.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+
+    /// The language for a tag such as `es-MX` or `es_ES.UTF-8`; English when
+    /// there is no bundle for it.
+    pub fn from_tag(tag: &str) -> Self {
+        let primary = tag.split(['-', '_', '.']).next().unwrap_or_default().to_lowercase();
+        Self::ALL.into_iter().find(|language| language.code() == primary).unwrap_or_default()
+    }
+
+    /// The user's preferred language.
+    pub fn detect() -> Self {
+        backend::preferred().map(|tag| Self::from_tag(&tag)).unwrap_or_default()
+    }
+}
+
+/// Engine types with a name in the UI. The engine knows nothing of
+/// languages, so their labels are attached here instead.
+pub trait Localized {
+    fn label(self, text: &Strings) -> &'static str;
+}
+
+impl Localized for Boundary {
+    fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Boundary::Torus => text.boundary_torus,
+            Boundary::Dead => text.boundary_dead,
+            Boundary::Klein => text.boundary_klein,
+            Boundary::CrossSurface => text.boundary_cross_surface,
+            Boundary::Shifted(_) => text.boundary_shifted,
+        }
+    }
+}
+
+impl Localized for MergeMode {
+    fn label(self, text: &Strings) -> &'static str {
+        match self {
+            MergeMode::Replace => text.merge_replace,
+            MergeMode::Or => text.merge_or,
+            MergeMode::And => text.merge_and,
+            MergeMode::Xor => text.merge_xor,
+        }
+    }
+}
+
+impl Localized for SetOp {
+    fn label(self, text: &Strings) -> &'static str {
+        match self {
+            SetOp::Union => text.set_union,
+            SetOp::Intersection => text.set_intersection,
+            SetOp::Difference => text.set_difference,
+            SetOp::Xor => text.set_xor,
+        }
+    }
+}
+
+impl Localized for Mode {
+    fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Mode::Elementary => text.mode_elementary,
+            Mode::Turmites => text.mode_turmites,
+            Mode::Sandpile => text.mode_sandpile,
+            Mode::ForestFire => text.mode_forest_fire,
+            Mode::Epidemic => text.mode_epidemic,
+            Mode::Wator => text.mode_wator,
+            Mode::Sand => text.mode_sand,
+            Mode::Ising => text.mode_ising,
+            Mode::RuleTable => text.mode_rule_table,
+        }
+    }
+}
+
+impl Localized for Material {
+    fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Material::Empty => text.material_eraser,
+            Material::Sand => text.material_sand,
+            Material::Water => text.material_water,
+            Material::Wall => text.material_wall,
+        }
+    }
+}
+
+/// Everything the UI says, in one language.
+pub struct Strings {
+    pub title: &'static str,
+    pub language: &'static str,
+    pub tab_label: fn(number: usize) -> String,
+    pub new_tab: &'static str,
+    pub close_tab: &'static str,
+    pub mode: &'static str,
+    pub mode_life: &'static str,
+    pub mode_elementary: &'static str,
+    pub rule_number: &'static str,
+    pub rule_number_button: fn(rule: u8) -> String,
+    pub mode_turmites: &'static str,
+    pub program_label: fn(number: usize) -> String,
+    pub turmite_preset: &'static str,
+    pub transition_table: &'static str,
+    pub add_program: &'static str,
+    pub place_with: &'static str,
+    pub turmite_count: fn(count: usize) -> String,
+    pub mode_sandpile: &'static str,
+    pub grains_per_drop: &'static str,
+    pub drop_at_center: &'static str,
+    pub feed_center: &'static str,
+    pub sandpile_status: fn(grains: u64, stable: bool) -> String,
+    pub mode_forest_fire: &'static str,
+    pub growth_chance: fn(chance: f64) -> String,
+    pub lightning_chance: fn(chance: f64) -> String,
+    pub forest_status: fn(trees: usize, fires: usize) -> String,
+    pub mode_epidemic: &'static str,
+    pub infection_chance: fn(chance: f64) -> String,
+    pub infection_duration: fn(steps: u32) -> String,
+    pub immunity_loss_chance: fn(chance: f64) -> String,
+    pub epidemic_status: fn(susceptible: usize, infected: usize, recovered: usize) -> String,
+    pub mode_wator: &'static str,
+    pub fish_breed: &'static str,
+    pub shark_breed: &'static str,
+    pub shark_starve: &'static str,
+    pub fish: &'static str,
+    pub sharks: &'static str,
+    pub mode_sand: &'static str,
+    pub material_sand: &'static str,
+    pub material_water: &'static str,
+    pub material_wall: &'static str,
+    pub material_eraser: &'static str,
+    pub brush_radius: fn(radius: usize) -> String,
+    pub mode_ising: &'static str,
+    pub temperature: fn(temperature: f64) -> String,
+    pub critical_temperature: &'static str,
+    pub magnetization: &'static str,
+    pub mode_rule_table: &'static str,
+    pub upload_rule_table: &'static str,
+    pub rule_table_status: fn(name: &str, states: usize) -> String,
+    pub paint_state: &'static str,
+    pub state_label: fn(state: u8) -> String,
+
+    // Saved session
+    pub restore_prompt: fn(size: &str, rule: &str, generation: u64) -> String,
+    pub restore: &'static str,
+    pub discard: &'static str,
+
+    // Board controls
+    pub restart: &'static str,
+    pub compare_with: &'static str,
+    pub seed_grid: &'static str,
+    pub grid_off: &'static str,
+    pub mute: &'static str,
+    pub unmute: &'static str,
+    pub sound_unavailable: &'static str,
+    pub sequencer_row: &'static str,
+    pub custom_rule: &'static str,
+    pub surprise_me: &'static str,
+    pub previous_rule: &'static str,
+    pub next_rule: &'static str,
+    pub star_rule: &'static str,
+    pub starred_rules: &'static str,
+    pub rule_score: fn(points: u8, verdict: Verdict) -> String,
+    pub promising_rules: &'static str,
+    pub boundary: &'static str,
+    pub boundary_torus: &'static str,
+    pub boundary_dead: &'static str,
+    pub boundary_klein: &'static str,
+    pub boundary_cross_surface: &'static str,
+    pub boundary_shifted: &'static str,
+    pub boundary_shift: &'static str,
+    pub grow_board: &'static str,
+    pub crop_to_content: &'static str,
+    pub center_pattern: &'static str,
+    pub overlay_none: &'static str,
+    pub overlay_heatmap: &'static str,
+    pub overlay_diff: &'static str,
+    pub overlay_neighbors: &'static str,
+    pub no_grid: &'static str,
+    pub grid_every: fn(spacing: usize) -> String,
+    pub square_cells: &'static str,
+    pub round_cells: &'static str,
+    pub no_gap: &'static str,
+    pub gap: fn(px: usize) -> String,
+    pub no_fade: &'static str,
+    pub fade: fn(ms: u64) -> String,
+    pub crossfade: &'static str,
+    pub no_trails: &'static str,
+    pub trails: fn(generations: u16) -> String,
+    pub snapshot: &'static str,
+    pub drop_snapshot: &'static str,
+    pub set_union: &'static str,
+    pub set_intersection: &'static str,
+    pub set_difference: &'static str,
+    pub set_xor: &'static str,
+    pub cells_differ: fn(count: usize) -> String,
+    pub update_delay: fn(ms: u64) -> String,
+    pub reduced_motion: &'static str,
+    pub run_in_background: &'static str,
+    pub battery_saver: &'static str,
+    pub speed_max: &'static str,
+    pub update_delay_max: &'static str,
+
+    // Playback
+    pub pause: &'static str,
+    pub resume: &'static str,
+    pub step: &'static str,
+    pub back: &'static str,
+    pub replay: &'static str,
+    pub history_position: fn(generation: u64, latest: u64, frames: usize, kib: usize) -> String,
+    pub keep: &'static str,
+    pub frames_up_to: &'static str,
+    pub kib: &'static str,
+
+    // Tools and selection
+    pub draw: &'static str,
+    pub select: &'static str,
+    pub annotate: &'static str,
+    pub annotation_text: &'static str,
+    pub annotation_default: &'static str,
+    pub annotations: &'static str,
+    pub annotation_label: fn(text: &str, row: usize, col: usize) -> String,
+    pub remove_annotation: &'static str,
+    pub clear_annotations: &'static str,
+    pub measure: &'static str,
+    pub ruler_readout: fn(dx: isize, dy: isize, distance: f64, steps: usize) -> String,
+    pub stopwatch_mark: &'static str,
+    pub stopwatch_since: fn(generations: u64) -> String,
+    pub stopwatch_between: fn(generations: u64) -> String,
+    pub speed_readout: fn(speed: &str) -> String,
+    pub selection: fn(cols: usize, rows: usize) -> String,
+    pub find_predecessor: &'static str,
+    pub predecessor_limit: fn(edge: usize) -> String,
+    pub searching_predecessor: &'static str,
+    pub cancel_search: &'static str,
+    pub copy_rle: &'static str,
+    pub clear_selection: &'static str,
+    pub keyboard_cursor: &'static str,
+    pub cursor_position: fn(row: usize, col: usize, alive: bool) -> String,
+    pub predecessor_found: &'static str,
+    pub apply_predecessor: &'static str,
+    pub garden_of_eden: &'static str,
+    pub predecessor_gave_up: &'static str,
+
+    // Census
+    pub analyze: &'static str,
+    pub hide: &'static str,
+    pub apgcode_placeholder: &'static str,
+    pub place_apgcode: &'static str,
+    pub still_life: fn(cells: usize) -> String,
+    pub oscillator: fn(period: u32) -> String,
+    pub spaceship: fn(period: u32) -> String,
+    pub unstable: &'static str,
+    pub orthogonal: &'static str,
+    pub diagonal: &'static str,
+    pub oblique: &'static str,
+
+    // Patterns and import
+    pub find_pattern: &'static str,
+    pub fetch_lifewiki: &'static str,
+    pub downloading: &'static str,
+    pub placed: fn(name: &str) -> String,
+    pub pattern_author: fn(author: &str) -> String,
+    pub ascii_placeholder: &'static str,
+    pub export_text: &'static str,
+    pub export_packed: &'static str,
+    pub load_packed: &'static str,
+    pub export_session: &'static str,
+    pub import_session: &'static str,
+    pub trim: &'static str,
+    pub place_text: &'static str,
+    pub seed_from_image: &'static str,
+    pub threshold: &'static str,
+    pub dark_cells_live: &'static str,
+    pub forget_image: &'static str,
+    pub drop_hint: fn(paste: Option<&str>) -> String,
+    pub paste_hint: fn(cancel: Option<&str>) -> String,
+    pub paste_mode: &'static str,
+    pub snap_parity: &'static str,
+    pub library_summary: fn(count: usize) -> String,
+    pub library_name: &'static str,
+    pub library_tags: &'static str,
+    pub save_to_library: &'static str,
+    pub library_needs_selection: &'static str,
+    pub library_default: fn(number: usize) -> String,
+    pub search_library: &'static str,
+    pub library_empty: &'static str,
+    pub stamp_from_library: &'static str,
+    pub remove_from_library: &'static str,
+    pub import_archive: &'static str,
+    pub importing_archive: fn(done: usize, total: usize) -> String,
+    pub archive_imported: fn(added: usize, skipped: usize) -> String,
+    pub library_not_saved: fn(err: &str) -> String,
+    #[cfg(feature = "fullstack")]
+    pub gallery: &'static str,
+    #[cfg(feature = "fullstack")]
+    pub gallery_author: &'static str,
+    #[cfg(feature = "fullstack")]
+    pub gallery_upload: &'static str,
+    #[cfg(feature = "fullstack")]
+    pub gallery_uploaded: fn(name: &str) -> String,
+    #[cfg(feature = "fullstack")]
+    pub gallery_search: &'static str,
+    #[cfg(feature = "fullstack")]
+    pub gallery_empty: &'static str,
+    #[cfg(feature = "fullstack")]
+    pub gallery_like: &'static str,
+    pub merge_replace: &'static str,
+    pub merge_or: &'static str,
+    pub merge_and: &'static str,
+    pub merge_xor: &'static str,
+
+    // Spaceship tracking
+    pub track_spaceships: &'static str,
+    pub measured_velocity: fn(cols: f64, rows: f64) -> String,
+    pub follow: &'static str,
+    pub unfollow: &'static str,
+
+    // Soup race and search
+    pub start_race: &'static str,
+    pub race_for: &'static str,
+    pub generations: &'static str,
+    pub race_generation: fn(generation: u64, length: u64) -> String,
+    pub left: &'static str,
+    pub right: &'static str,
+    pub race_side: fn(side: &str, population: usize, invaded: usize) -> String,
+    pub wins: fn(side: &str) -> String,
+    pub draw_game: &'static str,
+    pub start_search: &'static str,
+    pub stop_search: &'static str,
+    pub cap: &'static str,
+    pub soups_tried: fn(count: u64) -> String,
+    pub longest_lived: &'static str,
+    pub most_expanding: &'static str,
+    pub seed: &'static str,
+    pub lifespan: &'static str,
+    pub final_population: &'static str,
+    pub span: &'static str,
+    pub load: &'static str,
+    pub evolve_patterns: &'static str,
+    pub goal: &'static str,
+    pub goal_longevity: &'static str,
+    pub goal_growth: &'static str,
+    pub goal_reach: &'static str,
+    pub target_cell: &'static str,
+    pub target_row: &'static str,
+    pub target_col: &'static str,
+    pub start_evolution: &'static str,
+    pub stop_evolution: &'static str,
+    pub evolution_status: fn(round: u64, evaluated: u64, best: Option<i64>) -> String,
+    pub load_best: &'static str,
+    pub benchmark: &'static str,
+    pub gpu_stepping: &'static str,
+    pub benchmark_hint: fn(size: usize, seconds: f64) -> String,
+
+    // Stats
+    pub seed_value: fn(seed: u64) -> String,
+    pub generation: fn(generation: u64) -> String,
+    pub population: fn(population: usize) -> String,
+    pub births: fn(total: u64, rate: f64) -> String,
+    pub deaths: fn(total: u64, rate: f64) -> String,
+    pub activity: &'static str,
+    pub entropy: &'static str,
+    pub versus: fn(ours: &str, theirs: &str) -> String,
+    pub cycle: fn(period: u64, since: u64) -> String,
+    pub debug_info: &'static str,
+    pub log_console: &'static str,
+    pub log_level: &'static str,
+    pub log_error: &'static str,
+    pub log_warn: &'static str,
+    pub log_info: &'static str,
+    pub log_debug: &'static str,
+    pub log_trace: &'static str,
+    pub save_log: &'static str,
+    pub clear_log: &'static str,
+    pub empty_log: &'static str,
+    pub cell_info: &'static str,
+    pub cell_readout: fn(row: usize, col: usize, alive: bool, live_neighbors: u8) -> String,
+    pub inspector: &'static str,
+    pub inspector_cell: fn(row: usize, col: usize) -> String,
+    pub rule_clause: fn(clause: Clause, live_neighbors: u8, counts: &str) -> String,
+    pub state_hash: fn(hash: u64) -> String,
+    pub board_dimensions: fn(width: usize, height: usize) -> String,
+    pub frame_times: fn(tick_ms: f64, render_ms: f64) -> String,
+
+    // Screen readers
+    pub board: &'static str,
+    pub board_size: &'static str,
+    pub overlay: &'static str,
+    pub grid_lines: &'static str,
+    pub cell_shape: &'static str,
+    pub cell_gap: &'static str,
+    pub fade_effect: &'static str,
+    pub trail_length: &'static str,
+    pub history_scrubber: &'static str,
+    pub bookmark_name: &'static str,
+    pub add_bookmark: &'static str,
+    pub bookmark_default: fn(generation: u64) -> String,
+    pub bookmark_label: fn(name: &str, generation: u64) -> String,
+    pub remove_bookmark: &'static str,
+    pub frames_kept: &'static str,
+    pub memory_kept: &'static str,
+    pub rule_preset: &'static str,
+    pub rule_notation: &'static str,
+    pub pattern_text: &'static str,
+    pub pan_up: &'static str,
+    pub pan_down: &'static str,
+    pub pan_left: &'static str,
+    pub pan_right: &'static str,
+    pub minimap: &'static str,
+    pub status: fn(generation: u64, population: usize) -> String,
+    pub describe_board: &'static str,
+    pub torus_view: &'static str,
+    pub space_time_view: &'static str,
+    pub space_time_caption: fn(layers: usize) -> String,
+    pub export_voxels: &'static str,
+    pub board_summary: fn(width: usize, height: usize, population: usize) -> String,
+    pub live_extent: fn(top: usize, bottom: usize, left: usize, right: usize) -> String,
+    pub empty_board: &'static str,
+    pub row_runs: fn(row: usize, runs: &str) -> String,
+
+    // Keyboard shortcuts
+    pub keyboard_shortcuts: &'static str,
+    pub press_key: &'static str,
+    pub reset_shortcuts: &'static str,
+    pub shortcut_conflict: fn(shortcut: &str, command: &str) -> String,
+    pub command_play_pause: &'static str,
+    pub command_cursor_action: &'static str,
+    pub command_paste: &'static str,
+    pub command_cancel: &'static str,
+    pub command_move_up: &'static str,
+    pub command_move_down: &'static str,
+    pub command_move_left: &'static str,
+    pub command_move_right: &'static str,
+    pub command_shift_up: &'static str,
+    pub command_shift_down: &'static str,
+    pub command_shift_left: &'static str,
+    pub command_shift_right: &'static str,
+    pub command_palette: &'static str,
+    pub palette_placeholder: &'static str,
+    pub palette_place: fn(name: &str) -> String,
+    pub palette_rule: fn(name: &str, notation: &str) -> String,
+    pub palette_overlay: fn(overlay: &str) -> String,
+    pub menu_toggle: &'static str,
+    pub menu_stamp: fn(name: &str) -> String,
+    pub last_pattern: &'static str,
+    pub menu_paste: &'static str,
+    pub menu_center: &'static str,
+    pub menu_inspect: &'static str,
+    pub neighborhood_summary: fn(row: usize, col: usize, live_neighbors: u8, alive: bool, next: bool) -> String,
+    pub close: &'static str,
+}
+
+pub const ENGLISH: Strings = Strings {
+    title: "Conway's Game of Life",
+    language: "Language",
+    tab_label: |number| format!("Board {number}"),
+    new_tab: "New tab",
+    close_tab: "Close tab",
+    mode: "Mode",
+    mode_life: "Game of Life",
+    mode_elementary: "Elementary automaton (1D)",
+    rule_number: "Rule",
+    rule_number_button: |rule| format!("Rule {rule}"),
+    mode_turmites: "Turmites",
+    program_label: |number| format!("Program {number}"),
+    turmite_preset: "Start from…",
+    transition_table: "Transition table",
+    add_program: "Add program",
+    place_with: "Clicks place a turmite running",
+    turmite_count: |count| format!("{count} turmites"),
+    mode_sandpile: "Abelian sandpile",
+    grains_per_drop: "Grains per click",
+    drop_at_center: "Drop at centre",
+    feed_center: "Grains on the centre per step",
+    sandpile_status: |grains, stable| format!("{grains} grains, {}", if stable { "stable" } else { "toppling" }),
+    mode_forest_fire: "Forest fire",
+    growth_chance: |chance| format!("Tree growth p = {chance}"),
+    lightning_chance: |chance| format!("Lightning f = {chance}"),
+    forest_status: |trees, fires| format!("{trees} trees, {fires} burning"),
+    mode_epidemic: "SIR epidemic",
+    infection_chance: |chance| format!("Infection chance {chance}"),
+    infection_duration: |steps| format!("Illness lasts {steps} steps"),
+    immunity_loss_chance: |chance| format!("Immunity loss {chance}"),
+    epidemic_status: |susceptible, infected, recovered| format!("{susceptible} susceptible, {infected} infected, {recovered} recovered"),
+    mode_wator: "Wa-Tor",
+    fish_breed: "Fish breed after",
+    shark_breed: "Sharks breed after",
+    shark_starve: "Sharks starve after",
+    fish: "Fish",
+    sharks: "Sharks",
+    mode_sand: "Falling sand",
+    material_sand: "Sand",
+    material_water: "Water",
+    material_wall: "Wall",
+    material_eraser: "Eraser",
+    brush_radius: |radius| format!("Brush radius {radius}"),
+    mode_ising: "Ising model",
+    temperature: |temperature| format!("Temperature {temperature:.2}"),
+    critical_temperature: "Critical temperature",
+    magnetization: "Magnetization |m|",
+    mode_rule_table: "Rule table",
+    upload_rule_table: "Load .rule file",
+    rule_table_status: |name, states| format!("{name}: {states} states"),
+    paint_state: "Paint state",
+    state_label: |state| format!("State {state}"),
+
+    restore_prompt: |size, rule, generation| format!("Restore last session? ({size}, {rule}, generation {generation})"),
+    restore: "Restore",
+    discard: "Discard",
+
+    restart: "Restart",
+    compare_with: "Compare with",
+    seed_grid: "Seed grid",
+    grid_off: "Off",
+    mute: "Mute sound",
+    unmute: "Unmute sound",
+    sound_unavailable: "Sound unavailable",
+    sequencer_row: "Sequencer row",
+    custom_rule: "Custom",
+    surprise_me: "Surprise me",
+    previous_rule: "Previous rule",
+    next_rule: "Next rule",
+    star_rule: "Star this rule",
+    starred_rules: "Starred:",
+    rule_score: |points, verdict| {
+        let verdict = match verdict {
+            Verdict::Extinct => "dies out",
+            Verdict::Exploded => "explodes",
+            Verdict::Frozen => "freezes",
+            Verdict::Chaotic => "chaotic",
+            Verdict::Lively => "lively",
+        };
+        format!("Score {points} ({verdict})")
+    },
+    promising_rules: "Promising rules",
+    boundary: "Edges",
+    boundary_torus: "Wrapping edges",
+    boundary_dead: "Dead edges",
+    boundary_klein: "Klein bottle",
+    boundary_cross_surface: "Cross-surface (projective plane)",
+    boundary_shifted: "Shifted torus",
+    boundary_shift: "Rows of shift",
+    grow_board: "Grow at the edges",
+    crop_to_content: "Crop to content",
+    center_pattern: "Center pattern",
+    overlay_none: "No overlay",
+    overlay_heatmap: "Activity heatmap",
+    overlay_diff: "Diff vs snapshot/rival",
+    overlay_neighbors: "Neighbour counts",
+    no_grid: "No grid",
+    grid_every: |spacing| if spacing == 1 { "Grid every cell".to_string() } else { format!("Grid every {spacing} cells") },
+    square_cells: "Square cells",
+    round_cells: "Round cells",
+    no_gap: "No gap",
+    gap: |px| format!("{px}px gap"),
+    no_fade: "No fade",
+    fade: |ms| format!("Fade {ms}ms"),
+    crossfade: "Crossfade slow generations",
+    no_trails: "No trails",
+    trails: |generations| format!("Trails {generations} gens"),
+    snapshot: "Snapshot",
+    drop_snapshot: "Drop snapshot",
+    set_union: "∪ Add snapshot",
+    set_intersection: "∩ Keep snapshot overlap",
+    set_difference: "− Remove snapshot",
+    set_xor: "⊕ Xor snapshot",
+    cells_differ: |count| format!("{count} cells differ"),
+    update_delay: |ms| format!("Update delay: {ms}ms"),
+    reduced_motion: "Reduced motion",
+    run_in_background: "Keep running in background tabs",
+    battery_saver: "Battery saver",
+    speed_max: "Max",
+    update_delay_max: "Update delay: as fast as possible",
+
+    pause: "Pause",
+    resume: "Resume",
+    step: "Step",
+    back: "Back",
+    replay: "Replay",
+    history_position: |generation, latest, frames, kib| format!("Gen {generation} of {latest} · {frames} frames ({kib} KiB)"),
+    keep: "keep",
+    frames_up_to: "frames, up to",
+    kib: "KiB",
+
+    draw: "Draw",
+    select: "Select",
+    annotate: "Annotate",
+    annotation_text: "Label text",
+    annotation_default: "Note",
+    annotations: "Labels:",
+    annotation_label: |text, row, col| format!("{text} ({row}, {col})"),
+    remove_annotation: "Remove label",
+    clear_annotations: "Clear labels",
+    measure: "Measure",
+    ruler_readout: |dx, dy, distance, steps| format!("Δx {dx}, Δy {dy} · {distance:.2} cells apart · {steps} steps"),
+    stopwatch_mark: "Mark generation",
+    stopwatch_since: |generations| format!("{generations} generations since the mark"),
+    stopwatch_between: |generations| format!("{generations} generations between the marks"),
+    speed_readout: |speed| format!("speed {speed}"),
+    selection: |cols, rows| format!("Selection {cols}×{rows}"),
+    find_predecessor: "Find predecessor",
+    predecessor_limit: |edge| format!("Regions up to {edge}×{edge}"),
+    searching_predecessor: "Searching for a predecessor",
+    cancel_search: "Stop search",
+    copy_rle: "Copy as RLE",
+    clear_selection: "Clear selection",
+    keyboard_cursor: "Keyboard cursor",
+    cursor_position: |row, col, alive| format!("Row {row}, column {col}: {}", if alive { "alive" } else { "dead" }),
+    predecessor_found: "Predecessor found.",
+    apply_predecessor: "Apply predecessor",
+    garden_of_eden: "No predecessor exists: this region is a Garden of Eden.",
+    predecessor_gave_up: "Search budget exhausted without a predecessor: likely a Garden of Eden.",
+
+    analyze: "Analyze",
+    hide: "Hide",
+    apgcode_placeholder: "apgcode, e.g. xq4_153",
+    place_apgcode: "Place apgcode",
+    still_life: |cells| format!("still life ({cells} cells)"),
+    oscillator: |period| format!("p{period} oscillator"),
+    spaceship: |period| format!("p{period} spaceship"),
+    unstable: "unstable / unidentified",
+    orthogonal: "orthogonal",
+    diagonal: "diagonal",
+    oblique: "oblique",
+
+    find_pattern: "Find a pattern…",
+    fetch_lifewiki: "Fetch from LifeWiki",
+    downloading: "Downloading…",
+    placed: |name| format!("Placed {name}."),
+    pattern_author: |author| format!("By {author}."),
+    ascii_placeholder: "Paste .O rows here…",
+    export_text: "Export as text",
+    export_packed: "Export packed board",
+    load_packed: "Load packed board",
+    export_session: "Export session",
+    import_session: "Import session",
+    trim: "Trim to live cells",
+    place_text: "Place text",
+    seed_from_image: "Seed from image",
+    threshold: "threshold",
+    dark_cells_live: "Dark cells live",
+    forget_image: "Forget image",
+    drop_hint: |paste| match paste {
+        Some(keys) => format!("…or drop .rle, .cells, .lif or image files anywhere, or paste with {keys}"),
+        None => "…or drop .rle, .cells, .lif or image files anywhere".to_string(),
+    },
+    paste_hint: |cancel| match cancel {
+        Some(keys) => format!("Click the board to drop the pasted pattern ({keys} cancels)."),
+        None => "Click the board to drop the pasted pattern.".to_string(),
+    },
+    paste_mode: "Placing patterns",
+    snap_parity: "Snap to even cells",
+    library_summary: |count| format!("My patterns ({count})"),
+    library_name: "Name",
+    library_tags: "Tags, comma-separated",
+    save_to_library: "Save selection",
+    library_needs_selection: "Select part of the board to save it",
+    library_default: |n| format!("Pattern {n}"),
+    search_library: "Search my patterns",
+    library_empty: "Nothing saved yet.",
+    stamp_from_library: "Stamp: click the board to place it",
+    remove_from_library: "Remove from library",
+    import_archive: "Import .zip",
+    importing_archive: |done, total| format!("Importing {done} of {total}…"),
+    archive_imported: |added, skipped| match skipped {
+        0 => format!("Imported {added} patterns."),
+        _ => format!("Imported {added} patterns; {skipped} files couldn't be read."),
+    },
+    library_not_saved: |err| format!("The library couldn't be saved ({err}); changes will be lost on reload."),
+    #[cfg(feature = "fullstack")]
+    gallery: "Community gallery",
+    #[cfg(feature = "fullstack")]
+    gallery_author: "Your name (optional)",
+    #[cfg(feature = "fullstack")]
+    gallery_upload: "Share selection",
+    #[cfg(feature = "fullstack")]
+    gallery_uploaded: |name| format!("Shared “{name}”."),
+    #[cfg(feature = "fullstack")]
+    gallery_search: "Search the gallery",
+    #[cfg(feature = "fullstack")]
+    gallery_empty: "No patterns found.",
+    #[cfg(feature = "fullstack")]
+    gallery_like: "Like",
+    merge_replace: "Replace what's under it",
+    merge_or: "Add to the board (or)",
+    merge_and: "Keep only overlap (and)",
+    merge_xor: "Flip cells (xor)",
+
+    track_spaceships: "Track spaceships",
+    measured_velocity: |cols, rows| format!("measured ({cols:+.3}, {rows:+.3})/gen"),
+    follow: "Follow",
+    unfollow: "Unfollow",
+
+    start_race: "Start soup race",
+    race_for: "for",
+    generations: "generations",
+    race_generation: |generation, length| format!("Gen {generation}/{length}"),
+    left: "Left",
+    right: "Right",
+    race_side: |side, population, invaded| format!("{side} {population} (invaded {invaded})"),
+    wins: |side| format!("{side} wins!"),
+    draw_game: "Draw!",
+    start_search: "Start soup search",
+    stop_search: "Stop soup search",
+    cap: "cap",
+    soups_tried: |count| format!("{count} soups tried"),
+    longest_lived: "Longest-lived",
+    most_expanding: "Most expanding",
+    seed: "Seed",
+    lifespan: "Lifespan",
+    final_population: "Final pop.",
+    span: "Span",
+    load: "Load",
+    evolve_patterns: "Evolve patterns (experimental)",
+    goal: "Goal",
+    goal_longevity: "Longest life",
+    goal_growth: "Most growth",
+    goal_reach: "Reach a cell",
+    target_cell: "target (row, column from the seed's corner)",
+    target_row: "Target row",
+    target_col: "Target column",
+    start_evolution: "Start evolving",
+    stop_evolution: "Stop evolving",
+    evolution_status: |round, evaluated, best| match best {
+        Some(best) => format!("Round {round}, {evaluated} seeds tried, best fitness {best}"),
+        None => format!("Round {round}, {evaluated} seeds tried"),
+    },
+    load_best: "Load best",
+    benchmark: "Benchmark",
+    gpu_stepping: "Step on the GPU (experimental)",
+    benchmark_hint: |size, seconds| format!("Runs an acorn on a {size}×{size} board for {seconds}s per engine, without drawing"),
+
+    seed_value: |seed| format!("Seed: {seed}"),
+    generation: |generation| format!("Generation: {generation}"),
+    population: |population| format!("Population: {population}"),
+    births: |total, rate| format!("Births: {total} ({rate:.1}/gen)"),
+    deaths: |total, rate| format!("Deaths: {total} ({rate:.1}/gen)"),
+    activity: "Activity",
+    entropy: "Entropy",
+    versus: |ours, theirs| format!("{ours}  vs  {theirs}"),
+    cycle: |period, since| {
+        if period == 1 {
+            format!("Still since generation {since}")
+        } else {
+            format!("Repeats every {period} generations since generation {since}")
+        }
+    },
+    debug_info: "Debug info",
+    log_console: "Log console",
+    log_level: "Log level",
+    log_error: "Errors only",
+    log_warn: "Warnings",
+    log_info: "Info",
+    log_debug: "Debug",
+    log_trace: "Trace (every tick)",
+    save_log: "Save log",
+    clear_log: "Clear log",
+    empty_log: "Nothing logged yet.",
+    cell_info: "Cell info on hover",
+    cell_readout: |row, col, alive, live_neighbors| {
+        let state = if alive { "alive" } else { "dead" };
+        format!("({row}, {col}) {state}, {live_neighbors} live neighbours")
+    },
+    inspector: "Inspector mode",
+    inspector_cell: |row, col| format!("Cell ({row}, {col}):"),
+    rule_clause: |clause, n, counts| match clause {
+        Clause::Birth => format!("a dead cell with {n} live neighbours is born, because {n} is in {counts}."),
+        Clause::StaysDead => format!("a dead cell with {n} live neighbours stays dead, because {n} is not in {counts}."),
+        Clause::Survival => format!("a live cell with {n} live neighbours survives, because {n} is in {counts}."),
+        Clause::Death => format!("a live cell with {n} live neighbours dies, because {n} is not in {counts}."),
+    },
+    state_hash: |hash| format!("State hash: {hash:016x}"),
+    board_dimensions: |width, height| format!("Board: {width}×{height}"),
+    frame_times: |tick_ms, render_ms| format!("Per frame: {tick_ms:.1} ms simulating, {render_ms:.1} ms rendering"),
+
+    board: "Game board",
+    board_size: "Board size",
+    overlay: "Overlay",
+    grid_lines: "Grid lines",
+    cell_shape: "Cell shape",
+    cell_gap: "Cell gap",
+    fade_effect: "Fade",
+    trail_length: "Trails",
+    history_scrubber: "Position in history",
+    bookmark_name: "Bookmark name",
+    add_bookmark: "Bookmark",
+    bookmark_default: |generation| format!("Gen {generation}"),
+    bookmark_label: |name, generation| format!("{name} (gen {generation})"),
+    remove_bookmark: "Remove bookmark",
+    frames_kept: "Frames to keep",
+    memory_kept: "Memory limit in KiB",
+    rule_preset: "Rule preset",
+    rule_notation: "Rule in B/S notation",
+    pattern_text: "Pattern as text",
+    pan_up: "Pan up",
+    pan_down: "Pan down",
+    pan_left: "Pan left",
+    pan_right: "Pan right",
+    minimap: "Minimap; click to move the view",
+    status: |generation, population| format!("Generation {generation}, {population} live cells"),
+    describe_board: "Describe board as text",
+    torus_view: "3D torus view",
+    space_time_view: "Space-time view",
+    space_time_caption: |layers| format!("Last {layers} generations, newest on top"),
+    export_voxels: "Export voxels",
+    board_summary: |width, height, population| format!("{width}×{height} board with {population} live cells."),
+    live_extent: |top, bottom, left, right| format!("Live cells lie in rows {top}–{bottom}, columns {left}–{right}."),
+    empty_board: "The board is empty.",
+    row_runs: |row, runs| format!("Row {row}: {runs}"),
+
+    keyboard_shortcuts: "Keyboard shortcuts",
+    press_key: "Press a key…",
+    reset_shortcuts: "Reset to defaults",
+    shortcut_conflict: |shortcut, command| format!("{shortcut} is already used by “{command}”"),
+    command_play_pause: "Run / pause",
+    command_cursor_action: "Toggle the cell under the cursor",
+    command_paste: "Paste",
+    command_cancel: "Cancel paste / leave cursor",
+    command_move_up: "Cursor or view up",
+    command_move_down: "Cursor or view down",
+    command_move_left: "Cursor or view left",
+    command_move_right: "Cursor or view right",
+    command_shift_up: "Move board up",
+    command_shift_down: "Move board down",
+    command_shift_left: "Move board left",
+    command_shift_right: "Move board right",
+    command_palette: "Command palette",
+    palette_placeholder: "Type a command…",
+    palette_place: |name| format!("Place pattern: {name}"),
+    palette_rule: |name, notation| format!("Rule: {name} ({notation})"),
+    palette_overlay: |overlay| format!("Overlay: {overlay}"),
+    menu_toggle: "Toggle cell",
+    menu_stamp: |name| format!("Stamp {name} here"),
+    last_pattern: "the last pattern",
+    menu_paste: "Paste here",
+    menu_center: "Center view here",
+    menu_inspect: "Inspect neighbourhood",
+    neighborhood_summary: |row, col, live_neighbors, alive, next| {
+        let now = if alive { "alive" } else { "dead" };
+        let then = if next { "alive" } else { "dead" };
+        format!("Cell ({row}, {col}): {now}, {live_neighbors} live neighbours, {then} next generation")
+    },
+    close: "Close",
+};
+
+pub const SPANISH: Strings = Strings {
+    title: "El Juego de la Vida de Conway",
+    language: "Idioma",
+    tab_label: |number| format!("Tablero {number}"),
+    new_tab: "Nueva pestaña",
+    close_tab: "Cerrar pestaña",
+    mode: "Modo",
+    mode_life: "Juego de la Vida",
+    mode_elementary: "Autómata elemental (1D)",
+    rule_number: "Regla",
+    rule_number_button: |rule| format!("Regla {rule}"),
+    mode_turmites: "Turmitas",
+    program_label: |number| format!("Programa {number}"),
+    turmite_preset: "Empezar desde…",
+    transition_table: "Tabla de transiciones",
+    add_program: "Añadir programa",
+    place_with: "Los clics colocan una turmita con",
+    turmite_count: |count| format!("{count} turmitas"),
+    mode_sandpile: "Pila de arena abeliana",
+    grains_per_drop: "Granos por clic",
+    drop_at_center: "Soltar en el centro",
+    feed_center: "Granos en el centro por paso",
+    sandpile_status: |grains, stable| format!("{grains} granos, {}", if stable { "estable" } else { "desmoronándose" }),
+    mode_forest_fire: "Incendio forestal",
+    growth_chance: |chance| format!("Crecimiento de árboles p = {chance}"),
+    lightning_chance: |chance| format!("Rayos f = {chance}"),
+    forest_status: |trees, fires| format!("{trees} árboles, {fires} ardiendo"),
+    mode_epidemic: "Epidemia SIR",
+    infection_chance: |chance| format!("Probabilidad de contagio {chance}"),
+    infection_duration: |steps| format!("La enfermedad dura {steps} pasos"),
+    immunity_loss_chance: |chance| format!("Pérdida de inmunidad {chance}"),
+    epidemic_status: |susceptible, infected, recovered| format!("{susceptible} susceptibles, {infected} infectados, {recovered} recuperados"),
+    mode_wator: "Wa-Tor",
+    fish_breed: "Los peces se reproducen tras",
+    shark_breed: "Los tiburones se reproducen tras",
+    shark_starve: "Los tiburones mueren de hambre tras",
+    fish: "Peces",
+    sharks: "Tiburones",
+    mode_sand: "Arena",
+    material_sand: "Arena",
+    material_water: "Agua",
+    material_wall: "Muro",
+    material_eraser: "Borrador",
+    brush_radius: |radius| format!("Radio del pincel {radius}"),
+    mode_ising: "Modelo de Ising",
+    temperature: |temperature| format!("Temperatura {temperature:.2}"),
+    critical_temperature: "Temperatura crítica",
+    magnetization: "Magnetización |m|",
+    mode_rule_table: "Tabla de reglas",
+    upload_rule_table: "Cargar archivo .rule",
+    rule_table_status: |name, states| format!("{name}: {states} estados"),
+    paint_state: "Estado a pintar",
+    state_label: |state| format!("Estado {state}"),
+
+    restore_prompt: |size, rule, generation| {
+        format!("¿Restaurar la última sesión? ({size}, {rule}, generación {generation})")
+    },
+    restore: "Restaurar",
+    discard: "Descartar",
+
+    restart: "Reiniciar",
+    compare_with: "Comparar con",
+    seed_grid: "Cuadrícula de semillas",
+    grid_off: "No",
+    mute: "Silenciar",
+    unmute: "Activar sonido",
+    sound_unavailable: "Sonido no disponible",
+    sequencer_row: "Fila del secuenciador",
+    custom_rule: "Personalizada",
+    surprise_me: "Sorpréndeme",
+    previous_rule: "Regla anterior",
+    next_rule: "Regla siguiente",
+    star_rule: "Marcar esta regla",
+    starred_rules: "Marcadas:",
+    rule_score: |points, verdict| {
+        let verdict = match verdict {
+            Verdict::Extinct => "se extingue",
+            Verdict::Exploded => "explota",
+            Verdict::Frozen => "se congela",
+            Verdict::Chaotic => "caótica",
+            Verdict::Lively => "animada",
+        };
+        format!("Puntuación {points} ({verdict})")
+    },
+    promising_rules: "Reglas prometedoras",
+    boundary: "Bordes",
+    boundary_torus: "Bordes que se unen",
+    boundary_dead: "Bordes muertos",
+    boundary_klein: "Botella de Klein",
+    boundary_cross_surface: "Superficie cruzada (plano proyectivo)",
+    boundary_shifted: "Toro desplazado",
+    boundary_shift: "Filas de desplazamiento",
+    grow_board: "Crecer en los bordes",
+    crop_to_content: "Recortar al contenido",
+    center_pattern: "Centrar patrón",
+    overlay_none: "Sin capa",
+    overlay_heatmap: "Mapa de actividad",
+    overlay_diff: "Diferencias con captura/rival",
+    overlay_neighbors: "Número de vecinas",
+    no_grid: "Sin cuadrícula",
+    grid_every: |spacing| {
+        if spacing == 1 { "Cuadrícula en cada celda".to_string() } else { format!("Cuadrícula cada {spacing} celdas") }
+    },
+    square_cells: "Celdas cuadradas",
+    round_cells: "Celdas redondas",
+    no_gap: "Sin separación",
+    gap: |px| format!("Separación de {px}px"),
+    no_fade: "Sin fundido",
+    fade: |ms| format!("Fundido de {ms}ms"),
+    crossfade: "Fundir generaciones lentas",
+    no_trails: "Sin estela",
+    trails: |generations| format!("Estela de {generations} gen."),
+    snapshot: "Captura",
+    drop_snapshot: "Descartar captura",
+    set_union: "∪ Añadir captura",
+    set_intersection: "∩ Conservar lo común con la captura",
+    set_difference: "− Quitar captura",
+    set_xor: "⊕ Xor con la captura",
+    cells_differ: |count| format!("{count} celdas distintas"),
+    update_delay: |ms| format!("Retardo: {ms}ms"),
+    reduced_motion: "Movimiento reducido",
+    run_in_background: "Seguir en pestañas en segundo plano",
+    battery_saver: "Ahorro de batería",
+    speed_max: "Máx.",
+    update_delay_max: "Retardo: lo más rápido posible",
+
+    pause: "Pausa",
+    resume: "Reanudar",
+    step: "Paso",
+    back: "Atrás",
+    replay: "Repetir",
+    history_position: |generation, latest, frames, kib| {
+        format!("Gen. {generation} de {latest} · {frames} fotogramas ({kib} KiB)")
+    },
+    keep: "guardar",
+    frames_up_to: "fotogramas, hasta",
+    kib: "KiB",
+
+    draw: "Dibujar",
+    select: "Seleccionar",
+    annotate: "Anotar",
+    annotation_text: "Texto de la etiqueta",
+    annotation_default: "Nota",
+    annotations: "Etiquetas:",
+    annotation_label: |text, row, col| format!("{text} ({row}, {col})"),
+    remove_annotation: "Quitar etiqueta",
+    clear_annotations: "Borrar etiquetas",
+    measure: "Medir",
+    ruler_readout: |dx, dy, distance, steps| format!("Δx {dx}, Δy {dy} · a {distance:.2} celdas · {steps} pasos"),
+    stopwatch_mark: "Marcar generación",
+    stopwatch_since: |generations| format!("{generations} generaciones desde la marca"),
+    stopwatch_between: |generations| format!("{generations} generaciones entre las marcas"),
+    speed_readout: |speed| format!("velocidad {speed}"),
+    selection: |cols, rows| format!("Selección {cols}×{rows}"),
+    find_predecessor: "Buscar predecesor",
+    predecessor_limit: |edge| format!("Regiones de hasta {edge}×{edge}"),
+    searching_predecessor: "Buscando un predecesor",
+    cancel_search: "Detener búsqueda",
+    copy_rle: "Copiar como RLE",
+    clear_selection: "Quitar selección",
+    keyboard_cursor: "Cursor de teclado",
+    cursor_position: |row, col, alive| format!("Fila {row}, columna {col}: {}", if alive { "viva" } else { "muerta" }),
+    predecessor_found: "Predecesor encontrado.",
+    apply_predecessor: "Aplicar predecesor",
+    garden_of_eden: "No existe predecesor: esta región es un Jardín del Edén.",
+    predecessor_gave_up: "Se agotó la búsqueda sin hallar predecesor: probablemente un Jardín del Edén.",
+
+    analyze: "Analizar",
+    hide: "Ocultar",
+    apgcode_placeholder: "apgcode, p. ej. xq4_153",
+    place_apgcode: "Colocar apgcode",
+    still_life: |cells| format!("vida estática ({cells} celdas)"),
+    oscillator: |period| format!("oscilador p{period}"),
+    spaceship: |period| format!("nave p{period}"),
+    unstable: "inestable / sin identificar",
+    orthogonal: "ortogonal",
+    diagonal: "diagonal",
+    oblique: "oblicua",
+
+    find_pattern: "Buscar un patrón…",
+    fetch_lifewiki: "Descargar de LifeWiki",
+    downloading: "Descargando…",
+    placed: |name| format!("{name} colocado."),
+    pattern_author: |author| format!("De {author}."),
+    ascii_placeholder: "Pega aquí filas de .O…",
+    export_text: "Exportar como texto",
+    export_packed: "Exportar tablero comprimido",
+    load_packed: "Cargar tablero comprimido",
+    export_session: "Exportar sesión",
+    import_session: "Importar sesión",
+    trim: "Recortar a las celdas vivas",
+    place_text: "Colocar texto",
+    seed_from_image: "Sembrar desde imagen",
+    threshold: "umbral",
+    dark_cells_live: "Viven las celdas oscuras",
+    forget_image: "Olvidar imagen",
+    drop_hint: |paste| match paste {
+        Some(keys) => format!("…o suelta archivos .rle, .cells, .lif o imágenes en cualquier parte, o pega con {keys}"),
+        None => "…o suelta archivos .rle, .cells, .lif o imágenes en cualquier parte".to_string(),
+    },
+    paste_hint: |cancel| match cancel {
+        Some(keys) => format!("Haz clic en el tablero para soltar el patrón pegado ({keys} cancela)."),
+        None => "Haz clic en el tablero para soltar el patrón pegado.".to_string(),
+    },
+    paste_mode: "Al colocar patrones",
+    snap_parity: "Ajustar a celdas pares",
+    library_summary: |count| format!("Mis patrones ({count})"),
+    library_name: "Nombre",
+    library_tags: "Etiquetas, separadas por comas",
+    save_to_library: "Guardar selección",
+    library_needs_selection: "Selecciona parte del tablero para guardarla",
+    library_default: |n| format!("Patrón {n}"),
+    search_library: "Buscar en mis patrones",
+    library_empty: "Todavía no hay nada guardado.",
+    stamp_from_library: "Estampar: haz clic en el tablero para colocarlo",
+    remove_from_library: "Quitar de la biblioteca",
+    import_archive: "Importar .zip",
+    importing_archive: |done, total| format!("Importando {done} de {total}…"),
+    archive_imported: |added, skipped| match skipped {
+        0 => format!("Se importaron {added} patrones."),
+        _ => format!("Se importaron {added} patrones; no se pudieron leer {skipped} archivos."),
+    },
+    library_not_saved: |err| format!("No se pudo guardar la biblioteca ({err}); los cambios se perderán al recargar."),
+    #[cfg(feature = "fullstack")]
+    gallery: "Galería de la comunidad",
+    #[cfg(feature = "fullstack")]
+    gallery_author: "Tu nombre (opcional)",
+    #[cfg(feature = "fullstack")]
+    gallery_upload: "Compartir selección",
+    #[cfg(feature = "fullstack")]
+    gallery_uploaded: |name| format!("Se compartió «{name}»."),
+    #[cfg(feature = "fullstack")]
+    gallery_search: "Buscar en la galería",
+    #[cfg(feature = "fullstack")]
+    gallery_empty: "No se encontraron patrones.",
+    #[cfg(feature = "fullstack")]
+    gallery_like: "Me gusta",
+    merge_replace: "Reemplazar lo que hay debajo",
+    merge_or: "Añadir al tablero (o)",
+    merge_and: "Conservar solo la intersección (y)",
+    merge_xor: "Invertir celdas (o exclusivo)",
+
+    track_spaceships: "Rastrear naves",
+    measured_velocity: |cols, rows| format!("medida ({cols:+.3}, {rows:+.3})/gen."),
+    follow: "Seguir",
+    unfollow: "Dejar de seguir",
+
+    start_race: "Iniciar carrera de sopas",
+    race_for: "durante",
+    generations: "generaciones",
+    race_generation: |generation, length| format!("Gen. {generation}/{length}"),
+    left: "Izquierda",
+    right: "Derecha",
+    race_side: |side, population, invaded| format!("{side} {population} (invadidas {invaded})"),
+    wins: |side| format!("¡Gana {side}!"),
+    draw_game: "¡Empate!",
+    start_search: "Iniciar búsqueda de sopas",
+    stop_search: "Detener búsqueda de sopas",
+    cap: "límite",
+    soups_tried: |count| format!("{count} sopas probadas"),
+    longest_lived: "Más longevas",
+    most_expanding: "Más expansivas",
+    seed: "Semilla",
+    lifespan: "Duración",
+    final_population: "Pob. final",
+    span: "Extensión",
+    load: "Cargar",
+    evolve_patterns: "Evolucionar patrones (experimental)",
+    goal: "Objetivo",
+    goal_longevity: "Vida más larga",
+    goal_growth: "Mayor crecimiento",
+    goal_reach: "Alcanzar una celda",
+    target_cell: "destino (fila, columna desde la esquina de la semilla)",
+    target_row: "Fila de destino",
+    target_col: "Columna de destino",
+    start_evolution: "Empezar a evolucionar",
+    stop_evolution: "Dejar de evolucionar",
+    evolution_status: |round, evaluated, best| match best {
+        Some(best) => format!("Ronda {round}, {evaluated} semillas probadas, mejor aptitud {best}"),
+        None => format!("Ronda {round}, {evaluated} semillas probadas"),
+    },
+    load_best: "Cargar la mejor",
+    benchmark: "Medir rendimiento",
+    gpu_stepping: "Avanzar en la GPU (experimental)",
+    benchmark_hint: |size, seconds| {
+        format!("Ejecuta una bellota en un tablero de {size}×{size} durante {seconds}s por motor, sin dibujar")
+    },
+
+    seed_value: |seed| format!("Semilla: {seed}"),
+    generation: |generation| format!("Generación: {generation}"),
+    population: |population| format!("Población: {population}"),
+    births: |total, rate| format!("Nacimientos: {total} ({rate:.1}/gen.)"),
+    deaths: |total, rate| format!("Muertes: {total} ({rate:.1}/gen.)"),
+    activity: "Actividad",
+    entropy: "Entropía",
+    versus: |ours, theirs| format!("{ours}  contra  {theirs}"),
+    cycle: |period, since| {
+        if period == 1 {
+            format!("Inmóvil desde la generación {since}")
+        } else {
+            format!("Se repite cada {period} generaciones desde la generación {since}")
+        }
+    },
+    debug_info: "Información de depuración",
+    log_console: "Consola de registro",
+    log_level: "Nivel de registro",
+    log_error: "Solo errores",
+    log_warn: "Avisos",
+    log_info: "Información",
+    log_debug: "Depuración",
+    log_trace: "Traza (cada generación)",
+    save_log: "Guardar registro",
+    clear_log: "Vaciar registro",
+    empty_log: "Aún no hay nada registrado.",
+    cell_info: "Información de celda al pasar",
+    cell_readout: |row, col, alive, live_neighbors| {
+        let state = if alive { "viva" } else { "muerta" };
+        format!("({row}, {col}) {state}, {live_neighbors} vecinas vivas")
+    },
+    inspector: "Modo inspector",
+    inspector_cell: |row, col| format!("Celda ({row}, {col}):"),
+    rule_clause: |clause, n, counts| match clause {
+        Clause::Birth => format!("una celda muerta con {n} vecinas vivas nace, porque {n} está en {counts}."),
+        Clause::StaysDead => format!("una celda muerta con {n} vecinas vivas sigue muerta, porque {n} no está en {counts}."),
+        Clause::Survival => format!("una celda viva con {n} vecinas vivas sobrevive, porque {n} está en {counts}."),
+        Clause::Death => format!("una celda viva con {n} vecinas vivas muere, porque {n} no está en {counts}."),
+    },
+    state_hash: |hash| format!("Hash del estado: {hash:016x}"),
+    board_dimensions: |width, height| format!("Tablero: {width}×{height}"),
+    frame_times: |tick_ms, render_ms| format!("Por fotograma: {tick_ms:.1} ms simulando, {render_ms:.1} ms dibujando"),
+
+    board: "Tablero",
+    board_size: "Tamaño del tablero",
+    overlay: "Capa",
+    grid_lines: "Líneas de cuadrícula",
+    cell_shape: "Forma de las celdas",
+    cell_gap: "Separación entre celdas",
+    fade_effect: "Fundido",
+    trail_length: "Estela",
+    history_scrubber: "Posición en el historial",
+    bookmark_name: "Nombre del marcador",
+    add_bookmark: "Añadir marcador",
+    bookmark_default: |generation| format!("Gen {generation}"),
+    bookmark_label: |name, generation| format!("{name} (gen {generation})"),
+    remove_bookmark: "Quitar marcador",
+    frames_kept: "Fotogramas a guardar",
+    memory_kept: "Límite de memoria en KiB",
+    rule_preset: "Regla predefinida",
+    rule_notation: "Regla en notación B/S",
+    pattern_text: "Patrón como texto",
+    pan_up: "Desplazar arriba",
+    pan_down: "Desplazar abajo",
+    pan_left: "Desplazar a la izquierda",
+    pan_right: "Desplazar a la derecha",
+    minimap: "Minimapa; haz clic para mover la vista",
+    status: |generation, population| format!("Generación {generation}, {population} celdas vivas"),
+    describe_board: "Describir el tablero como texto",
+    torus_view: "Vista de toro en 3D",
+    space_time_view: "Vista espacio-tiempo",
+    space_time_caption: |layers| format!("Últimas {layers} generaciones, la más reciente arriba"),
+    export_voxels: "Exportar vóxeles",
+    board_summary: |width, height, population| format!("Tablero de {width}×{height} con {population} celdas vivas."),
+    live_extent: |top, bottom, left, right| {
+        format!("Las celdas vivas ocupan las filas {top}–{bottom} y las columnas {left}–{right}.")
+    },
+    empty_board: "El tablero está vacío.",
+    row_runs: |row, runs| format!("Fila {row}: {runs}"),
+
+    keyboard_shortcuts: "Atajos de teclado",
+    press_key: "Pulsa una tecla…",
+    reset_shortcuts: "Restablecer valores predeterminados",
+    shortcut_conflict: |shortcut, command| format!("{shortcut} ya se usa para «{command}»"),
+    command_play_pause: "Ejecutar / pausar",
+    command_cursor_action: "Alternar la celda bajo el cursor",
+    command_paste: "Pegar",
+    command_cancel: "Cancelar pegado / salir del cursor",
+    command_move_up: "Cursor o vista arriba",
+    command_move_down: "Cursor o vista abajo",
+    command_move_left: "Cursor o vista a la izquierda",
+    command_move_right: "Cursor o vista a la derecha",
+    command_shift_up: "Mover el tablero arriba",
+    command_shift_down: "Mover el tablero abajo",
+    command_shift_left: "Mover el tablero a la izquierda",
+    command_shift_right: "Mover el tablero a la derecha",
+    command_palette: "Paleta de comandos",
+    palette_placeholder: "Escribe un comando…",
+    palette_place: |name| format!("Colocar patrón: {name}"),
+    palette_rule: |name, notation| format!("Regla: {name} ({notation})"),
+    palette_overlay: |overlay| format!("Capa: {overlay}"),
+    menu_toggle: "Alternar celda",
+    menu_stamp: |name| format!("Estampar {name} aquí"),
+    last_pattern: "el último patrón",
+    menu_paste: "Pegar aquí",
+    menu_center: "Centrar la vista aquí",
+    menu_inspect: "Inspeccionar vecindario",
+    neighborhood_summary: |row, col, live_neighbors, alive, next| {
+        let now = if alive { "viva" } else { "muerta" };
+        let then = if next { "viva" } else { "muerta" };
+        format!("Celda ({row}, {col}): {now}, {live_neighbors} vecinas vivas, {then} en la siguiente generación")
+    },
+    close: "Cerrar",
+};
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    pub fn preferred() -> Option<String> {
+        web_sys::window()?.navigator().language()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    pub fn preferred() -> Option<String> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter().find_map(|key| std::env::var(key).ok().filter(|value| !value.is_empty()))
+    }
+}
@@ -0,0 +1,55 @@
+// Image seeds
+//
+// A PNG or JPEG becomes a starting board: the webview decodes it and scales
+// it to the board size on a canvas (`document::eval`). It already reads
+// every format an `<img>` can show and smooths as it scales, which an image
+// crate would only repeat in a bigger binary. Each pixel's luminance is then
+// thresholded into alive/dead, and kept so the threshold can be tuned
+// afterwards.
+
+use dioxus::prelude::*;
+
+/// Decode `bytes` and scale the image to `width × height`, returning one
+/// Rec. 709 luminance byte per pixel, row by row.
+pub async fn luminance(bytes: Vec<u8>, width: usize, height: usize) -> Result<Vec<u8>, String> {
+    let mut eval = document::eval(
+        r#"
+        const [bytes, width, height] = await dioxus.recv();
+        try {
+            const bitmap = await createImageBitmap(new Blob([new Uint8Array(bytes)]));
+            const canvas = document.createElement("canvas");
+            canvas.width = width;
+            canvas.height = height;
+            const context = canvas.getContext("2d");
+            context.drawImage(bitmap, 0, 0, width, height);
+            const { data } = context.getImageData(0, 0, width, height);
+            const luma = new Array(width * height);
+            for (let i = 0; i < luma.length; i++) {
+                luma[i] = Math.round(0.2126 * data[4 * i] + 0.7152 * data[4 * i + 1] + 0.0722 * data[4 * i + 2]);
+            }
+            dioxus.send([true, luma, ""]);
+        } catch (error) {
+            dioxus.send([false, [], String(error)]);
+        }
+        "#,
+    );
+    eval.send((bytes, width, height)).map_err(|err| err.to_string())?;
+    let (ok, luma, error): (bool, Vec<u8>, String) = eval.recv().await.map_err(|err| err.to_string())?;
+    if ok {
+        Ok(luma)
+    } else {
+        Err(format!("could not decode image: {error}"))
+    }
+}
+
+/// Pixels at least as bright as `level` come alive (or those darker than it,
+/// when `invert`ed, for dark-on-light logos).
+pub fn threshold(luma: &[u8], level: u8, invert: bool) -> Vec<bool> {
+    luma.iter().map(|&value| (value >= level) != invert).collect()
+}
+
+/// True for file names this module can decode.
+pub fn is_image(name: &str) -> bool {
+    let name = name.to_lowercase();
+    [".png", ".jpg", ".jpeg"].iter().any(|ext| name.ends_with(ext))
+}
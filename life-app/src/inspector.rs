@@ -0,0 +1,44 @@
+// Inspector mode
+//
+// With the inspector on, hovering a cell outlines it and its eight
+// neighbours on the board and spells out which part of the rule decides
+// that cell next generation, e.g. "a dead cell with 3 live neighbours is
+// born, because 3 is in B3". Walking the pointer over a pattern turns into
+// a hands-on tour of how the rule works.
+
+use dioxus::prelude::*;
+use life_engine::rule::{Clause, Rule};
+
+use crate::context_menu::Neighborhood;
+use crate::i18n::Language;
+
+/// The half of `rule`'s notation `clause` comes from, e.g. `"S23"`.
+fn counts(rule: Rule, clause: Clause) -> String {
+    let notation = rule.to_string();
+    let (birth, survival) = notation.split_once('/').unwrap_or((&notation, ""));
+    match clause {
+        Clause::Birth | Clause::StaysDead => birth.to_string(),
+        Clause::Survival | Clause::Death => survival.to_string(),
+    }
+}
+
+/// The hovered cell's 3×3 block and the rule clause it falls under.
+#[component]
+pub fn InspectorPanel(neighborhood: Neighborhood, rule: Rule) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let clause = rule.clause(neighborhood.cells[4], neighborhood.live_neighbors);
+    rsx! {
+        figure { class: "neighborhood inspector", "aria-live": "polite",
+            div { class: "neighborhood-grid", "aria-hidden": "true",
+                for (i, alive) in neighborhood.cells.into_iter().enumerate() {
+                    div { class: if alive { "cell alive" } else { "cell dead" }, class: if i == 4 { "center" } }
+                }
+            }
+            figcaption {
+                strong { {(text.inspector_cell)(neighborhood.row, neighborhood.col)} }
+                " "
+                {(text.rule_clause)(clause, neighborhood.live_neighbors, &counts(rule, clause))}
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+// Keyboard shortcuts
+//
+// Every board command reachable from the keyboard goes through a `Keymap`,
+// so any of them can be rebound from the "Keyboard shortcuts" panel. The map
+// is shared by every board on the page and saved next to the autosaves. Each
+// chord drives at most one command: rebinding to a chord that is already
+// taken is refused, naming the command that holds it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::autosave;
+use crate::i18n::{Language, Strings};
+
+/// Something a key can be bound to.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    /// Run or pause; with the keyboard cursor on, toggles the cell under it.
+    PlayPause,
+    Step,
+    StepBack,
+    Restart,
+    KeyboardCursor,
+    /// Toggle the cell under the keyboard cursor.
+    CursorAction,
+    Copy,
+    Paste,
+    /// Drop a floating paste, or else leave the keyboard cursor.
+    Cancel,
+    /// Move the keyboard cursor, or pan the view when it is off.
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    /// Move the board's contents one cell.
+    ShiftUp,
+    ShiftDown,
+    ShiftLeft,
+    ShiftRight,
+    OpenPalette,
+}
+
+impl Command {
+    pub const ALL: [Command; 22] = [
+        Command::PlayPause,
+        Command::Step,
+        Command::StepBack,
+        Command::Restart,
+        Command::KeyboardCursor,
+        Command::CursorAction,
+        Command::Copy,
+        Command::Paste,
+        Command::Cancel,
+        Command::MoveUp,
+        Command::MoveDown,
+        Command::MoveLeft,
+        Command::MoveRight,
+        Command::PanUp,
+        Command::PanDown,
+        Command::PanLeft,
+        Command::PanRight,
+        Command::ShiftUp,
+        Command::ShiftDown,
+        Command::ShiftLeft,
+        Command::ShiftRight,
+        Command::OpenPalette,
+    ];
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Command::PlayPause => text.command_play_pause,
+            Command::Step => text.step,
+            Command::StepBack => text.back,
+            Command::Restart => text.restart,
+            Command::KeyboardCursor => text.keyboard_cursor,
+            Command::CursorAction => text.command_cursor_action,
+            Command::Copy => text.copy_rle,
+            Command::Paste => text.command_paste,
+            Command::Cancel => text.command_cancel,
+            Command::MoveUp => text.command_move_up,
+            Command::MoveDown => text.command_move_down,
+            Command::MoveLeft => text.command_move_left,
+            Command::MoveRight => text.command_move_right,
+            Command::PanUp => text.pan_up,
+            Command::PanDown => text.pan_down,
+            Command::PanLeft => text.pan_left,
+            Command::PanRight => text.pan_right,
+            Command::ShiftUp => text.command_shift_up,
+            Command::ShiftDown => text.command_shift_down,
+            Command::ShiftLeft => text.command_shift_left,
+            Command::ShiftRight => text.command_shift_right,
+            Command::OpenPalette => text.command_palette,
+        }
+    }
+
+    /// The chord this command starts out on, if any.
+    fn default_binding(self) -> Option<Binding> {
+        let plain = |key: &str| Binding { key: key.to_string(), command: false, alt: false, shift: false };
+        Some(match self {
+            Command::PlayPause => plain(" "),
+            Command::Step => return None,
+            Command::StepBack => plain("Backspace"),
+            Command::Restart => plain("r"),
+            Command::KeyboardCursor => plain("k"),
+            Command::CursorAction => plain("Enter"),
+            Command::Copy => Binding { command: true, ..plain("c") },
+            Command::Paste => Binding { command: true, ..plain("v") },
+            Command::Cancel => plain("Escape"),
+            Command::MoveUp => plain("ArrowUp"),
+            Command::MoveDown => plain("ArrowDown"),
+            Command::MoveLeft => plain("ArrowLeft"),
+            Command::MoveRight => plain("ArrowRight"),
+            Command::PanUp => Binding { shift: true, ..plain("ArrowUp") },
+            Command::PanDown => Binding { shift: true, ..plain("ArrowDown") },
+            Command::PanLeft => Binding { shift: true, ..plain("ArrowLeft") },
+            Command::PanRight => Binding { shift: true, ..plain("ArrowRight") },
+            Command::ShiftUp => Binding { alt: true, ..plain("ArrowUp") },
+            Command::ShiftDown => Binding { alt: true, ..plain("ArrowDown") },
+            Command::ShiftLeft => Binding { alt: true, ..plain("ArrowLeft") },
+            Command::ShiftRight => Binding { alt: true, ..plain("ArrowRight") },
+            Command::OpenPalette => Binding { command: true, ..plain("k") },
+        })
+    }
+}
+
+/// A key plus the modifiers held with it.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Binding {
+    /// The key's name as the browser reports it (`"ArrowUp"`, `"Enter"`),
+    /// or the lowercased character for printable keys (`" "` for space).
+    pub key: String,
+    /// Ctrl, or ⌘ on a Mac.
+    #[serde(default)]
+    pub command: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub shift: bool,
+}
+
+impl Binding {
+    /// The chord pressed in `evt`; `None` while only modifiers are down.
+    pub fn from_event(evt: &KeyboardEvent) -> Option<Self> {
+        let key = match evt.key() {
+            Key::Shift | Key::Control | Key::Alt | Key::Meta | Key::AltGraph => return None,
+            Key::Character(c) => c.to_lowercase(),
+            key => key.to_string(),
+        };
+        let modifiers = evt.modifiers();
+        Some(Self {
+            key,
+            command: modifiers.ctrl() || modifiers.meta(),
+            alt: modifiers.alt(),
+            shift: modifiers.shift(),
+        })
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (held, name) in [(self.command, "Ctrl+"), (self.alt, "Alt+"), (self.shift, "Shift+")] {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        match self.key.as_str() {
+            " " => f.write_str("Space"),
+            "ArrowUp" => f.write_str("↑"),
+            "ArrowDown" => f.write_str("↓"),
+            "ArrowLeft" => f.write_str("←"),
+            "ArrowRight" => f.write_str("→"),
+            "Backspace" => f.write_str("⌫"),
+            "Escape" => f.write_str("Esc"),
+            key => f.write_str(&key.to_uppercase()),
+        }
+    }
+}
+
+/// Which chord drives which command. Commands missing from the map are
+/// unbound.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Keymap(BTreeMap<Command, Binding>);
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self(Command::ALL.into_iter().filter_map(|command| Some((command, command.default_binding()?))).collect())
+    }
+}
+
+impl Keymap {
+    /// The saved keymap, or the defaults. Commands added since it was saved
+    /// get their default chord unless something else took it meanwhile.
+    pub fn load() -> Self {
+        let Some(mut keymap) = autosave::load_keymap() else { return Self::default() };
+        for command in Command::ALL {
+            if let Some(binding) = command.default_binding().filter(|_| !keymap.0.contains_key(&command)) {
+                if keymap.command(&binding).is_none() {
+                    keymap.0.insert(command, binding);
+                }
+            }
+        }
+        keymap
+    }
+
+    pub fn binding(&self, command: Command) -> Option<&Binding> {
+        self.0.get(&command)
+    }
+
+    /// The command `binding` drives, if any.
+    pub fn command(&self, binding: &Binding) -> Option<Command> {
+        self.0.iter().find(|(_, bound)| *bound == binding).map(|(&command, _)| command)
+    }
+
+    /// Put `command` on `binding`, or name the other command already on it.
+    pub fn rebind(&mut self, command: Command, binding: Binding) -> Result<(), Command> {
+        match self.command(&binding) {
+            Some(other) if other != command => Err(other),
+            _ => {
+                self.0.insert(command, binding);
+                Ok(())
+            }
+        }
+    }
+
+    /// `" (R)"`-style suffix for labels on buttons with a shortcut.
+    pub fn hint(&self, command: Command) -> String {
+        self.binding(command).map(|binding| format!(" ({binding})")).unwrap_or_default()
+    }
+}
+
+/// Table of every command with its chord; clicking a chord and pressing a
+/// new one rebinds it.
+#[component]
+pub fn ShortcutSettings() -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut keymap = use_context::<Signal<Keymap>>();
+    // The command waiting for its new chord, and why the last one was refused.
+    let mut listening = use_signal(|| None::<Command>);
+    let mut conflict = use_signal(|| None::<String>);
+
+    let mut save = move |next: Keymap| {
+        autosave::save_keymap(&next);
+        keymap.set(next);
+    };
+
+    rsx! {
+        details { class: "shortcuts",
+            summary { "{text.keyboard_shortcuts}" }
+            table {
+                for command in Command::ALL {
+                    tr {
+                        td { {command.label(text)} }
+                        td {
+                            button {
+                                onclick: move |_| {
+                                    listening.set(Some(command));
+                                    conflict.set(None);
+                                },
+                                onblur: move |_| {
+                                    if listening() == Some(command) {
+                                        listening.set(None);
+                                    }
+                                },
+                                onkeydown: move |evt| {
+                                    if listening() != Some(command) {
+                                        return;
+                                    }
+                                    let Some(binding) = Binding::from_event(&evt) else { return };
+                                    evt.prevent_default();
+                                    let mut next = keymap();
+                                    match next.rebind(command, binding.clone()) {
+                                        Ok(()) => save(next),
+                                        Err(other) => {
+                                            conflict.set(Some((text.shortcut_conflict)(&binding.to_string(), other.label(text))))
+                                        }
+                                    }
+                                    listening.set(None);
+                                },
+                                if listening() == Some(command) {
+                                    "{text.press_key}"
+                                } else if let Some(binding) = keymap.read().binding(command) {
+                                    "{binding}"
+                                } else {
+                                    "—"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "controls",
+                button {
+                    onclick: move |_| {
+                        save(Keymap::default());
+                        conflict.set(None);
+                    },
+                    "{text.reset_shortcuts}"
+                }
+                if let Some(err) = conflict() {
+                    span { class: "error", role: "alert", "{err}" }
+                }
+            }
+        }
+    }
+}
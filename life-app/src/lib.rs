@@ -0,0 +1,66 @@
+// Life app
+//
+// The Dioxus front end as a library: `GameOfLife` is the whole board with its
+// controls, ready to drop into any Dioxus app, and `Workspace` puts several
+// of them under a tab bar. The `life-app` binary is just a thin shell round
+// the latter.
+
+mod annotations;
+mod apgcode;
+mod audio;
+mod autosave;
+pub mod bench;
+mod board;
+mod bundle;
+mod census;
+mod chart;
+mod clipboard;
+mod context_menu;
+mod describe;
+mod events;
+mod evolve;
+mod explorer;
+mod export;
+#[cfg(feature = "fullstack")]
+mod gallery;
+mod game;
+mod gpu;
+mod guides;
+mod minimap;
+mod modes;
+mod motion;
+mod history;
+mod i18n;
+mod image_import;
+mod inspector;
+mod keys;
+mod library;
+pub mod logs;
+mod measure;
+mod overlay;
+mod palette;
+mod params;
+mod patterns;
+mod power;
+mod predecessor;
+mod profile;
+mod race;
+mod render;
+mod rule_picker;
+mod rule_table;
+mod search;
+mod seed_grid;
+mod spacetime;
+mod speed;
+mod stats;
+mod tabs;
+mod theme;
+mod tools;
+mod tooltip;
+mod torus;
+mod tracker;
+mod turmites;
+mod viewport;
+
+pub use game::GameOfLife;
+pub use tabs::Workspace;
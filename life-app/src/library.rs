@@ -0,0 +1,268 @@
+// Pattern library
+//
+// The user's own collection of patterns. A selection is saved under a name
+// and a few tags, shown with a PNG thumbnail rendered when it is saved,
+// found again by typing words from its name or tags, and stamped back onto
+// the board: it floats under the mouse like a paste until a click drops it.
+// Zip archives of pattern files, like the collection that comes with Golly,
+// import in bulk, each file tagged with the archive's name and the folders
+// it sat in. The library is saved next to the autosave (see `autosave`), so
+// every tab shares it and it outlives the session, and travels with
+// exported sessions, whose entries join it on import.
+
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+use life_engine::thumbnail::Thumbnail;
+use life_formats::archive::Archive;
+use life_formats::{png, PatternText};
+use serde::{Deserialize, Serialize};
+
+use crate::autosave;
+use crate::i18n::Language;
+
+/// Longest side of a thumbnail, in pixels.
+pub const THUMBNAIL_SIZE: usize = 48;
+/// Thumbnail colours. An image can't follow the theme's properties, so
+/// these are the default theme's dead and live cells.
+const DEAD: u32 = 0x000000;
+const ALIVE: u32 = 0xffffff;
+
+/// One saved pattern.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// The pattern itself, as RLE.
+    pub rle: String,
+    /// A `data:` URL of the thumbnail; empty in entries saved before there
+    /// were thumbnails, until the library is next loaded.
+    #[serde(default)]
+    pub thumbnail: String,
+}
+
+impl Entry {
+    /// `pattern` trimmed to its live cells, under `name` and the
+    /// comma-separated `tags`.
+    pub fn new(name: &str, tags: &str, pattern: &Pattern) -> Self {
+        let mut pattern = pattern.trimmed();
+        pattern.name = Some(name.to_string());
+        let thumbnail = thumbnail_url(&Thumbnail::of_pattern(&pattern, THUMBNAIL_SIZE));
+        Self { name: name.to_string(), tags: parse_tags(tags), rle: pattern.to_rle(), thumbnail }
+    }
+
+    /// The saved pattern, if its RLE still reads.
+    pub fn pattern(&self) -> Option<Pattern> {
+        Pattern::parse_rle(&self.rle).ok()
+    }
+
+    /// Whether every word of `query` turns up in the name or a tag,
+    /// ignoring case. An empty query matches everything.
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        query.to_lowercase().split_whitespace().all(|word| name.contains(word) || self.tags.iter().any(|tag| tag.contains(word)))
+    }
+}
+
+/// Comma-separated tags, trimmed, lowercased and without repeats.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Tags for a file at `path` inside the archive `archive`: the archive's
+/// name and each folder on the way.
+fn archive_tags(archive: &str, path: &str) -> String {
+    let stem = archive.rsplit_once('.').map_or(archive, |(stem, _)| stem);
+    let folders = path.rsplit_once('/').map_or("", |(folders, _)| folders);
+    std::iter::once(stem).chain(folders.split('/')).collect::<Vec<_>>().join(",")
+}
+
+/// `thumbnail` as a PNG `data:` URL for an `<img>`.
+pub fn thumbnail_url(thumbnail: &Thumbnail) -> String {
+    png::data_url(thumbnail, DEAD, ALIVE)
+}
+
+/// Add the entries of `more` that `entries` doesn't have yet (by name and
+/// pattern), returning how many were added.
+pub fn add_missing(entries: &mut Vec<Entry>, more: Vec<Entry>) -> usize {
+    let before = entries.len();
+    for entry in more {
+        if !entries.iter().any(|kept| kept.name == entry.name && kept.rle == entry.rle) {
+            entries.push(entry);
+        }
+    }
+    entries.len() - before
+}
+
+/// The saved library, with any missing thumbnails filled in.
+pub fn load() -> Vec<Entry> {
+    let mut entries = autosave::load_library().unwrap_or_default();
+    let mut filled = false;
+    for entry in entries.iter_mut().filter(|entry| entry.thumbnail.is_empty()) {
+        if let Some(pattern) = entry.pattern() {
+            entry.thumbnail = thumbnail_url(&Thumbnail::of_pattern(&pattern, THUMBNAIL_SIZE));
+            filled = true;
+        }
+    }
+    // Failing here only means they're drawn again next time.
+    if filled {
+        _ = autosave::save_library(&entries);
+    }
+    entries
+}
+
+/// Library controls over `entries`, the library as `load` read it.
+/// `selection` is what "Save" would keep; stamped patterns go to
+/// `on_stamp`.
+#[component]
+pub fn LibraryPanel(entries: Signal<Vec<Entry>>, selection: Option<Pattern>, on_stamp: EventHandler<Pattern>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut name = use_signal(String::new);
+    let mut tags = use_signal(String::new);
+    let mut query = use_signal(String::new);
+    // Archive import under way, as files done and files in all.
+    let mut importing = use_signal(|| None::<(usize, usize)>);
+    // How the last import went: patterns added and files that wouldn't read,
+    // or why the archive itself couldn't be opened.
+    let mut imported = use_signal(|| None::<Result<(usize, usize), String>>);
+    // Why the library last failed to save, until a save goes through.
+    let mut not_saved = use_signal(|| None::<String>);
+    let mut save = move |list: &[Entry]| not_saved.set(autosave::save_library(list).err());
+    let found: Vec<(usize, Entry)> =
+        entries.read().iter().cloned().enumerate().filter(|(_, entry)| entry.matches(&query.read())).collect();
+
+    rsx! {
+        details { class: "library",
+            summary { {(text.library_summary)(entries.read().len())} }
+            div { class: "controls",
+                input {
+                    placeholder: text.library_name,
+                    aria_label: text.library_name,
+                    value: "{name}",
+                    oninput: move |evt| name.set(evt.value()),
+                }
+                input {
+                    placeholder: text.library_tags,
+                    aria_label: text.library_tags,
+                    value: "{tags}",
+                    oninput: move |evt| tags.set(evt.value()),
+                }
+                button {
+                    disabled: selection.is_none(),
+                    title: text.library_needs_selection,
+                    onclick: move |_| {
+                        let Some(pattern) = &selection else { return };
+                        let mut list = entries.write();
+                        let label = match name.peek().trim() {
+                            "" => (text.library_default)(list.len() + 1),
+                            typed => typed.to_string(),
+                        };
+                        list.push(Entry::new(&label, &tags.peek(), pattern));
+                        save(&list);
+                        name.set(String::new());
+                    },
+                    "{text.save_to_library}"
+                }
+                input {
+                    r#type: "search",
+                    placeholder: text.search_library,
+                    aria_label: text.search_library,
+                    value: "{query}",
+                    oninput: move |evt| query.set(evt.value()),
+                }
+                label {
+                    "{text.import_archive} "
+                    input {
+                        r#type: "file",
+                        accept: ".zip",
+                        disabled: importing.read().is_some(),
+                        onchange: move |evt| async move {
+                            let Some(files) = evt.files() else { return };
+                            for file in files.files() {
+                                let Some(bytes) = files.read_file(&file).await else { continue };
+                                let archive = match Archive::open(&bytes) {
+                                    Ok(archive) => archive,
+                                    Err(err) => {
+                                        imported.set(Some(Err(err)));
+                                        continue;
+                                    }
+                                };
+                                let patterns: Vec<_> = archive.entries.iter().filter(|entry| life_formats::is_pattern_file(&entry.name)).collect();
+                                let (mut added, mut skipped) = (0, 0);
+                                for (done, entry) in patterns.iter().enumerate() {
+                                    importing.set(Some((done, patterns.len())));
+                                    let base = entry.name.rsplit('/').next().unwrap_or(&entry.name);
+                                    let parsed = archive
+                                        .read(entry)
+                                        .and_then(|contents| String::from_utf8(contents).map_err(|_| "not text".to_string()))
+                                        .and_then(|contents| life_formats::parse_file(base, &contents));
+                                    match parsed {
+                                        Ok(pattern) => {
+                                            let label = pattern.name.clone().unwrap_or_else(|| base.to_string());
+                                            entries.write().push(Entry::new(&label, &archive_tags(&file, &entry.name), &pattern));
+                                            added += 1;
+                                        }
+                                        Err(_) => skipped += 1,
+                                    }
+                                    // Let the progress show before the next file.
+                                    dioxus_time::sleep(Duration::ZERO).await;
+                                }
+                                save(&entries.read());
+                                imported.set(Some(Ok((added, skipped))));
+                            }
+                            importing.set(None);
+                        },
+                    }
+                }
+                if let Some((done, total)) = importing() {
+                    progress { value: done as f64, max: total as f64 }
+                    span { role: "status", {(text.importing_archive)(done, total)} }
+                }
+                match imported() {
+                    Some(Ok((added, skipped))) => rsx! { span { role: "status", {(text.archive_imported)(added, skipped)} } },
+                    Some(Err(err)) => rsx! { span { class: "error", role: "alert", "{err}" } },
+                    None => rsx! {},
+                }
+                if let Some(err) = not_saved() {
+                    span { class: "error", role: "alert", {(text.library_not_saved)(&err)} }
+                }
+            }
+            if entries.read().is_empty() {
+                p { "{text.library_empty}" }
+            }
+            ul { class: "library-entries",
+                for (index, entry) in found {
+                    li { key: "{index}-{entry.name}",
+                        if let Some(pattern) = entry.pattern() {
+                            button {
+                                title: text.stamp_from_library,
+                                onclick: move |_| on_stamp.call(pattern.clone()),
+                                img { class: "thumbnail", src: "{entry.thumbnail}", alt: "" }
+                                " {entry.name}"
+                            }
+                        }
+                        for tag in entry.tags.clone() {
+                            button { class: "tag", onclick: move |_| query.set(tag.clone()), "#{tag}" }
+                        }
+                        button {
+                            aria_label: text.remove_from_library,
+                            onclick: move |_| {
+                                let mut list = entries.write();
+                                list.remove(index);
+                                save(&list);
+                            },
+                            "×"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
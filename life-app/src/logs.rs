@@ -0,0 +1,328 @@
+// Logging
+//
+// The app reports through `tracing`: spans round each tick, each render of a
+// board, each parse of a pattern and each autosave read or write, and events
+// for anything that went wrong on the way. `init` installs the one
+// subscriber, which keeps the last `KEPT_LINES` lines for the in-app log
+// console and also writes every line to the browser console (or stderr) and,
+// natively, to a log file in the OS temp directory. A closed span logs how
+// long it took, at its own level, so TRACE shows every tick and render.
+//
+// Only the app's own crates log below WARN; Dioxus and the rest alike only
+// get through with warnings and errors. The level is one setting for the
+// whole page, changed from the console.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_time::use_interval;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+use crate::bundle;
+use crate::i18n::{Language, Strings};
+use crate::speed;
+
+/// Lines the console keeps; older ones are dropped.
+const KEPT_LINES: usize = 500;
+/// How often an open console looks for new lines, in ms.
+const REFRESH_MS: u64 = 500;
+/// File name the log is saved under.
+const FILE_NAME: &str = "game-of-life.log";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            LogLevel::Error => text.log_error,
+            LogLevel::Warn => text.log_warn,
+            LogLevel::Info => text.log_info,
+            LogLevel::Debug => text.log_debug,
+            LogLevel::Trace => text.log_trace,
+        }
+    }
+
+    /// Lower-case name, for CSS classes.
+    pub fn key(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    fn of(level: Level) -> Self {
+        match level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+}
+
+/// Most verbose level let through, as a `LogLevel` discriminant.
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+pub fn level() -> LogLevel {
+    LogLevel::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// One logged line.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Line {
+    /// Seconds since `init`.
+    pub at: f64,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:9.3} {:5} {}: {}", self.at, self.level.key().to_uppercase(), self.target, self.message)
+    }
+}
+
+/// What the console shows, and how many lines were ever logged, so it can
+/// tell when there are new ones.
+struct Kept {
+    lines: VecDeque<Line>,
+    total: u64,
+    started: Option<f64>,
+}
+
+static KEPT: Mutex<Kept> = Mutex::new(Kept { lines: VecDeque::new(), total: 0, started: None });
+
+/// The kept lines, oldest first, and the number logged so far.
+pub fn lines() -> (Vec<Line>, u64) {
+    let kept = KEPT.lock().unwrap_or_else(|err| err.into_inner());
+    (kept.lines.iter().cloned().collect(), kept.total)
+}
+
+/// Empty the console (the log file keeps everything).
+pub fn clear() {
+    KEPT.lock().unwrap_or_else(|err| err.into_inner()).lines.clear();
+}
+
+/// Install the subscriber, once, before the app starts; panics are logged
+/// as errors too.
+pub fn init() {
+    KEPT.lock().unwrap_or_else(|err| err.into_inner()).started = Some(speed::now_ms());
+    if tracing::subscriber::set_global_default(Registry::default().with(LogLayer)).is_err() {
+        return;
+    }
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!("{info}");
+        previous(info);
+    }));
+}
+
+fn log(level: Level, target: &str, message: String) {
+    let line = {
+        let mut kept = KEPT.lock().unwrap_or_else(|err| err.into_inner());
+        let at = (speed::now_ms() - kept.started.unwrap_or_default()) / 1000.0;
+        let line = Line { at, level: LogLevel::of(level), target: target.to_string(), message };
+        if kept.lines.len() == KEPT_LINES {
+            kept.lines.pop_front();
+        }
+        kept.lines.push_back(line.clone());
+        kept.total += 1;
+        line
+    };
+    output::write(level, &line.to_string());
+}
+
+/// When a span opened, in ms, and its fields, for the line it logs on
+/// closing.
+struct Opened {
+    at: f64,
+    fields: String,
+}
+
+struct LogLayer;
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for LogLayer {
+    // The level can change at any time, so nothing is cached per callsite.
+    fn register_callsite(&self, _meta: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, meta: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        let ours = meta.target().starts_with("life_");
+        let level = LogLevel::of(*meta.level());
+        let most = if ours { self::level() } else { self::level().min(LogLevel::Warn) };
+        level <= most
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Opened { at: speed::now_ms(), fields: fields.0 });
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        log(*event.metadata().level(), event.metadata().target(), fields.0);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let extensions = span.extensions();
+        let Some(opened) = extensions.get::<Opened>() else { return };
+        let ms = speed::now_ms() - opened.at;
+        log(*span.metadata().level(), span.metadata().target(), format!("{}{} took {ms:.2} ms", span.name(), opened.fields));
+    }
+}
+
+/// An event's message and fields as text: the message first, then
+/// `name=value` for the rest.
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            _ = write!(self.0, " {}={value}", field.name());
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod output {
+    use tracing::Level;
+
+    pub fn write(level: Level, line: &str) {
+        let line = js_sys::JsString::from(line);
+        match level {
+            Level::ERROR => web_sys::console::error_1(&line),
+            Level::WARN => web_sys::console::warn_1(&line),
+            _ => web_sys::console::log_1(&line),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod output {
+    use std::fs::File;
+    use std::io::Write;
+    use std::sync::{Mutex, OnceLock};
+
+    use tracing::Level;
+
+    /// The log file, started afresh each run; `None` when it can't be
+    /// created.
+    fn file() -> &'static Option<Mutex<File>> {
+        static FILE: OnceLock<Option<Mutex<File>>> = OnceLock::new();
+        FILE.get_or_init(|| File::create(std::env::temp_dir().join(super::FILE_NAME)).ok().map(Mutex::new))
+    }
+
+    pub fn write(_level: Level, line: &str) {
+        eprintln!("{line}");
+        if let Some(file) = file() {
+            let mut file = file.lock().unwrap_or_else(|err| err.into_inner());
+            // A full disk just means the file misses lines.
+            _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+/// The log console: the kept lines, newest at the bottom, with the level
+/// setting and a download of everything kept.
+#[component]
+pub fn LogConsole() -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut shown = use_signal(self::lines);
+    let mut chosen = use_signal(self::level);
+    use_interval(Duration::from_millis(REFRESH_MS), move |_| {
+        let latest = self::lines();
+        if latest.1 != shown.peek().1 {
+            shown.set(latest);
+        }
+    });
+    let (lines, _) = &*shown.read();
+    let saved: String = lines.iter().map(|line| format!("{line}\n")).collect();
+
+    rsx! {
+        section { class: "log-console",
+            div { class: "controls",
+                select {
+                    aria_label: text.log_level,
+                    onchange: move |evt| {
+                        let index: usize = evt.value().parse().unwrap_or(0);
+                        let level = LogLevel::ALL[index % LogLevel::ALL.len()];
+                        set_level(level);
+                        chosen.set(level);
+                    },
+                    for (index, level) in LogLevel::ALL.into_iter().enumerate() {
+                        option { value: "{index}", selected: chosen() == level, {level.label(text)} }
+                    }
+                }
+                button {
+                    onclick: move |_| {
+                        if let Err(err) = bundle::download(FILE_NAME, "text/plain", &saved) {
+                            tracing::warn!("couldn't save the log: {err}");
+                        }
+                    },
+                    "{text.save_log}"
+                }
+                button {
+                    onclick: move |_| {
+                        clear();
+                        shown.set(self::lines());
+                    },
+                    "{text.clear_log}"
+                }
+            }
+            pre { class: "log-lines", role: "log",
+                if lines.is_empty() {
+                    "{text.empty_log}"
+                }
+                for line in lines.iter() {
+                    span { class: "log-{line.level.key()}", "{line}\n" }
+                }
+            }
+        }
+    }
+}
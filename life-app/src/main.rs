@@ -0,0 +1,25 @@
+use dioxus::prelude::*;
+use life_app::Workspace;
+use life_engine::rule::Rule;
+
+// Static assets bundled by `asset!`
+const FAVICON: Asset = asset!("/assets/favicon.ico");
+
+// Bootstrapping
+fn main() {
+    #[cfg(not(target_arch = "wasm32"))]
+    if std::env::args().any(|arg| arg == "--bench") {
+        println!("{}", life_app::bench::report(&life_app::bench::run_all()));
+        return;
+    }
+    life_app::logs::init();
+    launch(App);
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        document::Link { rel: "icon", href: FAVICON }
+        Workspace { width: 128, height: 128, rule: Rule::CONWAY, cell_size: 4, autoplay: true }
+    }
+}
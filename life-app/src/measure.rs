@@ -0,0 +1,104 @@
+// Measuring
+//
+// The measure tool's ruler and stopwatch, for characterising moving
+// objects. Drag between two cells for their offset and distance; press
+// "Mark" at two generations for the time between them. Measure a
+// spaceship's displacement over one period and the pair gives its speed in
+// the usual `c/n` notation.
+
+use dioxus::prelude::*;
+
+use crate::viewport::Viewport;
+
+/// A measured pair of cells.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ruler {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+}
+
+impl Ruler {
+    /// Columns right and rows down from `from` to `to`.
+    pub fn offset(&self) -> (isize, isize) {
+        (self.to.1 as isize - self.from.1 as isize, self.to.0 as isize - self.from.0 as isize)
+    }
+
+    /// Straight-line distance, in cells.
+    pub fn distance(&self) -> f64 {
+        let (dx, dy) = self.offset();
+        ((dx * dx + dy * dy) as f64).sqrt()
+    }
+
+    /// King's-move distance: the cells crossed when diagonal steps count as
+    /// one, which is how spaceship speeds are measured.
+    pub fn steps(&self) -> usize {
+        let (dx, dy) = self.offset();
+        dx.unsigned_abs().max(dy.unsigned_abs())
+    }
+}
+
+/// Generations between two marks.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Stopwatch {
+    start: Option<u64>,
+    stop: Option<u64>,
+}
+
+impl Stopwatch {
+    /// Mark `generation`: the first mark starts the watch, the second stops
+    /// it, and a third starts over.
+    pub fn mark(&mut self, generation: u64) {
+        match (self.start, self.stop) {
+            (Some(_), None) => self.stop = Some(generation),
+            _ => *self = Self { start: Some(generation), stop: None },
+        }
+    }
+
+    /// Generations from the start mark to the stop mark, or to `now` while
+    /// the watch runs; `None` before the first mark.
+    pub fn elapsed(&self, now: u64) -> Option<u64> {
+        Some(self.stop.unwrap_or(now).abs_diff(self.start?))
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stop.is_some()
+    }
+}
+
+/// `cells` per `generations` as a speed: `c/4`, `2c/5`, `c`…, reduced.
+pub fn speed(cells: usize, generations: u64) -> String {
+    let (mut a, mut b) = (cells as u64, generations);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let gcd = a.max(1);
+    let (cells, generations) = (cells as u64 / gcd, generations / gcd);
+    let numerator = if cells == 1 { "c".to_string() } else { format!("{cells}c") };
+    if generations == 1 {
+        numerator
+    } else {
+        format!("{numerator}/{generations}")
+    }
+}
+
+/// The ruler drawn over the board's viewport.
+#[component]
+pub fn RulerLayer(ruler: Ruler, viewport: Viewport, cell_size: usize) -> Element {
+    let scale = cell_size as f64;
+    let center = move |(row, col): (usize, usize)| {
+        ((col as f64 - viewport.col as f64 + 0.5) * scale, (row as f64 - viewport.row as f64 + 0.5) * scale)
+    };
+    let ((x1, y1), (x2, y2)) = (center(ruler.from), center(ruler.to));
+
+    rsx! {
+        svg {
+            class: "annotations",
+            "aria-hidden": "true",
+            width: viewport.cols * cell_size,
+            height: viewport.rows * cell_size,
+            line { x1, y1, x2, y2, style: "stroke: var(--gol-annotation)", stroke_width: 2, stroke_dasharray: "4 3" }
+            circle { cx: x1, cy: y1, r: scale / 2.0 + 1.0, fill: "none", style: "stroke: var(--gol-annotation)" }
+            circle { cx: x2, cy: y2, r: scale / 2.0 + 1.0, fill: "none", style: "stroke: var(--gol-annotation)" }
+        }
+    }
+}
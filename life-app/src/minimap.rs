@@ -0,0 +1,68 @@
+// Minimap
+//
+// Coarse overview of a board that doesn't fit in the viewport. Cells are
+// binned into blocks of `scale × scale`; any live cell lights its block. The
+// current view is outlined and clicking jumps the view there.
+
+use dioxus::prelude::*;
+use life_engine::universe::Universe;
+
+use crate::i18n::Language;
+use crate::viewport::Viewport;
+
+/// Minimap edge in pixels.
+const SIZE: f64 = 128.0;
+/// Blocks along the longer board edge; caps the number of SVG rects.
+const BLOCKS: usize = 64;
+
+#[component]
+pub fn Minimap(universe: Universe, viewport: Viewport, on_jump: EventHandler<(usize, usize)>) -> Element {
+    let longest = universe.width.max(universe.height);
+    let scale = longest.div_ceil(BLOCKS).max(1);
+    let px = SIZE / longest as f64;
+
+    let block_cols = universe.width.div_ceil(scale);
+    let block_rows = universe.height.div_ceil(scale);
+    let mut occupied = vec![false; block_cols * block_rows];
+    for (row, col) in universe.live_cells() {
+        occupied[(row / scale) * block_cols + col / scale] = true;
+    }
+
+    let width = universe.width;
+    let height = universe.height;
+    let text = use_context::<Signal<Language>>()().strings();
+
+    rsx! {
+        svg {
+            class: "minimap",
+            role: "img",
+            "aria-label": text.minimap,
+            width: "{universe.width as f64 * px}",
+            height: "{universe.height as f64 * px}",
+            onclick: move |evt| {
+                let point = evt.element_coordinates();
+                let row = ((point.y / px) as usize).min(height - 1);
+                let col = ((point.x / px) as usize).min(width - 1);
+                on_jump.call((row, col));
+            },
+            for (block, _) in occupied.iter().enumerate().filter(|(_, &lit)| lit) {
+                rect {
+                    x: "{(block % block_cols * scale) as f64 * px}",
+                    y: "{(block / block_cols * scale) as f64 * px}",
+                    width: "{scale as f64 * px}",
+                    height: "{scale as f64 * px}",
+                    style: "fill: var(--gol-minimap-cells)",
+                }
+            }
+            rect {
+                x: "{viewport.col as f64 * px}",
+                y: "{viewport.row as f64 * px}",
+                width: "{viewport.cols as f64 * px}",
+                height: "{viewport.rows as f64 * px}",
+                fill: "none",
+                style: "stroke: var(--gol-minimap-view)",
+                stroke_width: "1.5",
+            }
+        }
+    }
+}
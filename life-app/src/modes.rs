@@ -0,0 +1,307 @@
+// Modes
+//
+// Each tab runs either Life or one of the engine's other automata (see
+// `life_engine::automata`). Life gets the full `GameOfLife`; the others share
+// `AutomatonView`: the same board renderer – a memoized `BoardRow` per row –
+// and pannable viewport, run/step/reset controls, and whatever settings the
+// automaton has.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use dioxus::html::geometry::ElementPoint;
+use dioxus::prelude::*;
+use dioxus_time::use_interval;
+use life_engine::automata::elementary::FAMOUS;
+use life_engine::automata::ising::CRITICAL_TEMPERATURE;
+use life_engine::automata::sand::Material;
+use life_engine::automata::{Mode, World};
+use life_engine::rule::Rule;
+use life_engine::universe::random_seed;
+
+use crate::board::{BoardRow, CellLook, RowLook};
+use crate::chart::Sparkline;
+use crate::game::{GameOfLife, MAIN_CSS};
+use crate::i18n::{Language, Localized, Strings};
+use crate::params::ParamControls;
+use crate::render::RenderOptions;
+use crate::rule_table::RuleTableSettings;
+use crate::theme;
+use crate::turmites::TurmiteSettings;
+use crate::viewport::Viewport;
+
+/// Milliseconds between steps while running.
+const DELAY_MS: u64 = 60;
+/// Most rows and columns shown at once.
+const VIEW_CELLS: usize = 128;
+/// Steps of population counts kept for the charts.
+const CENSUS_LEN: usize = 200;
+
+/// A mode picker over Life or the picked automaton. The props are
+/// `GameOfLife`'s; the automata use the board size and cell size.
+#[component]
+pub fn Modes(width: usize, height: usize, rule: Rule, cell_size: usize, autoplay: bool, slot: u64) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    // `None` is Life.
+    let mut mode = use_signal(|| None::<Mode>);
+
+    rsx! {
+        div { class: "controls mode-picker",
+            label {
+                "{text.mode} "
+                select {
+                    onchange: move |evt| mode.set(Mode::from_key(&evt.value())),
+                    option { value: "life", selected: mode().is_none(), "{text.mode_life}" }
+                    for option in Mode::ALL {
+                        option { value: option.key(), selected: mode() == Some(option), {option.label(text)} }
+                    }
+                }
+            }
+        }
+        match mode() {
+            None => rsx! {
+                GameOfLife { width, height, rule, cell_size, autoplay, slot }
+            },
+            Some(mode) => rsx! {
+                AutomatonView { key: "{mode.key()}", mode, width, height, cell_size }
+            },
+        }
+    }
+}
+
+/// A running `mode` world with its controls.
+#[component]
+pub fn AutomatonView(mode: Mode, width: usize, height: usize, cell_size: usize) -> Element {
+    let language = use_context::<Signal<Language>>();
+    let mut world = use_signal(|| mode.create(width, height, random_seed()));
+    let mut running = use_signal(|| true);
+    let mut generation = use_signal(|| 0u64);
+    // Recent `readings`, for the modes that chart something.
+    let mut census = use_signal(VecDeque::<Vec<f64>>::new);
+    // Whether a brush stroke is under way, for automata that paint.
+    let mut painting = use_signal(|| false);
+
+    let mut step = move || {
+        let mut current = world.write();
+        current.automaton_mut().step();
+        let reading = readings(&current);
+        if !reading.is_empty() {
+            let mut census = census.write();
+            if census.len() == CENSUS_LEN {
+                census.pop_front();
+            }
+            census.push_back(reading);
+        }
+        generation += 1;
+    };
+    use_interval(Duration::from_millis(DELAY_MS), move |_| {
+        if *running.peek() {
+            step();
+        }
+    });
+
+    // Window of cells rendered; larger worlds are panned, as Life boards are.
+    let mut viewport = use_signal(|| Viewport::new(VIEW_CELLS, VIEW_CELLS, width, height));
+    let pan_step = (VIEW_CELLS / 4) as isize;
+    let mut pan = move |d_row: isize, d_col: isize| viewport.write().pan(d_row, d_col, width, height);
+
+    let view = viewport();
+    let render = RenderOptions::default();
+    let cell_at = move |point: ElementPoint| {
+        let row = view.row + (point.y.max(0.0) as usize / cell_size).min(view.rows - 1);
+        let col = view.col + (point.x.max(0.0) as usize / cell_size).min(view.cols - 1);
+        (row, col)
+    };
+
+    let text = language().strings();
+    let current = world.read();
+    let automaton = current.automaton();
+    let agents = automaton.agents();
+    let charts = charts(&current, text);
+
+    rsx! {
+        document::Style { {theme::stylesheet()} }
+        document::Link { rel: "stylesheet", href: MAIN_CSS }
+
+        div { class: "container", lang: language().code(),
+            h1 { {mode.label(text)} }
+            div { class: "controls",
+                button { onclick: move |_| running.toggle(),
+                    if running() { "{text.pause}" } else { "{text.resume}" }
+                }
+                button {
+                    onclick: move |_| {
+                        running.set(false);
+                        step();
+                    },
+                    "{text.step}"
+                }
+                button {
+                    onclick: move |_| {
+                        world.set(mode.create(width, height, random_seed()));
+                        generation.set(0);
+                        census.write().clear();
+                    },
+                    "{text.restart}"
+                }
+                ParamControls { world }
+                match &*current {
+                    World::Elementary(_) => rsx! {
+                        for rule in FAMOUS {
+                            button {
+                                onclick: move |_| {
+                                    if let World::Elementary(elementary) = &mut *world.write() {
+                                        elementary.rule = rule;
+                                    }
+                                },
+                                {(text.rule_number_button)(rule)}
+                            }
+                        }
+                    },
+                    World::Turmites(_) | World::Wator(_) | World::RuleTable(_) => rsx! {},
+                    World::Sandpile(sandpile) => rsx! {
+                        button {
+                            onclick: move |_| {
+                                if let World::Sandpile(sandpile) = &mut *world.write() {
+                                    sandpile.drop_at_center(sandpile.drop);
+                                }
+                            },
+                            "{text.drop_at_center}"
+                        }
+                        span { {(text.sandpile_status)(sandpile.total(), sandpile.is_stable())} }
+                    },
+                    World::ForestFire(forest) => rsx! {
+                        {
+                            let (trees, fires) = forest.counts();
+                            rsx! { span { {(text.forest_status)(trees, fires)} } }
+                        }
+                    },
+                    World::Epidemic(epidemic) => rsx! {
+                        {
+                            let (susceptible, infected, recovered) = epidemic.counts();
+                            rsx! { span { {(text.epidemic_status)(susceptible, infected, recovered)} } }
+                        }
+                    },
+                    World::Sand(sand) => rsx! {
+                        for material in Material::ALL {
+                            button {
+                                aria_pressed: sand.brush == material,
+                                onclick: move |_| {
+                                    if let World::Sand(sand) = &mut *world.write() {
+                                        sand.brush = material;
+                                    }
+                                },
+                                {material.label(text)}
+                            }
+                        }
+                    },
+                    World::Ising(_) => rsx! {
+                        button {
+                            onclick: move |_| {
+                                if let World::Ising(ising) = &mut *world.write() {
+                                    ising.temperature = CRITICAL_TEMPERATURE;
+                                }
+                            },
+                            "{text.critical_temperature}"
+                        }
+                    },
+                }
+                span { {(text.generation)(generation())} }
+            }
+            if let World::Turmites(_) = &*current {
+                TurmiteSettings { world }
+            }
+            if let World::RuleTable(_) = &*current {
+                RuleTableSettings { world }
+            }
+            if !charts.is_empty() {
+                div { class: "stats",
+                    for (i, (label, max)) in charts.into_iter().enumerate() {
+                        {
+                            let values: Vec<f64> = census.read().iter().map(|reading| reading[i]).collect();
+                            let max = max.unwrap_or_else(|| values.iter().copied().fold(1.0, f64::max));
+                            rsx! {
+                                Sparkline { label, values, max }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "board-area",
+                div {
+                    class: render.board_class(),
+                    role: "application",
+                    aria_label: text.board,
+                    style: render.board_style(&view, cell_size),
+                    onmousedown: move |evt| {
+                        let (row, col) = cell_at(evt.element_coordinates());
+                        let mut current = world.write();
+                        current.automaton_mut().poke(row, col);
+                        painting.set(current.automaton().paints());
+                    },
+                    onmousemove: move |evt| {
+                        if *painting.peek() {
+                            let (row, col) = cell_at(evt.element_coordinates());
+                            world.write().automaton_mut().poke(row, col);
+                        }
+                    },
+                    onmouseup: move |_| painting.set(false),
+                    onmouseleave: move |_| painting.set(false),
+
+                    for row in view.row..view.row + view.rows {
+                        BoardRow {
+                            key: "{row}",
+                            look: RowLook::new((view.col..view.col + view.cols).map(|col| {
+                                let state = automaton.state(row, col);
+                                let (class, fill) = match (state, automaton.color(state)) {
+                                    (0, _) => ("cell dead", String::new()),
+                                    (_, None) => ("cell alive", String::new()),
+                                    (_, Some(rgb)) => ("cell", format!(" background-color: #{rgb:06x};")),
+                                };
+                                let agent = if agents.contains(&(row, col)) { " cursor" } else { "" };
+                                CellLook {
+                                    class: format!("{class}{agent}"),
+                                    style: format!("width: {cell_size}px; height: {cell_size}px;{fill}"),
+                                    count: None,
+                                }
+                            }).collect()),
+                        }
+                    }
+                }
+
+                if !view.covers(width, height) {
+                    div { class: "navigator",
+                        div { class: "pan-buttons",
+                            button { aria_label: text.pan_up, onclick: move |_| pan(-pan_step, 0), "▲" }
+                            button { aria_label: text.pan_down, onclick: move |_| pan(pan_step, 0), "▼" }
+                            button { aria_label: text.pan_left, onclick: move |_| pan(0, -pan_step), "◀" }
+                            button { aria_label: text.pan_right, onclick: move |_| pan(0, pan_step), "▶" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// What `world` charts, one reading per chart; empty for no charts.
+fn readings(world: &World) -> Vec<f64> {
+    match world {
+        World::Wator(wator) => {
+            let (fish, sharks) = wator.counts();
+            vec![fish as f64, sharks as f64]
+        }
+        World::Ising(ising) => vec![ising.magnetization().abs()],
+        _ => Vec::new(),
+    }
+}
+
+/// The label and full scale of each of `world`'s charts, in `readings`
+/// order; `None` scales the chart to its data.
+fn charts(world: &World, text: &Strings) -> Vec<(&'static str, Option<f64>)> {
+    match world {
+        World::Wator(_) => vec![(text.fish, None), (text.sharks, None)],
+        World::Ising(_) => vec![(text.magnetization, Some(1.0))],
+        _ => Vec::new(),
+    }
+}
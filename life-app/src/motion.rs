@@ -0,0 +1,22 @@
+// Reduced motion
+//
+// A calmer mode for sensitive users and long sessions: generations come at
+// most every `CALM_DELAY_MS`, and fades and trails are switched off. It
+// starts on when the OS asks for reduced motion (`prefers-reduced-motion`,
+// read through the webview) and can be toggled either way.
+
+use dioxus::prelude::*;
+
+/// Shortest time between generations in calm mode, in ms.
+pub const CALM_DELAY_MS: u64 = 250;
+
+/// True when the user has asked the OS for less motion.
+pub async fn prefers_reduced_motion() -> bool {
+    let mut eval = document::eval(r#"dioxus.send(window.matchMedia("(prefers-reduced-motion: reduce)").matches);"#);
+    eval.recv().await.unwrap_or(false)
+}
+
+/// Most generations per tick of a `delay` ms interval in calm mode.
+pub fn calm_rate(delay: u64) -> f64 {
+    delay as f64 / CALM_DELAY_MS as f64
+}
@@ -0,0 +1,124 @@
+// Board overlays
+//
+// Overlays recolour (or label) the board to surface information the plain
+// alive/dead view hides.
+
+use crate::i18n::Strings;
+use life_engine::universe::Changes;
+use serde::{Deserialize, Serialize};
+
+/// Which overlay the board is drawn with.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Overlay {
+    #[default]
+    None,
+    /// Colour cells by how often they have flipped since the run started.
+    Heatmap,
+    /// Highlight cells that differ from a reference board (snapshot or rival).
+    Diff,
+    /// Print each visible cell's live neighbour count on it.
+    Neighbors,
+}
+
+impl Overlay {
+    pub const ALL: [Overlay; 4] = [Overlay::None, Overlay::Heatmap, Overlay::Diff, Overlay::Neighbors];
+
+    /// Stable identifier used as the `<option>` value.
+    pub fn key(self) -> &'static str {
+        match self {
+            Overlay::None => "none",
+            Overlay::Heatmap => "heatmap",
+            Overlay::Diff => "diff",
+            Overlay::Neighbors => "neighbors",
+        }
+    }
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Overlay::None => text.overlay_none,
+            Overlay::Heatmap => text.overlay_heatmap,
+            Overlay::Diff => text.overlay_diff,
+            Overlay::Neighbors => text.overlay_neighbors,
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|overlay| overlay.key() == key).unwrap_or_default()
+    }
+}
+
+/// Per-cell flip counter. Oscillator cores glow hot, glider lanes show up as
+/// warm streaks, still lifes and empty space stay cold.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Heatmap {
+    flips: Vec<u32>,
+    max: u32,
+}
+
+impl Heatmap {
+    /// Bump every cell in `changes` on a board of `cells` cells. A resize
+    /// starts the counts again.
+    pub fn record(&mut self, changes: &Changes, cells: usize) {
+        if self.flips.len() != cells {
+            self.flips = vec![0; cells];
+            self.max = 0;
+        }
+        for &idx in changes.born.iter().chain(&changes.died) {
+            self.flips[idx] += 1;
+            self.max = self.max.max(self.flips[idx]);
+        }
+    }
+
+    /// CSS colour for cell `idx`: cold blue → hot red on a log scale,
+    /// the dead colour for cells that never changed.
+    pub fn color(&self, idx: usize) -> String {
+        let flips = self.flips.get(idx).copied().unwrap_or(0);
+        if flips == 0 || self.max == 0 {
+            return "var(--gol-cell-dead)".to_string();
+        }
+        let heat = (flips as f64).ln_1p() / (self.max as f64).ln_1p();
+        let hue = 240.0 * (1.0 - heat);
+        format!("hsl({hue:.0}, 100%, {:.0}%)", 20.0 + heat * 35.0)
+    }
+}
+
+/// Generations since each cell was last alive (0 = alive now), saturating.
+/// Recently dead cells are drawn as a fading trail behind moving objects.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Trails {
+    dead_for: Vec<u16>,
+}
+
+impl Trails {
+    pub fn record(&mut self, cells: &[bool]) {
+        if self.dead_for.len() != cells.len() {
+            self.dead_for = vec![u16::MAX; cells.len()];
+        }
+        for (age, &alive) in self.dead_for.iter_mut().zip(cells) {
+            *age = if alive { 0 } else { age.saturating_add(1) };
+        }
+    }
+
+    /// CSS colour for cell `idx` if it died within the last `length`
+    /// generations: `--gol-trail` just after death, mixing towards the dead
+    /// colour as it ages.
+    pub fn color(&self, idx: usize, length: u16) -> Option<String> {
+        let age = *self.dead_for.get(idx)?;
+        if age == 0 || age > length {
+            return None;
+        }
+        let left = 1.0 - (age - 1) as f64 / length as f64;
+        Some(format!("color-mix(in srgb, var(--gol-trail) {:.0}%, var(--gol-cell-dead))", 15.0 + left * 85.0))
+    }
+}
+
+/// CSS colour for a cell in the diff overlay: green when it is alive only on
+/// this board, magenta when only on the reference, `None` when they agree.
+pub fn diff_color(differs: bool, alive_here: bool) -> Option<&'static str> {
+    match (differs, alive_here) {
+        (false, _) => None,
+        (true, true) => Some("var(--gol-diff-here)"),
+        (true, false) => Some("var(--gol-diff-there)"),
+    }
+}
@@ -0,0 +1,132 @@
+// Command palette
+//
+// Ctrl+K (rebindable like every shortcut) opens a searchable list of what the
+// board can do – the keyboard commands, the bundled patterns, the rule
+// presets, the overlays and the exports – so it can be driven without
+// hunting through the panels. Typing filters the list (every word typed has
+// to appear in an entry), ↑/↓ pick an entry, Enter runs it and Escape closes
+// the palette.
+
+use dioxus::prelude::*;
+use life_engine::rule::PRESETS;
+
+use crate::i18n::{Language, Strings};
+use crate::keys::{Command, Keymap};
+use crate::overlay::Overlay;
+use crate::patterns::BUNDLED;
+
+/// One palette entry.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    Command(Command),
+    /// Place a bundled pattern, by name.
+    Pattern(&'static str),
+    /// Switch to a rule preset, by index into `PRESETS`.
+    Rule(usize),
+    Overlay(Overlay),
+    ExportText,
+    ExportPacked,
+    ExportSession,
+}
+
+impl Action {
+    /// Every action, in the order the palette lists them.
+    pub fn all() -> Vec<Action> {
+        let commands = Command::ALL.into_iter().filter(|&command| command != Command::OpenPalette).map(Action::Command);
+        let patterns = BUNDLED.iter().map(|&(name, _)| Action::Pattern(name));
+        let rules = (0..PRESETS.len()).map(Action::Rule);
+        let overlays = Overlay::ALL.into_iter().map(Action::Overlay);
+        let exports = [Action::ExportText, Action::ExportPacked, Action::ExportSession];
+        commands.chain(patterns).chain(rules).chain(overlays).chain(exports).collect()
+    }
+
+    pub fn label(self, text: &Strings) -> String {
+        match self {
+            Action::Command(command) => command.label(text).to_string(),
+            Action::Pattern(name) => (text.palette_place)(name),
+            Action::Rule(index) => (text.palette_rule)(PRESETS[index].0, PRESETS[index].1),
+            Action::Overlay(overlay) => (text.palette_overlay)(overlay.label(text)),
+            Action::ExportText => text.export_text.to_string(),
+            Action::ExportPacked => text.export_packed.to_string(),
+            Action::ExportSession => text.export_session.to_string(),
+        }
+    }
+}
+
+/// True when every word of `query` appears in `label` (case-insensitive).
+fn matches(query: &str, label: &str) -> bool {
+    let label = label.to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| label.contains(word))
+}
+
+/// The palette itself, as a modal over the page. Runs nothing by itself:
+/// the picked action goes to `on_run`.
+#[component]
+pub fn CommandPalette(on_run: EventHandler<Action>, on_close: EventHandler<()>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let keymap = use_context::<Signal<Keymap>>();
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+
+    let entries: Vec<(Action, String)> =
+        Action::all().into_iter().map(|action| (action, action.label(text))).filter(|(_, label)| matches(&query(), label)).collect();
+    let last = entries.len().saturating_sub(1);
+    let picked = entries.get(selected().min(last)).map(|&(action, _)| action);
+
+    rsx! {
+        div { class: "palette-backdrop", onclick: move |_| on_close.call(()),
+            div {
+                class: "palette",
+                role: "dialog",
+                aria_label: text.command_palette,
+                onclick: move |evt| evt.stop_propagation(),
+                input {
+                    r#type: "search",
+                    placeholder: text.palette_placeholder,
+                    aria_label: text.command_palette,
+                    value: "{query}",
+                    onmounted: move |evt| async move {
+                        let _ = evt.set_focus(true).await;
+                    },
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        selected.set(0);
+                    },
+                    onkeydown: move |evt| match evt.key() {
+                        Key::ArrowDown => {
+                            evt.prevent_default();
+                            selected.set((selected() + 1).min(last));
+                        }
+                        Key::ArrowUp => {
+                            evt.prevent_default();
+                            selected.set(selected().saturating_sub(1));
+                        }
+                        Key::Enter => {
+                            if let Some(action) = picked {
+                                on_run.call(action);
+                            }
+                        }
+                        Key::Escape => on_close.call(()),
+                        _ => {}
+                    },
+                }
+                ul { role: "listbox",
+                    for (index, (action, label)) in entries.into_iter().enumerate() {
+                        li {
+                            role: "option",
+                            aria_selected: index == selected().min(last),
+                            class: if index == selected().min(last) { "selected" },
+                            onclick: move |_| on_run.call(action),
+                            "{label}"
+                            if let Action::Command(command) = action {
+                                if let Some(binding) = keymap.read().binding(command) {
+                                    kbd { "{binding}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Automaton settings
+//
+// The controls for whatever settings the running automaton declares through
+// `Automaton::params`: a slider, a number box or a checkbox per `ParamSpec`,
+// labelled from the spec's key. A new automaton setting only needs its spec
+// and an entry in `label`.
+
+use dioxus::prelude::*;
+use life_engine::automata::{ParamKind, ParamSpec, World};
+
+use crate::i18n::{Language, Strings};
+
+/// One control per setting of the automaton in `world`.
+#[component]
+pub fn ParamControls(world: Signal<World>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let current = world.read();
+    let automaton = current.automaton();
+    let params: Vec<(ParamSpec, f64)> = automaton.params().into_iter().map(|spec| (spec, automaton.param(spec.key))).collect();
+
+    let mut set = move |spec: ParamSpec, value: f64| world.write().automaton_mut().set_param(spec.key, spec.clamp(value));
+
+    rsx! {
+        for (spec, value) in params {
+            label { key: "{spec.key}",
+                match spec.kind {
+                    ParamKind::Slider { min, max, step } => rsx! {
+                        {label(spec.key, value, text)}
+                        " "
+                        input {
+                            r#type: "range",
+                            min,
+                            max,
+                            step,
+                            value: "{value}",
+                            oninput: move |evt| {
+                                if let Ok(value) = evt.value().parse() {
+                                    set(spec, value);
+                                }
+                            },
+                        }
+                    },
+                    ParamKind::Integer { min, max } => rsx! {
+                        {label(spec.key, value, text)}
+                        " "
+                        input {
+                            r#type: "number",
+                            min,
+                            max: max.map(|max| max.to_string()),
+                            value: "{value}",
+                            oninput: move |evt| {
+                                if let Ok(value) = evt.value().parse() {
+                                    set(spec, value);
+                                }
+                            },
+                        }
+                    },
+                    ParamKind::Toggle => rsx! {
+                        input {
+                            r#type: "checkbox",
+                            checked: value != 0.0,
+                            onchange: move |evt| set(spec, evt.checked() as u8 as f64),
+                        }
+                        " "
+                        {label(spec.key, value, text)}
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// What the setting called `key` is called in the UI, given its value.
+fn label(key: &str, value: f64, text: &Strings) -> String {
+    match key {
+        "rule" => text.rule_number.to_string(),
+        "drop" => text.grains_per_drop.to_string(),
+        "feed" => text.feed_center.to_string(),
+        "growth" => (text.growth_chance)(value),
+        "lightning" => (text.lightning_chance)(value),
+        "infection" => (text.infection_chance)(value),
+        "duration" => (text.infection_duration)(value as u32),
+        "immunity_loss" => (text.immunity_loss_chance)(value),
+        "fish_breed" => text.fish_breed.to_string(),
+        "shark_breed" => text.shark_breed.to_string(),
+        "starve" => text.shark_starve.to_string(),
+        "radius" => (text.brush_radius)(value as usize),
+        "temperature" => (text.temperature)(value),
+        key => key.to_string(),
+    }
+}
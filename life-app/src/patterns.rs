@@ -0,0 +1,142 @@
+// Pattern sources
+//
+// * **Bundled** – a handful of classics compiled into the binary, so search
+//   works offline.
+// * **LifeWiki** – anything else is looked up on conwaylife.com by its file
+//   name (`pattern name` → `patternname.rle`) and downloaded as RLE.
+//
+// Downloads go through the webview's own `fetch` (`document::eval`): it
+// already has TLS, redirects and an HTTP cache, and in a browser it's the
+// only way out, so there's no HTTP client to bundle and keep up to date.
+
+use dioxus::prelude::*;
+
+use life_engine::pattern::Pattern;
+use life_formats::PatternText;
+
+use crate::i18n::Language;
+
+/// Where [`fetch`] downloads `<name>.rle` from.
+const LIFEWIKI: &str = "https://conwaylife.com/patterns";
+
+/// Bundled patterns as `(name, RLE body)`.
+pub const BUNDLED: [(&str, &str); 10] = [
+    ("Glider", "bo$2bo$3o!"),
+    ("Lightweight spaceship", "bo2bo$o4b$o3bo$4o!"),
+    ("R-pentomino", "b2o$2o$bo!"),
+    ("Acorn", "bo5b$3bo3b$2o2b3o!"),
+    ("Diehard", "6bob$2o6b$bo3b3o!"),
+    ("Pulsar", "2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!"),
+    ("Pentadecathlon", "2bo4bo2b$2ob4ob2o$2bo4bo!"),
+    ("Gosper glider gun", "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!"),
+    ("Switch engine", "bobo$o$bo2bo$3b3o!"),
+    ("One-line infinite growth", "8ob5o3b3o6b7ob5o!"),
+];
+
+/// Bundled patterns whose name contains `query` (case-insensitive).
+pub fn search(query: &str) -> Vec<&'static str> {
+    let query = query.trim().to_lowercase();
+    BUNDLED
+        .iter()
+        .map(|&(name, _)| name)
+        .filter(|name| name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// The bundled pattern called `name`, parsed.
+pub fn bundled(name: &str) -> Option<Pattern> {
+    let (name, rle) = BUNDLED.iter().find(|(entry, _)| *entry == name)?;
+    let mut pattern = Pattern::parse_rle(rle).ok()?;
+    pattern.name = Some(name.to_string());
+    Some(pattern)
+}
+
+/// LifeWiki file name for a pattern: lowercase letters and digits only.
+pub fn lifewiki_name(query: &str) -> String {
+    query.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+/// Download and parse `<name>.rle` from LifeWiki.
+pub async fn fetch(name: &str) -> Result<Pattern, String> {
+    let url = format!("{LIFEWIKI}/{name}.rle");
+    let mut eval = document::eval(
+        r#"
+        const url = await dioxus.recv();
+        try {
+            const response = await fetch(url);
+            if (!response.ok) throw new Error(`HTTP ${response.status}`);
+            dioxus.send([true, await response.text()]);
+        } catch (error) {
+            dioxus.send([false, String(error)]);
+        }
+        "#,
+    );
+    eval.send(url.clone()).map_err(|err| err.to_string())?;
+    let (ok, text): (bool, String) = eval.recv().await.map_err(|err| err.to_string())?;
+    if !ok {
+        return Err(format!("could not download {url}: {text}"));
+    }
+    let mut pattern = Pattern::parse_rle(&text)?;
+    pattern.name.get_or_insert_with(|| name.to_string());
+    Ok(pattern)
+}
+
+/// Search box over the bundled patterns, with a button per match, and a
+/// LifeWiki download for anything else. What's picked is placed through
+/// `on_place`.
+#[component]
+pub fn PatternSearch(on_place: EventHandler<Pattern>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut pattern_query = use_signal(String::new);
+    // LifeWiki download whenever this is set; ready = `Some(outcome)`, and
+    // `None` inside means nothing was requested.
+    let mut wanted_pattern = use_signal(|| None::<String>);
+    let download = use_resource(move || async move {
+        let name = wanted_pattern()?;
+        Some(fetch(&name).await.map(|pattern| {
+            on_place.call(pattern.clone());
+            Pattern { name: pattern.name.clone().or(Some(name)), ..pattern }
+        }))
+    });
+
+    rsx! {
+        div { class: "controls",
+            input {
+                r#type: "search",
+                placeholder: text.find_pattern,
+                aria_label: text.find_pattern,
+                value: "{pattern_query}",
+                oninput: move |evt| pattern_query.set(evt.value()),
+            }
+            for name in search(&pattern_query()) {
+                button {
+                    onclick: move |_| {
+                        if let Some(pattern) = bundled(name) {
+                            on_place.call(pattern);
+                        }
+                    },
+                    "{name}"
+                }
+            }
+            button {
+                disabled: lifewiki_name(&pattern_query()).is_empty(),
+                onclick: move |_| wanted_pattern.set(Some(lifewiki_name(&pattern_query()))),
+                "{text.fetch_lifewiki}"
+            }
+            match &*download.read() {
+                None => rsx! { span { "{text.downloading}" } },
+                Some(Some(Ok(pattern))) => rsx! {
+                    span { {(text.placed)(pattern.name.as_deref().unwrap_or_default())} }
+                    if let Some(author) = &pattern.author {
+                        span { {(text.pattern_author)(author)} }
+                    }
+                    if !pattern.comments.is_empty() {
+                        p { class: "description", {pattern.comments.join(" ")} }
+                    }
+                },
+                Some(Some(Err(err))) => rsx! { span { class: "error", role: "alert", "{err}" } },
+                Some(None) => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// Battery saver
+//
+// A low-power profile for phones and laptops off the charger: generations
+// come at most every `SAVER_DELAY_MS`, fades and trails are switched off, and
+// a run that settles (a generation with no births or deaths) pauses itself,
+// so neither the timer nor the renderer has anything left to do. It starts
+// on when the device looks mobile or is running on battery, as far as the
+// webview can tell, and can be toggled either way.
+
+use dioxus::prelude::*;
+
+/// Shortest time between generations in battery saver, in ms.
+pub const SAVER_DELAY_MS: u64 = 500;
+
+/// True on a touch-first device or one that isn't charging. Browsers
+/// without the Battery Status API only get the first check.
+pub async fn on_battery() -> bool {
+    let mut eval = document::eval(
+        r#"(navigator.getBattery ? navigator.getBattery().then(battery => !battery.charging, () => false) : Promise.resolve(false))
+            .then(unplugged => dioxus.send(unplugged || window.matchMedia("(pointer: coarse)").matches));"#,
+    );
+    eval.recv().await.unwrap_or(false)
+}
+
+/// Most generations per tick of a `delay` ms interval in battery saver.
+pub fn saver_rate(delay: u64) -> f64 {
+    delay as f64 / SAVER_DELAY_MS as f64
+}
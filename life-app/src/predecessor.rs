@@ -0,0 +1,136 @@
+// Predecessor search ("what came before this?")
+//
+// For a small target region, look for any arrangement of the surrounding
+// `(rows + 2) × (cols + 2)` window whose next generation reproduces the
+// target exactly. Cells outside the window can't influence the target, so an
+// exhaustive search with no solution proves the region is an *orphan*: a
+// pattern that can only ever appear as a Garden of Eden.
+//
+// Plain backtracking, window cells in row-major order, dead before alive,
+// kept on the window itself rather than the call stack so it can pause.
+// After each assignment every target cell touching it is checked for
+// feasibility: can the still-unknown neighbours push the count into the
+// rule's birth/survival set? That prunes hard enough for ~10×10 targets.
+
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+
+/// Largest target edge the search accepts.
+pub const MAX_EDGE: usize = 10;
+/// Backtracking steps before giving up.
+const NODE_BUDGET: u64 = 3_000_000;
+/// Backtracking steps taken between yields to the page.
+pub const SLICE: u64 = 20_000;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Outcome {
+    /// A predecessor window, `(rows + 2) × (cols + 2)`.
+    Found(Pattern),
+    /// Exhaustive search failed: the region is a Garden of Eden.
+    Orphan,
+    /// Budget ran out before an answer; a strong hint of an orphan.
+    GaveUp,
+}
+
+/// A search for a predecessor, run a slice at a time so the page stays
+/// responsive and it can be stopped.
+pub struct Search {
+    target: Vec<bool>,
+    rows: usize,
+    cols: usize,
+    width: usize,
+    rule: Rule,
+    window: Vec<Option<bool>>,
+    /// Window cell being assigned next.
+    idx: usize,
+    nodes: u64,
+}
+
+impl Search {
+    /// Start looking for a predecessor of the `rows × cols` `target` under
+    /// `rule`.
+    pub fn new(target: Vec<bool>, rows: usize, cols: usize, rule: Rule) -> Self {
+        Self { target, rows, cols, width: cols + 2, rule, window: vec![None; (rows + 2) * (cols + 2)], idx: 0, nodes: 0 }
+    }
+
+    /// Share of the budget spent so far, `0.0` to `1.0`.
+    pub fn progress(&self) -> f64 {
+        self.nodes as f64 / NODE_BUDGET as f64
+    }
+
+    /// Take up to `steps` more backtracking steps; the outcome once there
+    /// is one.
+    pub fn run(&mut self, steps: u64) -> Option<Outcome> {
+        for _ in 0..steps {
+            if self.idx == self.window.len() {
+                let cells = self.window.iter().map(|cell| cell.unwrap_or(false)).collect();
+                return Some(Outcome::Found(Pattern::new(self.cols + 2, self.rows + 2, cells)));
+            }
+            // Dead, then alive, then back up to the previous cell.
+            self.window[self.idx] = match self.window[self.idx] {
+                None => Some(false),
+                Some(false) => Some(true),
+                Some(true) => {
+                    self.window[self.idx] = None;
+                    if self.idx == 0 {
+                        return Some(Outcome::Orphan);
+                    }
+                    self.idx -= 1;
+                    continue;
+                }
+            };
+            self.nodes += 1;
+            if self.nodes > NODE_BUDGET {
+                return Some(Outcome::GaveUp);
+            }
+            if self.consistent_around(self.idx) {
+                self.idx += 1;
+            }
+        }
+        None
+    }
+
+    /// Check every target cell whose 3×3 neighbourhood contains window `idx`.
+    fn consistent_around(&self, idx: usize) -> bool {
+        let (w_row, w_col) = (idx / self.width, idx % self.width);
+        // Window (r, c) is in the neighbourhood of target (r - 1 + dr, c - 1 + dc)
+        // for dr, dc in -1..=1; target coords are window coords minus one.
+        for t_row in w_row.saturating_sub(2)..=w_row.min(self.rows - 1) {
+            for t_col in w_col.saturating_sub(2)..=w_col.min(self.cols - 1) {
+                if !self.feasible(t_row, t_col) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Can target `(row, col)` still come out right given what's assigned?
+    fn feasible(&self, row: usize, col: usize) -> bool {
+        let want = self.target[row * self.cols + col];
+        let (mut alive, mut unknown) = (0u8, 0u8);
+        let mut center = None;
+        for d_row in 0..3 {
+            for d_col in 0..3 {
+                let cell = self.window[(row + d_row) * self.width + col + d_col];
+                if d_row == 1 && d_col == 1 {
+                    center = Some(cell);
+                    continue;
+                }
+                match cell {
+                    Some(true) => alive += 1,
+                    Some(false) => {}
+                    None => unknown += 1,
+                }
+            }
+        }
+        let centers: &[bool] = match center.flatten() {
+            Some(true) => &[true],
+            Some(false) => &[false],
+            None => &[false, true],
+        };
+        centers
+            .iter()
+            .any(|&state| (alive..=alive + unknown).any(|count| self.rule.next(state, count) == want))
+    }
+}
@@ -0,0 +1,63 @@
+// Frame profile
+//
+// Where the time of a frame goes, as rolling averages over the last
+// `WINDOW` frames: simulating (every generation an interval tick runs, and
+// everything that follows the board) against rendering (the board component
+// building its elements, timed from just before it runs to just after). The
+// debug HUD shows both, so a slow board can be blamed on the right side.
+
+use std::collections::VecDeque;
+
+/// Frames each average covers.
+const WINDOW: usize = 60;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Profile {
+    tick: VecDeque<f64>,
+    render: VecDeque<f64>,
+    /// When the render under way started, in ms.
+    render_started: Option<f64>,
+}
+
+impl Profile {
+    /// Add one frame's simulation time, in ms.
+    pub fn record_tick(&mut self, ms: f64) {
+        push_capped(&mut self.tick, ms);
+    }
+
+    /// A render starts at `now` ms.
+    pub fn start_render(&mut self, now: f64) {
+        self.render_started = Some(now);
+    }
+
+    /// The render started last finishes at `now` ms.
+    pub fn end_render(&mut self, now: f64) {
+        if let Some(started) = self.render_started.take() {
+            push_capped(&mut self.render, now - started);
+        }
+    }
+
+    /// Average simulation time per frame, in ms.
+    pub fn tick_ms(&self) -> f64 {
+        average(&self.tick)
+    }
+
+    /// Average render time per frame, in ms.
+    pub fn render_ms(&self) -> f64 {
+        average(&self.render)
+    }
+}
+
+fn push_capped(samples: &mut VecDeque<f64>, ms: f64) {
+    if samples.len() == WINDOW {
+        samples.pop_front();
+    }
+    samples.push_back(ms);
+}
+
+fn average(samples: &VecDeque<f64>) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
@@ -0,0 +1,199 @@
+// Soup race
+//
+// Two independent random soups start on either half of the board, separated
+// by empty divider bands: one down the middle, and one along the left and
+// right edges, which meet wherever the boundary joins them. Every live cell
+// belongs to a side: survivors keep their side, newborns join the majority
+// of the live neighbours that bore them (ties go left, which is rare with
+// three parents). After `length` generations the side holding more cells in
+// the *opponent's* half wins.
+
+use dioxus::prelude::*;
+use life_engine::rng::Rng;
+use life_engine::rule::Rule;
+use life_engine::universe::{Universe, UniverseBuilder};
+
+use crate::i18n::{Language, Strings};
+
+/// Empty columns left on each side of the centre line, and in from each
+/// edge, at the start.
+const DIVIDER: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Side::Left => text.left,
+            Side::Right => text.right,
+        }
+    }
+
+    pub fn color(self) -> &'static str {
+        match self {
+            Side::Left => "var(--gol-race-left)",
+            Side::Right => "var(--gol-race-right)",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Race {
+    owners: Vec<Option<Side>>,
+    width: usize,
+    pub generation: u64,
+    pub length: u64,
+    /// Live cells per side, `[left, right]`.
+    pub population: [usize; 2],
+    /// Live cells per side inside the opposing half, `[left, right]`.
+    pub invaded: [usize; 2],
+}
+
+impl Race {
+    /// Build the two-soup starting board and the matching scoreboard.
+    pub fn start(width: usize, height: usize, seed: u64, rule: Rule, length: u64) -> (Universe, Race) {
+        let mut universe = UniverseBuilder::new().size(width, height).seed(seed).rule(rule).build();
+        // Independent streams so the halves aren't mirror images of each other.
+        let mut left = Rng::new(seed);
+        let mut right = Rng::new(seed ^ 0xA5A5_A5A5_A5A5_A5A5);
+        let middle = width / 2;
+        let mut owners = vec![None; width * height];
+
+        for (idx, cell) in universe.cells_mut().iter_mut().enumerate() {
+            let col = idx % width;
+            let (alive, side) = if col < DIVIDER || col + DIVIDER >= width {
+                (false, Side::Left)
+            } else if col + DIVIDER < middle {
+                (left.gen_bool(0.5), Side::Left)
+            } else if col >= middle + DIVIDER {
+                (right.gen_bool(0.5), Side::Right)
+            } else {
+                (false, Side::Left)
+            };
+            *cell = alive;
+            owners[idx] = alive.then_some(side);
+        }
+
+        let mut race = Race { owners, width, generation: 0, length, population: [0; 2], invaded: [0; 2] };
+        race.score();
+        (universe, race)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.generation >= self.length
+    }
+
+    pub fn owner(&self, idx: usize) -> Option<Side> {
+        self.owners.get(idx).copied().flatten()
+    }
+
+    /// Side ahead on invaded territory, `None` on a tie.
+    pub fn leader(&self) -> Option<Side> {
+        match self.invaded[0].cmp(&self.invaded[1]) {
+            std::cmp::Ordering::Greater => Some(Side::Left),
+            std::cmp::Ordering::Less => Some(Side::Right),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Re-assign ownership after `universe` advanced one generation.
+    /// Does nothing once the race is over, freezing the final score.
+    pub fn record(&mut self, universe: &Universe) {
+        if self.finished() || self.owners.len() != universe.cells.len() {
+            return;
+        }
+        let previous = &self.owners;
+        let next = (0..universe.cells.len())
+            .map(|idx| {
+                if !universe.cells[idx] {
+                    return None;
+                }
+                if let Some(side) = previous[idx] {
+                    return Some(side);
+                }
+                let neighbors = universe.neighbor_indices(idx / universe.width, idx % universe.width);
+                let (left, right) = neighbors.iter().fold((0, 0), |(l, r), &n| match previous[n] {
+                    Some(Side::Left) => (l + 1, r),
+                    Some(Side::Right) => (l, r + 1),
+                    None => (l, r),
+                });
+                Some(if right > left { Side::Right } else { Side::Left })
+            })
+            .collect();
+        self.owners = next;
+        self.generation += 1;
+        self.score();
+    }
+
+    fn score(&mut self) {
+        let middle = self.width / 2;
+        self.population = [0; 2];
+        self.invaded = [0; 2];
+        for (idx, owner) in self.owners.iter().enumerate() {
+            let left_half = idx % self.width < middle;
+            match owner {
+                Some(Side::Left) => {
+                    self.population[0] += 1;
+                    self.invaded[0] += !left_half as usize;
+                }
+                Some(Side::Right) => {
+                    self.population[1] += 1;
+                    self.invaded[1] += left_half as usize;
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Starting a race, its length, and the scoreboard of the one under way. The
+/// race itself runs with the board, which `on_start` hands a new one to.
+#[component]
+pub fn RacePanel(race: Signal<Option<Race>>, mut race_length: Signal<u64>, on_start: EventHandler) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    rsx! {
+        div { class: "controls",
+            button { onclick: move |_| on_start.call(()), "{text.start_race}" }
+            label {
+                "{text.race_for} "
+                input {
+                    r#type: "number",
+                    min: 1,
+                    value: "{race_length}",
+                    oninput: move |evt| {
+                        if let Ok(length) = evt.value().parse::<u64>() {
+                            race_length.set(length.max(1));
+                        }
+                    },
+                }
+                " {text.generations}"
+            }
+            if let Some(race) = race() {
+                span {
+                    "{(text.race_generation)(race.generation, race.length)} · "
+                    span {
+                        style: "color: {Side::Left.color()}",
+                        {(text.race_side)(Side::Left.label(text), race.population[0], race.invaded[0])}
+                    }
+                    " · "
+                    span {
+                        style: "color: {Side::Right.color()}",
+                        {(text.race_side)(Side::Right.label(text), race.population[1], race.invaded[1])}
+                    }
+                }
+                if race.finished() {
+                    strong {
+                        match race.leader() {
+                            Some(side) => (text.wins)(side.label(text)),
+                            None => text.draw_game.to_string(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
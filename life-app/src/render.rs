@@ -0,0 +1,123 @@
+// Render options
+//
+// Purely cosmetic settings for how boards are drawn. None of them touch the
+// simulation.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Strings;
+use crate::viewport::Viewport;
+
+/// Spacings offered for grid lines, in cells.
+pub const GRID_SPACINGS: [usize; 3] = [1, 5, 10];
+/// Gaps offered between cells, in px. Cells keep their pitch; the gap is
+/// carved out of each cell, so it has to stay below the cell size.
+pub const CELL_GAPS: [usize; 3] = [0, 1, 2];
+
+/// Fade durations offered, in ms (0 = cells switch instantly).
+pub const FADE_DURATIONS: [u64; 4] = [0, 50, 150, 400];
+
+/// Generations slower than this, in ms, crossfade into the next when asked.
+pub const CROSSFADE_AFTER_MS: f64 = 250.0;
+
+/// Trail lengths offered, in generations (0 = no trail).
+pub const TRAIL_LENGTHS: [u16; 4] = [0, 8, 16, 32];
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CellShape {
+    #[default]
+    Square,
+    Circle,
+}
+
+impl CellShape {
+    pub const ALL: [CellShape; 2] = [CellShape::Square, CellShape::Circle];
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            CellShape::Square => text.square_cells,
+            CellShape::Circle => text.round_cells,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct RenderOptions {
+    /// Draw grid lines every this many cells.
+    pub grid: Option<usize>,
+    pub shape: CellShape,
+    /// Empty space between neighbouring cells, in px.
+    pub gap: usize,
+    /// Births fade in and deaths fade out over this many ms.
+    pub fade_ms: u64,
+    /// Cells that died within this many generations leave a dimming trail.
+    pub trail: u16,
+    /// At slow speeds, blend each generation into the next over the whole
+    /// time between them.
+    #[serde(default)]
+    pub crossfade: bool,
+}
+
+impl RenderOptions {
+    /// These options with every animated effect switched off.
+    pub fn calmed(self) -> Self {
+        Self { fade_ms: 0, trail: 0, crossfade: false, ..self }
+    }
+
+    /// These options for generations `period` ms apart: with crossfade on
+    /// and the period past `CROSSFADE_AFTER_MS`, cells fade for all of it.
+    pub fn crossfaded(self, period: Option<f64>) -> Self {
+        match period {
+            Some(period) if self.crossfade && period > CROSSFADE_AFTER_MS => Self { fade_ms: self.fade_ms.max(period as u64), ..self },
+            _ => self,
+        }
+    }
+
+    /// Class list for a board container.
+    pub fn board_class(&self) -> String {
+        let mut class = "game-board".to_string();
+        if self.shape == CellShape::Circle {
+            class.push_str(" round");
+        }
+        if self.gap > 0 {
+            class.push_str(" gapped");
+        }
+        if self.fade_ms > 0 {
+            class.push_str(" fading");
+        }
+        class
+    }
+
+    /// Inline style for a board container showing `viewport`.
+    pub fn board_style(&self, viewport: &Viewport, cell_size: usize) -> String {
+        format!(
+            "display: grid; grid-template-columns: repeat({}, {cell_size}px); grid-template-rows: repeat({}, {cell_size}px); --cell-gap: {}px; --fade: {}ms;",
+            viewport.cols,
+            viewport.rows,
+            self.gap.min(cell_size.saturating_sub(1)),
+            self.fade_ms,
+        )
+    }
+}
+
+/// Grid lines every `spacing` cells, laid over a board. The lines follow
+/// board coordinates, so they stay put relative to the cells while panning.
+#[component]
+pub fn GridLines(spacing: usize, viewport: Viewport, cell_size: usize) -> Element {
+    let step = spacing * cell_size;
+    let offset_x = (viewport.col % spacing) * cell_size;
+    let offset_y = (viewport.row % spacing) * cell_size;
+    rsx! {
+        div {
+            class: "grid-lines",
+            aria_hidden: "true",
+            style: format!(
+                "width: {}px; height: {}px; background-size: {step}px {step}px; background-position: -{offset_x}px -{offset_y}px;",
+                viewport.cols * cell_size,
+                viewport.rows * cell_size,
+            ),
+        }
+    }
+}
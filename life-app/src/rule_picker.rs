@@ -0,0 +1,57 @@
+// Rule picker: preset dropdown plus free-form B/S entry
+use dioxus::prelude::*;
+use life_engine::rule::{Rule, PRESETS};
+
+use crate::i18n::Language;
+
+#[component]
+pub fn RulePicker(rule: Rule, on_change: EventHandler<Rule>) -> Element {
+    let mut draft = use_signal(|| rule.to_string());
+    let mut error = use_signal(|| None::<String>);
+    let current = rule.to_string();
+    let text = use_context::<Signal<Language>>()().strings();
+
+    // Keep the text box in sync when the rule is changed from elsewhere.
+    use_effect(use_reactive!(|rule| {
+        draft.set(rule.to_string());
+        error.set(None);
+    }));
+
+    rsx! {
+        span { class: "rule-picker",
+            select {
+                aria_label: text.rule_preset,
+                onchange: move |evt| {
+                    if let Ok(rule) = Rule::parse(&evt.value()) {
+                        draft.set(rule.to_string());
+                        error.set(None);
+                        on_change.call(rule);
+                    }
+                },
+                for (name, notation) in PRESETS {
+                    option { value: notation, selected: current == notation, "{name}" }
+                }
+                if !PRESETS.iter().any(|(_, notation)| *notation == current) {
+                    option { value: "{current}", selected: true, "{text.custom_rule}" }
+                }
+            }
+            input {
+                r#type: "text",
+                aria_label: text.rule_notation,
+                size: 12,
+                value: "{draft}",
+                oninput: move |evt| draft.set(evt.value()),
+                onchange: move |_| match Rule::parse(&draft.read()) {
+                    Ok(rule) => {
+                        error.set(None);
+                        on_change.call(rule);
+                    }
+                    Err(message) => error.set(Some(message)),
+                },
+            }
+            if let Some(message) = error() {
+                span { class: "error", role: "alert", "{message}" }
+            }
+        }
+    }
+}
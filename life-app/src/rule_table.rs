@@ -0,0 +1,76 @@
+// Rule-table settings
+//
+// Settings for the rule-table mode: upload a Golly `.rule` file to run its
+// `@TABLE`, and pick which state painting puts down. A file that doesn't
+// parse leaves the current table running and says why.
+
+use dioxus::prelude::*;
+use life_engine::automata::table::Table;
+use life_engine::automata::World;
+
+use crate::i18n::Language;
+
+/// The upload and brush for the rule table in `world`.
+#[component]
+pub fn RuleTableSettings(world: Signal<World>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut error = use_signal(|| None::<String>);
+    let World::RuleTable(board) = &*world.read() else { return rsx! {} };
+    let table = board.table();
+    let (name, states, brush) = (table.name.clone(), table.states, board.brush);
+    let swatches: Vec<(u8, u32)> = (1..=table.last_state()).map(|state| (state, table.color(state))).collect();
+
+    rsx! {
+        div { class: "controls rule-table",
+            label {
+                "{text.upload_rule_table} "
+                input {
+                    r#type: "file",
+                    accept: ".rule,.table,.txt",
+                    onchange: move |evt| async move {
+                        let Some(files) = evt.files() else { return };
+                        for name in files.files() {
+                            let Some(file) = files.read_file_to_string(&name).await else { continue };
+                            match Table::parse(&file) {
+                                Ok(table) => {
+                                    if let World::RuleTable(board) = &mut *world.write() {
+                                        board.set_table(table);
+                                    }
+                                    error.set(None);
+                                }
+                                Err(message) => error.set(Some(message)),
+                            }
+                        }
+                    },
+                }
+            }
+            span { {(text.rule_table_status)(&name, states)} }
+            if let Some(message) = error() {
+                span { class: "error", role: "alert", "{message}" }
+            }
+        }
+        div { class: "controls rule-table-brush", role: "group", aria_label: text.paint_state,
+            for (state, rgb) in swatches {
+                button {
+                    aria_pressed: brush == state,
+                    onclick: move |_| {
+                        if let World::RuleTable(board) = &mut *world.write() {
+                            board.brush = state;
+                        }
+                    },
+                    span { class: "state-swatch", style: "background-color: #{rgb:06x};" }
+                    {(text.state_label)(state)}
+                }
+            }
+            button {
+                aria_pressed: brush == 0,
+                onclick: move |_| {
+                    if let World::RuleTable(board) = &mut *world.write() {
+                        board.brush = 0;
+                    }
+                },
+                "{text.material_eraser}"
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Soup search
+//
+// Background hunt for methuselahs: small random soups are run headlessly on
+// the infinite plane until they settle into periodic behaviour (or hit the
+// generation cap), and the longest-lived and most-expanding ones are kept.
+// Work is done in slices from the UI loop so the page stays responsive.
+
+use dioxus::prelude::*;
+use life_engine::rng::Rng;
+use life_engine::rule::Rule;
+use life_engine::sparse::SparsePlane;
+use life_engine::universe::{random_seed, CellState, Universe};
+
+use crate::i18n::Language;
+
+/// Edge length of the random soups, in cells.
+pub const SOUP: usize = 16;
+/// Entries kept per leaderboard.
+const TOP: usize = 10;
+/// A run counts as settled once its population has been periodic, with a
+/// period of at most `MAX_PERIOD`, for this many generations.
+pub const SETTLE_WINDOW: usize = 60;
+const MAX_PERIOD: usize = 15;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SoupResult {
+    pub seed: u64,
+    /// Generations until the soup settled (or the cap, if it never did).
+    pub lifespan: u64,
+    pub settled: bool,
+    /// Population when the run ended.
+    pub population: usize,
+    /// Largest bounding-box edge reached during the run.
+    pub span: i32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Run {
+    seed: u64,
+    plane: SparsePlane,
+    generation: u64,
+    populations: Vec<usize>,
+    span: i32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SoupSearch {
+    rng: Rng,
+    rule: Rule,
+    pub max_generations: u64,
+    current: Option<Run>,
+    pub tried: u64,
+    /// Best by lifespan, longest first.
+    pub longest: Vec<SoupResult>,
+    /// Best by span, widest first.
+    pub widest: Vec<SoupResult>,
+}
+
+impl SoupSearch {
+    pub fn new(seed: u64, rule: Rule, max_generations: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            rule,
+            max_generations,
+            current: None,
+            tried: 0,
+            longest: Vec::new(),
+            widest: Vec::new(),
+        }
+    }
+
+    /// The `SOUP × SOUP` soup a seed expands to, as `(row, col)` cells.
+    pub fn soup(seed: u64) -> Vec<(i32, i32)> {
+        let mut rng = Rng::new(seed);
+        (0..SOUP * SOUP)
+            .filter(|_| rng.gen_bool(0.5))
+            .map(|i| ((i / SOUP) as i32, (i % SOUP) as i32))
+            .collect()
+    }
+
+    /// Replace `universe` with the soup for `seed`, centred on the board.
+    pub fn load(seed: u64, universe: &mut Universe) {
+        universe.cells_mut().fill(false);
+        let top = universe.height.saturating_sub(SOUP) / 2;
+        let left = universe.width.saturating_sub(SOUP) / 2;
+        for (row, col) in Self::soup(seed) {
+            // Soup cells past the edge of a small board are dropped.
+            let _ = universe.set(top + row as usize, left + col as usize, CellState::Alive);
+        }
+    }
+
+    /// Spend up to `budget` generations of work, starting new soups as old
+    /// ones finish.
+    pub fn advance(&mut self, mut budget: u64) {
+        while budget > 0 {
+            let run = self.current.get_or_insert_with(|| {
+                let seed = self.rng.next_u64();
+                Run {
+                    seed,
+                    plane: SparsePlane::new(Self::soup(seed), self.rule),
+                    generation: 0,
+                    populations: Vec::new(),
+                    span: SOUP as i32,
+                }
+            });
+
+            run.plane.step();
+            run.generation += 1;
+            budget -= 1;
+            run.populations.push(run.plane.population());
+            if let Some((r0, c0, r1, c1)) = run.plane.bounds() {
+                run.span = run.span.max(r1 - r0 + 1).max(c1 - c0 + 1);
+            }
+
+            let extinct = run.plane.population() == 0;
+            let settled = extinct || settled(&run.populations);
+            if settled || run.generation >= self.max_generations {
+                let lifespan = if settled && !extinct {
+                    run.generation.saturating_sub(SETTLE_WINDOW as u64)
+                } else {
+                    run.generation
+                };
+                let result = SoupResult {
+                    seed: run.seed,
+                    lifespan,
+                    settled,
+                    population: run.plane.population(),
+                    span: run.span,
+                };
+                self.current = None;
+                self.tried += 1;
+                insert_ranked(&mut self.longest, result, |r| r.lifespan as i64);
+                insert_ranked(&mut self.widest, result, |r| r.span as i64);
+            }
+        }
+    }
+}
+
+/// True when the tail of `populations` repeats with a short period.
+pub fn settled(populations: &[usize]) -> bool {
+    if populations.len() < SETTLE_WINDOW + MAX_PERIOD {
+        return false;
+    }
+    let tail = &populations[populations.len() - SETTLE_WINDOW - MAX_PERIOD..];
+    (1..=MAX_PERIOD).any(|period| (period..tail.len()).all(|i| tail[i] == tail[i - period]))
+}
+
+/// Keep `board` sorted by `key`, highest first, holding at most `TOP` entries.
+fn insert_ranked(board: &mut Vec<SoupResult>, result: SoupResult, key: impl Fn(&SoupResult) -> i64) {
+    let at = board.iter().position(|other| key(other) < key(&result)).unwrap_or(board.len());
+    if at < TOP {
+        board.insert(at, result);
+        board.truncate(TOP);
+    }
+}
+
+/// Starting and stopping the soup search under `rule`, its generation cap,
+/// and the leaderboards, whose soups load onto the board through `on_load`.
+/// The search runs with the board's UI loop.
+#[component]
+pub fn SearchPanel(mut search: Signal<Option<SoupSearch>>, rule: Rule, on_load: EventHandler<u64>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut search_cap = use_signal(|| 2000u64);
+    rsx! {
+        div { class: "controls",
+            button {
+                onclick: move |_| {
+                    let running = search.read().is_some();
+                    search.set((!running).then(|| SoupSearch::new(random_seed(), rule, search_cap())));
+                },
+                if search.read().is_some() { "{text.stop_search}" } else { "{text.start_search}" }
+            }
+            label {
+                "{text.cap} "
+                input {
+                    r#type: "number",
+                    min: 100,
+                    value: "{search_cap}",
+                    oninput: move |evt| {
+                        if let Ok(cap) = evt.value().parse::<u64>() {
+                            search_cap.set(cap.max(100));
+                            if let Some(search) = search.write().as_mut() {
+                                search.max_generations = cap.max(100);
+                            }
+                        }
+                    },
+                }
+                " {text.generations}"
+            }
+            if let Some(search) = search() {
+                span { {(text.soups_tried)(search.tried)} }
+            }
+        }
+        if let Some(search) = search() {
+            div { class: "search-results",
+                SoupTable { title: text.longest_lived, results: search.longest.clone(), on_load }
+                SoupTable { title: text.most_expanding, results: search.widest.clone(), on_load }
+            }
+        }
+    }
+}
+
+/// One soup-search leaderboard with a "Load" button per entry.
+#[component]
+fn SoupTable(title: String, results: Vec<SoupResult>, on_load: EventHandler<u64>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    rsx! {
+        table { class: "soup-table",
+            caption { "{title}" }
+            tr {
+                th { "{text.seed}" }
+                th { "{text.lifespan}" }
+                th { "{text.final_population}" }
+                th { "{text.span}" }
+                th {}
+            }
+            for result in results {
+                tr {
+                    td { "{result.seed:016x}" }
+                    td {
+                        "{result.lifespan}"
+                        if !result.settled { "+" }
+                    }
+                    td { "{result.population}" }
+                    td { "{result.span}" }
+                    td {
+                        button { onclick: move |_| on_load.call(result.seed), "{text.load}" }
+                    }
+                }
+            }
+        }
+    }
+}
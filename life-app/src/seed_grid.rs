@@ -0,0 +1,54 @@
+// Seed grid
+//
+// A row of small, independent boards under the same rule, each started from
+// its own random soup, for comparing how different seeds play out. Every
+// board is a whole `GameOfLife` with its own universe, clock and controls;
+// nothing is shared with the main board or with each other, and none of
+// them is autosaved. Grid boards come without their panels, so each is just
+// a board with its playback and stats, and can't open a seed grid of its own.
+
+use dioxus::prelude::*;
+use life_engine::rule::Rule;
+
+use crate::game::GameOfLife;
+
+/// Selectable numbers of boards in the grid; 0 hides it.
+pub const GRID_COUNTS: [usize; 4] = [0, 2, 4, 6];
+
+/// Edge length of each grid board, in cells.
+const SIZE: usize = 64;
+/// Pixels per cell on a grid board.
+const CELL_SIZE: usize = 3;
+/// Slots from here up belong to grid boards, well past any tab's.
+const GRID_SLOTS: u64 = 1 << 32;
+
+/// Slot of the `index`th grid board under the board in `slot`, or `None` if
+/// it would overflow. Grid boards aren't saved, so it only keeps their
+/// element ids apart.
+fn grid_slot(slot: u64, index: usize) -> Option<u64> {
+    let most = GRID_COUNTS[GRID_COUNTS.len() - 1] as u64;
+    slot.checked_mul(most)?.checked_add(GRID_SLOTS)?.checked_add(index as u64)
+}
+
+/// `count` boards under `rule`, below the board in `slot`. Changing the
+/// rule starts them all over. A board whose slot would overflow is left out.
+#[component]
+pub fn SeedGrid(count: usize, rule: Rule, slot: u64) -> Element {
+    rsx! {
+        div { class: "seed-grid",
+            for (index, slot) in (0..count).filter_map(|index| Some((index, grid_slot(slot, index)?))) {
+                GameOfLife {
+                    key: "{rule}-{index}",
+                    width: SIZE,
+                    height: SIZE,
+                    rule,
+                    cell_size: CELL_SIZE,
+                    autoplay: false,
+                    slot,
+                    autosave: false,
+                    panels: false,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Space-time view
+//
+// The last few generations stacked as translucent layers, newest on top, so
+// motion turns into shape: a glider leaves a slanted world-line through the
+// stack, an oscillator a column, a still life a solid pillar. The stack can
+// also be exported as a voxel file (Goxel's text format: one `x y z RRGGBB`
+// line per live cell, time along z) to look at in a real 3D viewer.
+
+use std::collections::VecDeque;
+
+use dioxus::prelude::*;
+use life_engine::universe::Universe;
+
+use crate::bundle;
+use crate::i18n::Language;
+use crate::viewport::Viewport;
+
+/// Generations kept in the stack.
+pub const LAYERS: usize = 32;
+/// Name the voxel export is saved under.
+const FILE_NAME: &str = "space-time.txt";
+/// Oblique projection: how far each row shifts right and down, and the
+/// vertical gap between layers, in cells.
+const SHEAR: f64 = 0.5;
+const SQUASH: f64 = 0.5;
+const GAP: f64 = 4.0;
+
+/// The recorded generations, oldest first.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SpaceTime {
+    width: usize,
+    height: usize,
+    layers: VecDeque<Vec<bool>>,
+}
+
+impl SpaceTime {
+    /// Add `universe` as the newest layer, dropping the oldest past
+    /// `LAYERS`. A board of another size starts the stack afresh.
+    pub fn record(&mut self, universe: &Universe) {
+        if (universe.width, universe.height) != (self.width, self.height) {
+            *self = Self { width: universe.width, height: universe.height, layers: VecDeque::new() };
+        }
+        if self.layers.len() == LAYERS {
+            self.layers.pop_front();
+        }
+        self.layers.push_back(universe.cells.clone());
+    }
+
+    /// The stack in Goxel's text voxel format, coloured from blue (oldest)
+    /// to yellow (newest).
+    pub fn to_voxels(&self) -> String {
+        let mut text = String::from("# Goxel 0.10.0\n# One line per voxel\n# X Y Z RRGGBB\n");
+        let last = self.layers.len().saturating_sub(1).max(1) as f64;
+        for (z, layer) in self.layers.iter().enumerate() {
+            let t = z as f64 / last;
+            let (r, g, b) = ((255.0 * t) as u8, (80.0 + 150.0 * t) as u8, (255.0 * (1.0 - t)) as u8);
+            for (idx, _) in layer.iter().enumerate().filter(|(_, &alive)| alive) {
+                text.push_str(&format!("{} {} {z} {r:02x}{g:02x}{b:02x}\n", idx % self.width, idx / self.width));
+            }
+        }
+        text
+    }
+}
+
+/// `space_time`'s layers as an oblique stack, cropped to `viewport`.
+#[component]
+pub fn SpaceTimeView(space_time: SpaceTime, viewport: Viewport) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut export_error = use_signal(|| None::<String>);
+    let (rows, cols) = (viewport.rows as f64, viewport.cols as f64);
+    let count = space_time.layers.len();
+    let (view_width, view_height) = (cols + rows * SHEAR, rows * SQUASH + count.saturating_sub(1) as f64 * GAP);
+
+    // One path per layer: its outline first, then a parallelogram per live
+    // cell, drawn oldest (lowest) first.
+    let layers: Vec<(String, String, f64)> = space_time
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(z, layer)| {
+            let top = (count - 1 - z) as f64 * GAP;
+            let outline = format!("M0 {top} h{cols} l{} {} h-{cols} z", rows * SHEAR, rows * SQUASH);
+            let mut cells = String::new();
+            for row in 0..viewport.rows {
+                for col in 0..viewport.cols {
+                    if layer.get((viewport.row + row) * space_time.width + viewport.col + col) == Some(&true) {
+                        let (x, y) = (col as f64 + row as f64 * SHEAR, top + row as f64 * SQUASH);
+                        cells.push_str(&format!("M{x} {y}h1l{SHEAR} {SQUASH}h-1z"));
+                    }
+                }
+            }
+            // Older layers fade out.
+            let opacity = 0.15 + 0.85 * (z + 1) as f64 / count as f64;
+            (outline, cells, opacity)
+        })
+        .collect();
+    let voxels = move || space_time.to_voxels();
+
+    rsx! {
+        figure { class: "space-time",
+            svg {
+                view_box: "0 0 {view_width} {view_height.max(1.0)}",
+                role: "img",
+                "aria-label": (text.space_time_caption)(count),
+                for (outline, cells, opacity) in layers {
+                    path { class: "space-time-layer", d: outline }
+                    path { class: "space-time-cells", d: cells, opacity: "{opacity:.2}" }
+                }
+            }
+            figcaption {
+                {(text.space_time_caption)(count)}
+                " "
+                button {
+                    onclick: move |_| export_error.set(bundle::download(FILE_NAME, "text/plain", &voxels()).err()),
+                    "{text.export_voxels}"
+                }
+                if let Some(err) = export_error() {
+                    span { class: "error", role: "alert", "{err}" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+// Playback speed
+//
+// Frames come every `FRAME_MS`, about the display rate, while the simulation
+// keeps a fixed timestep of its own: at normal speed one generation every
+// `BASE_DELAY` ms of wall-clock time, at half speed one every two, at 4×
+// four. Each frame runs however many generations the time since the last one
+// is worth, so late or dropped frames don't slow the run down. Lag beyond
+// `MAX_LAG_MS` is dropped rather than run in a burst after a stall. `Max`
+// batches as many generations into each frame as fit in `MAX_BATCH_MS` of
+// wall-clock time, leaving the rest of the frame to draw.
+//
+// Browsers slow the timers of hidden tabs to about one tick a second. With
+// "run in background" on, lag is never dropped and is paid off in batches of
+// up to `CATCH_UP_MS` a frame, so the board has caught up by the time the
+// tab is looked at again.
+
+use crate::i18n::Strings;
+use serde::{Deserialize, Serialize};
+
+/// Time between frames, in ms.
+pub const FRAME_MS: u64 = 16;
+
+/// Time between generations at normal speed, in ms.
+pub const BASE_DELAY: u64 = 50;
+
+/// Wall-clock budget per frame in `Max` mode, and for running what's due at
+/// the other speeds, in ms.
+pub const MAX_BATCH_MS: f64 = 30.0;
+
+/// Wall-clock budget per frame for catching up in the background, in ms.
+pub const CATCH_UP_MS: f64 = 250.0;
+
+/// Most lag carried from frame to frame, in ms, unless catching up.
+const MAX_LAG_MS: f64 = 250.0;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Speed {
+    Half,
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+    Max,
+}
+
+impl Speed {
+    pub const ALL: [Speed; 5] = [Speed::Half, Speed::Normal, Speed::Double, Speed::Quadruple, Speed::Max];
+
+    /// Multiplier on the base rate, `None` for `Max`.
+    pub fn multiplier(self) -> Option<f64> {
+        match self {
+            Speed::Half => Some(0.5),
+            Speed::Normal => Some(1.0),
+            Speed::Double => Some(2.0),
+            Speed::Quadruple => Some(4.0),
+            Speed::Max => None,
+        }
+    }
+
+    /// Generations per `BASE_DELAY`, held to at most `cap`; `None` is
+    /// unlimited.
+    pub fn rate(self, cap: Option<f64>) -> Option<f64> {
+        match (self.multiplier(), cap) {
+            (Some(rate), Some(cap)) => Some(rate.min(cap)),
+            (rate, cap) => rate.or(cap),
+        }
+    }
+
+    pub fn label(self, text: &Strings) -> String {
+        match self.multiplier() {
+            Some(multiplier) => format!("{multiplier}×"),
+            None => text.speed_max.to_string(),
+        }
+    }
+}
+
+/// Turns wall-clock time into whole generations on a fixed timestep.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pacer {
+    /// Time the simulation is behind the clock, in ms.
+    behind: f64,
+    /// When the last frame came, in ms.
+    last_frame: Option<f64>,
+}
+
+impl Pacer {
+    /// Generations due in a frame at `now` ms, at one every `period` ms.
+    /// Lag is capped at `MAX_LAG_MS` (or a period, if longer) unless
+    /// `keep_up`.
+    pub fn due(&mut self, now: f64, period: f64, keep_up: bool) -> u64 {
+        self.behind += self.last_frame.map_or(0.0, |last| now - last);
+        self.last_frame = Some(now);
+        if !keep_up {
+            self.behind = self.behind.min(MAX_LAG_MS.max(period));
+        }
+        let due = (self.behind / period).floor();
+        self.behind -= due * period;
+        due as u64
+    }
+
+    /// Hand back `left` generations of `period` ms that didn't fit in the
+    /// frame, to run in the next.
+    pub fn defer(&mut self, left: u64, period: f64) {
+        self.behind += left as f64 * period;
+    }
+
+    /// A frame at `now` ms with nothing to run (paused, say): no lag builds
+    /// up.
+    pub fn idle(&mut self, now: f64) {
+        (self.behind, self.last_frame) = (0.0, Some(now));
+    }
+}
+
+/// Generations for the next batch of a frame: as many as fit in the `left`
+/// ms remaining at the pace of the `done` run in `elapsed` ms so far, or
+/// one with nothing to go on yet; at least one and at most `most`.
+pub fn batch(done: u64, elapsed: f64, left: f64, most: u64) -> u64 {
+    let fit = if done == 0 || elapsed <= 0.0 { 1.0 } else { left * done as f64 / elapsed };
+    (fit as u64).clamp(1, most.max(1))
+}
+
+/// Milliseconds on a monotonic clock, for timing batches: the page's
+/// `performance.now()`, which the system clock changing doesn't move, or
+/// `Date.now()` where there is no `performance`.
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    web_sys::window().and_then(|window| window.performance()).map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+/// Milliseconds on a monotonic clock, for timing batches.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_fill_what_is_left_of_the_frame() {
+        // Nothing timed yet: feel the way with one.
+        assert_eq!(batch(0, 0.0, 16.0, 100), 1);
+        // 10 generations took 2 ms, so 8 ms more fits 40.
+        assert_eq!(batch(10, 2.0, 8.0, 100), 40);
+        assert_eq!(batch(10, 2.0, 8.0, 25), 25);
+        // Out of time still runs one.
+        assert_eq!(batch(10, 20.0, -4.0, 100), 1);
+    }
+
+    #[test]
+    fn generations_come_a_period_apart() {
+        let mut pacer = Pacer::default();
+        // The first frame has nothing to measure from.
+        assert_eq!(pacer.due(0.0, 50.0, false), 0);
+        let due: Vec<u64> = [16.0, 32.0, 48.0, 64.0, 80.0, 96.0].into_iter().map(|now| pacer.due(now, 50.0, false)).collect();
+        assert_eq!(due, [0, 0, 0, 1, 0, 0]);
+        assert_eq!(pacer.due(100.0, 50.0, false), 1);
+    }
+
+    #[test]
+    fn a_dropped_frame_is_made_up_in_the_next() {
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 50.0, false);
+        assert_eq!(pacer.due(100.0, 50.0, false), 2);
+    }
+
+    #[test]
+    fn lag_is_capped_unless_keeping_up() {
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 50.0, false);
+        assert_eq!(pacer.due(10_000.0, 50.0, false), (MAX_LAG_MS / 50.0) as u64);
+        // A period longer than the cap still gets its generation.
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 1000.0, false);
+        assert_eq!(pacer.due(10_000.0, 1000.0, false), 1);
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 50.0, true);
+        assert_eq!(pacer.due(10_000.0, 50.0, true), 200);
+    }
+
+    #[test]
+    fn deferred_generations_carry_into_the_next_frame() {
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 50.0, false);
+        assert_eq!(pacer.due(100.0, 50.0, false), 2);
+        // Only one of the two fitted.
+        pacer.defer(1, 50.0);
+        assert_eq!(pacer.due(116.0, 50.0, false), 1);
+        assert_eq!(pacer.due(132.0, 50.0, false), 0);
+    }
+
+    #[test]
+    fn idling_drops_the_lag() {
+        let mut pacer = Pacer::default();
+        pacer.due(0.0, 50.0, false);
+        pacer.due(40.0, 50.0, false);
+        pacer.idle(5_000.0);
+        assert_eq!(pacer.due(5_030.0, 50.0, false), 0);
+        assert_eq!(pacer.due(5_050.0, 50.0, false), 1);
+    }
+}
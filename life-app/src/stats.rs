@@ -0,0 +1,165 @@
+// Run statistics
+//
+// Population alone hides churn: a board can hold steady at 2 000 cells while
+// hundreds are born and die every generation. `RunStats` keeps running totals
+// plus a short window of recent ticks for rates, and longer series of the
+// activity (fraction of cells that changed) and block entropy for plotting,
+// which show when a board "settles down". It also remembers the state hash
+// of recent generations, which spots a board that has fallen into a cycle
+// exactly, however big it is.
+
+use std::collections::{HashMap, VecDeque};
+
+use life_engine::universe::{TickStats, Universe};
+
+/// How many generations the rolling rates average over.
+const WINDOW: usize = 32;
+/// How many generations the activity/entropy plots keep.
+const HISTORY: usize = 200;
+/// How many generations of state hashes are kept, i.e. the longest cycle found.
+const CYCLE_MEMORY: usize = 1024;
+
+/// The board repeating itself exactly.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cycle {
+    /// First generation of the repeating part.
+    pub since: u64,
+    pub period: u64,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RunStats {
+    pub generation: u64,
+    pub total_births: u64,
+    pub total_deaths: u64,
+    /// Stats of the most recent generation.
+    pub last: TickStats,
+    recent: VecDeque<TickStats>,
+    /// Fraction of cells that changed, one sample per generation.
+    pub activity: VecDeque<f64>,
+    /// Normalised 2×2 block entropy, one sample per generation.
+    pub entropy: VecDeque<f64>,
+    /// The first cycle found, if the board has repeated itself.
+    pub cycle: Option<Cycle>,
+    /// State hashes of the last `CYCLE_MEMORY` generations, oldest first.
+    hashes: VecDeque<u64>,
+    /// Latest generation seen with each hash in `hashes`.
+    seen: HashMap<u64, u64>,
+}
+
+impl RunStats {
+    /// Fold one generation into the counters; `universe` is the board after the tick.
+    pub fn record(&mut self, tick: TickStats, universe: &Universe) {
+        self.generation += 1;
+        self.total_births += tick.births as u64;
+        self.total_deaths += tick.deaths as u64;
+        self.last = tick;
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(tick);
+
+        let changed = (tick.births + tick.deaths) as f64 / universe.cells.len().max(1) as f64;
+        push_capped(&mut self.activity, changed);
+        push_capped(&mut self.entropy, universe.block_entropy());
+
+        let hash = universe.state_hash();
+        if let (None, Some(&since)) = (self.cycle, self.seen.get(&hash)) {
+            self.cycle = Some(Cycle { since, period: self.generation - since });
+        }
+        if self.hashes.len() == CYCLE_MEMORY {
+            let oldest = self.hashes.pop_front().expect("hashes is full");
+            let oldest_generation = self.generation - CYCLE_MEMORY as u64;
+            if self.seen.get(&oldest) == Some(&oldest_generation) {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.hashes.push_back(hash);
+        self.seen.insert(hash, self.generation);
+    }
+
+    /// Take back the latest generation: `tick` is what it reported and
+    /// `previous` the stats of the generation before it.
+    pub fn unrecord(&mut self, tick: TickStats, previous: TickStats) {
+        self.generation = self.generation.saturating_sub(1);
+        self.total_births -= tick.births as u64;
+        self.total_deaths -= tick.deaths as u64;
+        self.last = previous;
+        self.recent.pop_back();
+        self.activity.pop_back();
+        self.entropy.pop_back();
+        // Earlier sightings of the hash are forgotten too; only cycles are
+        // lost by stepping back, never invented.
+        if let Some(hash) = self.hashes.pop_back() {
+            self.seen.remove(&hash);
+        }
+        if self.cycle.is_some_and(|cycle| cycle.since + cycle.period > self.generation) {
+            self.cycle = None;
+        }
+    }
+
+    /// Fold in `n` generations run somewhere the board can't be looked at
+    /// (the GPU), given their totals. Rates count them at their average;
+    /// the plots skip them and the cycle search starts over.
+    pub fn skip(&mut self, n: u64, totals: TickStats) {
+        if n == 0 {
+            return;
+        }
+        self.generation += n;
+        self.total_births += totals.births as u64;
+        self.total_deaths += totals.deaths as u64;
+        self.last = per_generation(n, totals);
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(self.last);
+        self.hashes.clear();
+        self.seen.clear();
+    }
+
+    /// Take back `skip(n, totals)`; `previous` is the generation before's
+    /// stats. The cycle search starts over again.
+    pub fn unskip(&mut self, n: u64, totals: TickStats, previous: TickStats) {
+        self.generation = self.generation.saturating_sub(n);
+        self.total_births -= totals.births as u64;
+        self.total_deaths -= totals.deaths as u64;
+        self.last = previous;
+        self.recent.pop_back();
+        self.hashes.clear();
+        self.seen.clear();
+        if self.cycle.is_some_and(|cycle| cycle.since + cycle.period > self.generation) {
+            self.cycle = None;
+        }
+    }
+
+    /// Average births per generation over the recent window.
+    pub fn birth_rate(&self) -> f64 {
+        self.average(|tick| tick.births)
+    }
+
+    /// Average deaths per generation over the recent window.
+    pub fn death_rate(&self) -> f64 {
+        self.average(|tick| tick.deaths)
+    }
+
+    fn average(&self, field: impl Fn(&TickStats) -> usize) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        self.recent.iter().map(field).sum::<usize>() as f64 / self.recent.len() as f64
+    }
+}
+
+fn push_capped(series: &mut VecDeque<f64>, value: f64) {
+    if series.len() == HISTORY {
+        series.pop_front();
+    }
+    series.push_back(value);
+}
+
+/// `totals` of `n` generations as the stats of one, births and deaths at
+/// their average.
+pub fn per_generation(n: u64, totals: TickStats) -> TickStats {
+    let average = |count: usize| (count as u64 / n.max(1)) as usize;
+    TickStats { births: average(totals.births), deaths: average(totals.deaths), ..totals }
+}
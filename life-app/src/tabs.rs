@@ -0,0 +1,101 @@
+// Workspace tabs
+//
+// Several independent sessions on one page. Each tab is its own `GameOfLife`
+// (or another automaton, see `modes`) with its own board, settings and
+// autosave slot, so a work-in-progress pattern can sit in one tab while
+// another is used for experiments. The tab list is saved too, and a reload
+// brings every tab back. Hidden tabs stay mounted and keep running; only the
+// UI language and the keyboard shortcuts are shared.
+
+use dioxus::prelude::*;
+use life_engine::rule::Rule;
+use serde::{Deserialize, Serialize};
+
+use crate::autosave;
+use crate::i18n::Language;
+use crate::keys::Keymap;
+use crate::modes::Modes;
+
+/// The open tabs, by autosave slot, in display order.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TabList {
+    pub slots: Vec<u64>,
+    pub active: u64,
+}
+
+impl Default for TabList {
+    fn default() -> Self {
+        Self { slots: vec![0], active: 0 }
+    }
+}
+
+impl TabList {
+    /// Add a tab with a fresh slot after the others and switch to it.
+    pub fn open(&mut self) {
+        let slot = self.slots.iter().max().map_or(0, |max| max + 1);
+        self.slots.push(slot);
+        self.active = slot;
+    }
+
+    /// Remove `slot`'s tab, switching to its neighbour if it was active. The
+    /// last tab never closes.
+    pub fn close(&mut self, slot: u64) -> bool {
+        let Some(position) = self.slots.iter().position(|&open| open == slot) else { return false };
+        if self.slots.len() == 1 {
+            return false;
+        }
+        self.slots.remove(position);
+        if self.active == slot {
+            self.active = self.slots[position.min(self.slots.len() - 1)];
+        }
+        true
+    }
+}
+
+/// A tab bar over one board per tab. New tabs start from these props.
+#[component]
+pub fn Workspace(width: usize, height: usize, rule: Rule, cell_size: usize, autoplay: bool) -> Element {
+    let language = use_context_provider(|| Signal::new(Language::detect()));
+    use_context_provider(|| Signal::new(Keymap::load()));
+    let mut tabs = use_signal(|| autosave::load_tabs().unwrap_or_default());
+    use_effect(move || autosave::save_tabs(&tabs.read()));
+
+    let text = language().strings();
+    let list = tabs.read().clone();
+
+    rsx! {
+        nav { class: "tab-bar", role: "tablist",
+            for (position, slot) in list.slots.iter().copied().enumerate() {
+                div { key: "{slot}", class: if slot == list.active { "tab active" } else { "tab" },
+                    button {
+                        role: "tab",
+                        aria_selected: slot == list.active,
+                        onclick: move |_| tabs.write().active = slot,
+                        {(text.tab_label)(position + 1)}
+                    }
+                    if list.slots.len() > 1 {
+                        button {
+                            aria_label: text.close_tab,
+                            onclick: move |_| {
+                                if tabs.write().close(slot) {
+                                    autosave::clear(slot);
+                                }
+                            },
+                            "×"
+                        }
+                    }
+                }
+            }
+            button {
+                aria_label: text.new_tab,
+                onclick: move |_| tabs.write().open(),
+                "+"
+            }
+        }
+        for slot in list.slots.iter().copied() {
+            div { key: "{slot}", role: "tabpanel", hidden: slot != list.active,
+                Modes { width, height, rule, cell_size, autoplay, slot }
+            }
+        }
+    }
+}
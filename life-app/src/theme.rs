@@ -0,0 +1,79 @@
+// Theme
+//
+// Every colour and size the UI draws with is a CSS custom property. The
+// defaults are emitted from here as a `:where(:root)` rule, which has zero
+// specificity, so any stylesheet an embedder loads can override them, e.g.
+//
+//     :root { --gol-cell-alive: #ffd400; --gol-cell-dead: #101010; }
+//
+// `main.css` and the inline styles built in Rust only ever refer to the
+// properties, never to literal colours.
+
+/// One themeable property.
+pub struct Property {
+    /// Name without the leading `--`.
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+const fn property(name: &'static str, default: &'static str, description: &'static str) -> Property {
+    Property { name, default, description }
+}
+
+/// All themeable properties, in the order the README documents them.
+pub const PROPERTIES: &[Property] = &[
+    // Page
+    property("gol-background", "#000000", "Page background"),
+    property("gol-text", "#ffffff", "Text colour"),
+    property("gol-font", "'Segoe UI', Tahoma, Geneva, Verdana, sans-serif", "Font family"),
+    property("gol-error", "#ff6b6b", "Error messages"),
+    property("gol-spacing", "20px", "Space between sections"),
+    property("gol-gap", "10px", "Space between controls in a row"),
+    property("gol-radius", "4px", "Corner radius of buttons and banners"),
+    // Controls
+    property("gol-accent", "rgba(66, 236, 87, 0.7)", "Buttons and highlights"),
+    property("gol-accent-hover", "#45a049", "Buttons under the pointer"),
+    property("gol-button-text", "#ffffff", "Button labels"),
+    property("gol-button-padding", "8px 12px", "Button padding"),
+    property("gol-panel", "#111111", "Background of charts and text areas"),
+    property("gol-panel-border", "#333333", "Border of charts"),
+    property("gol-chart-line", "rgba(66, 236, 87, 0.9)", "Sparkline stroke"),
+    // Board
+    property("gol-cell-alive", "#ffffff", "Live cells"),
+    property("gol-cell-dead", "#000000", "Dead cells"),
+    property("gol-board-border", "#cccccc", "Border around boards and the minimap"),
+    property("gol-board-background", "#f0f0f0", "Shows through between square cells"),
+    property("gol-gap-color", "#222222", "Shows through cell gaps"),
+    property("gol-grid-line", "rgba(128, 128, 128, 0.6)", "Grid lines"),
+    property("gol-selected-alive", "#9cc3ff", "Live cells inside the selection"),
+    property("gol-selected-dead", "#1a2a4a", "Dead cells inside the selection"),
+    property("gol-sequencer-alive", "rgba(66, 236, 87, 1)", "Live cells on the sequencer row"),
+    property("gol-sequencer-dead", "#1f3a24", "Dead cells on the sequencer row"),
+    property("gol-cursor", "#ff4fd8", "Outline of the keyboard cursor"),
+    property("gol-paste-live", "#ffcc00", "Live cells of a pattern being pasted"),
+    property("gol-paste-dead", "#3a3000", "Dead cells of a pattern being pasted"),
+    property("gol-trail", "hsl(200, 90%, 50%)", "Cells that just died; older trail cells mix towards dead"),
+    // Overlays
+    property("gol-minimap-cells", "#cccccc", "Occupied blocks on the minimap"),
+    property("gol-minimap-view", "rgba(66, 236, 87, 1)", "Viewport outline on the minimap"),
+    property("gol-race-left", "rgb(80, 200, 255)", "Left territory in a race"),
+    property("gol-race-right", "rgb(255, 160, 60)", "Right territory in a race"),
+    property("gol-diff-here", "rgb(66, 236, 87)", "Alive only on this board"),
+    property("gol-diff-there", "rgb(236, 66, 200)", "Alive only on the reference board"),
+    property("gol-track", "#ff4fd8", "Tracked spaceships"),
+    property("gol-track-followed", "#ffcc00", "The spaceship the camera follows"),
+    property("gol-annotation", "#7df9ff", "Annotation labels and arrows"),
+    property("gol-guide", "#ffd166", "Alignment guides while placing a pattern"),
+];
+
+/// The default value of every property, as a stylesheet. Each line carries
+/// its description, so the list can be read straight from devtools.
+pub fn stylesheet() -> String {
+    let mut css = String::from(":where(:root) {\n");
+    for property in PROPERTIES {
+        css.push_str(&format!("    --{}: {}; /* {} */\n", property.name, property.default, property.description));
+    }
+    css.push_str("}\n");
+    css
+}
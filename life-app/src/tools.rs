@@ -0,0 +1,223 @@
+// Editing tools and the rectangular selection
+
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use life_engine::pattern::Pattern;
+use life_engine::universe::{CellState, Universe};
+
+use crate::i18n::{Language, Strings};
+use crate::keys::{Command, Keymap};
+use crate::measure::{self, Ruler, Stopwatch};
+use crate::predecessor::{self, Outcome, MAX_EDGE};
+
+/// What a mouse drag on the board does.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Tool {
+    /// Paint cells: the first cell touched is toggled, the rest of the drag
+    /// copies that new state.
+    #[default]
+    Draw,
+    /// Drag out a rectangular selection.
+    Select,
+    /// Pin a label where the button goes down; dragging gives it an arrow
+    /// to where the button comes up.
+    Annotate,
+    /// Drag between two cells to measure how far apart they are.
+    Measure,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 4] = [Tool::Draw, Tool::Select, Tool::Annotate, Tool::Measure];
+
+    pub fn label(self, text: &Strings) -> &'static str {
+        match self {
+            Tool::Draw => text.draw,
+            Tool::Select => text.select,
+            Tool::Annotate => text.annotate,
+            Tool::Measure => text.measure,
+        }
+    }
+}
+
+/// Inclusive cell rectangle in universe coordinates.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Selection {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl Selection {
+    /// Rectangle spanned by two opposite corners, in any order.
+    pub fn spanning((row_a, col_a): (usize, usize), (row_b, col_b): (usize, usize)) -> Self {
+        Self {
+            top: row_a.min(row_b),
+            left: col_a.min(col_b),
+            bottom: row_a.max(row_b),
+            right: col_a.max(col_b),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.bottom - self.top + 1
+    }
+
+    pub fn cols(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+}
+
+/// The tool picker and what goes with the tool in hand, the keyboard cursor,
+/// and the selection with its predecessor search. The search runs here, a
+/// slice at a time; writing a found predecessor back is an edit, so it goes
+/// back to the board through `on_apply_predecessor`, as copying and the
+/// cursor do.
+#[component]
+pub fn ToolsPanel(
+    mut tool: Signal<Tool>,
+    mut note_text: Signal<String>,
+    ruler: Signal<Option<Ruler>>,
+    mut stopwatch: Signal<Stopwatch>,
+    generation: u64,
+    universe: Signal<Universe>,
+    cursor: Option<(usize, usize)>,
+    mut selection: Signal<Option<Selection>>,
+    mut predecessor: Signal<Option<(Selection, Outcome)>>,
+    on_toggle_cursor: EventHandler,
+    on_copy: EventHandler,
+    on_apply_predecessor: EventHandler,
+) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let keymap = use_context::<Signal<Keymap>>();
+    let hint = move |command| keymap.read().hint(command);
+    // A predecessor search under way, and how far through its budget.
+    let mut searching = use_signal(|| None::<(Task, f64)>);
+
+    let find_predecessor = move |_| {
+        let Some(area) = selection() else { return };
+        let board = universe.peek();
+        let target = Pattern::from_region(&board, area.top, area.left, area.rows(), area.cols());
+        let mut search = predecessor::Search::new(target.cells, area.rows(), area.cols(), board.rule);
+        drop(board);
+        predecessor.set(None);
+        let task = spawn(async move {
+            loop {
+                if let Some(outcome) = search.run(predecessor::SLICE) {
+                    predecessor.set(Some((area, outcome)));
+                    break;
+                }
+                if let Some((_, progress)) = searching.write().as_mut() {
+                    *progress = search.progress();
+                }
+                dioxus_time::sleep(Duration::ZERO).await;
+            }
+            searching.set(None);
+        });
+        searching.set(Some((task, 0.0)));
+    };
+
+    let cancel_search = move |_| {
+        if let Some((task, _)) = searching.take() {
+            task.cancel();
+        }
+    };
+
+    rsx! {
+        div { class: "controls",
+            for option in Tool::ALL {
+                label {
+                    input {
+                        r#type: "radio",
+                        name: "tool",
+                        checked: tool() == option,
+                        onchange: move |_| tool.set(option),
+                    }
+                    " {option.label(text)}"
+                }
+            }
+            if tool() == Tool::Annotate {
+                input {
+                    r#type: "text",
+                    aria_label: text.annotation_text,
+                    placeholder: text.annotation_text,
+                    value: "{note_text}",
+                    oninput: move |evt| note_text.set(evt.value()),
+                }
+            }
+            if tool() == Tool::Measure {
+                if let Some(ruler) = ruler() {
+                    span { role: "status",
+                        {
+                            let (dx, dy) = ruler.offset();
+                            (text.ruler_readout)(dx, dy, ruler.distance(), ruler.steps())
+                        }
+                    }
+                }
+                button {
+                    onclick: move |_| stopwatch.write().mark(generation),
+                    "{text.stopwatch_mark}"
+                }
+                if let Some(elapsed) = stopwatch.read().elapsed(generation) {
+                    span { role: "status",
+                        if stopwatch.read().is_stopped() {
+                            {(text.stopwatch_between)(elapsed)}
+                        } else {
+                            {(text.stopwatch_since)(elapsed)}
+                        }
+                        if let Some(ruler) = ruler().filter(|ruler| ruler.steps() > 0 && elapsed > 0) {
+                            " · "
+                            {(text.speed_readout)(&measure::speed(ruler.steps(), elapsed))}
+                        }
+                    }
+                }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: cursor.is_some(),
+                    onchange: move |_| on_toggle_cursor.call(()),
+                }
+                " {text.keyboard_cursor}{hint(Command::KeyboardCursor)}"
+            }
+            if let Some((row, col)) = cursor {
+                span { role: "status",
+                    {(text.cursor_position)(row + 1, col + 1, universe.read().get(row, col).is_ok_and(CellState::is_alive))}
+                }
+            }
+            if let Some(area) = selection() {
+                span { {(text.selection)(area.cols(), area.rows())} }
+                button {
+                    onclick: find_predecessor,
+                    disabled: area.rows() > MAX_EDGE || area.cols() > MAX_EDGE || searching.read().is_some(),
+                    title: (text.predecessor_limit)(MAX_EDGE),
+                    "{text.find_predecessor}"
+                }
+                button { onclick: move |_| on_copy.call(()), "{text.copy_rle}{hint(Command::Copy)}" }
+                button { onclick: move |_| selection.set(None), "{text.clear_selection}" }
+            }
+            if let Some((_, progress)) = searching() {
+                progress { value: progress, max: 1.0, aria_label: text.searching_predecessor }
+                button { onclick: cancel_search, "{text.cancel_search}" }
+            }
+            match predecessor() {
+                Some((_, Outcome::Found(_))) => rsx! {
+                    span { "{text.predecessor_found}" }
+                    button { onclick: move |_| on_apply_predecessor.call(()), "{text.apply_predecessor}" }
+                },
+                Some((_, Outcome::Orphan)) => rsx! {
+                    span { "{text.garden_of_eden}" }
+                },
+                Some((_, Outcome::GaveUp)) => rsx! {
+                    span { "{text.predecessor_gave_up}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// Cell tooltip
+//
+// Optional readout that follows the pointer over the board with the hovered
+// cell's coordinates, state and live neighbour count, from
+// `Universe::inspect`. Handy when debugging hand-drawn patterns.
+
+use dioxus::prelude::*;
+use life_engine::universe::CellInfo;
+
+use crate::i18n::Language;
+
+/// Offset of the tooltip from the pointer, in px, so it doesn't hide the cell.
+const OFFSET: f64 = 12.0;
+
+/// `info` next to page position `at`.
+#[component]
+pub fn CellTooltip(info: CellInfo, at: (f64, f64)) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let (x, y) = (at.0 + OFFSET, at.1 + OFFSET);
+    rsx! {
+        div { class: "tooltip", role: "tooltip", style: "left: {x}px; top: {y}px;",
+            {(text.cell_readout)(info.row, info.col, info.state.is_alive(), info.live_neighbors)}
+        }
+    }
+}
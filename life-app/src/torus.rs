@@ -0,0 +1,129 @@
+// Torus view
+//
+// The board wraps at its edges, which makes it the surface of a torus. This
+// view shows it as one: columns run around the ring, rows around the tube,
+// and the live cells are dotted onto the slowly turning surface. It is a
+// software projection, so it needs nothing from the GPU: the wireframe is
+// SVG, and the dots are drawn into one image each frame, a mask over the
+// theme's live colour, so a busy board costs pixels rather than elements.
+
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_time::use_interval;
+use life_engine::thumbnail::Thumbnail;
+use life_engine::universe::Universe;
+use life_formats::png;
+
+use crate::i18n::Language;
+
+/// Ring and tube radius.
+const RING: f64 = 1.0;
+const TUBE: f64 = 0.45;
+/// Tilt towards the viewer, in radians.
+const TILT: f64 = 1.0;
+/// Viewer distance, for the perspective.
+const DISTANCE: f64 = 4.0;
+/// Spin per frame, in radians, and frame interval.
+const SPIN: f64 = 0.02;
+const FRAME_MS: u64 = 50;
+/// Wireframe rings drawn round the ring and round the tube.
+const MERIDIANS: usize = 24;
+const PARALLELS: usize = 12;
+/// Half the side of the view, in surface units, and of the dots image in
+/// pixels.
+const VIEW: f64 = 1.7;
+const IMAGE: usize = 320;
+
+/// Screen position and depth (larger is nearer) of the surface point at ring
+/// angle `u` and tube angle `v`, with the torus spun by `spin`.
+fn project(u: f64, v: f64, spin: f64) -> (f64, f64, f64) {
+    let u = u + spin;
+    let (x, y, z) = ((RING + TUBE * v.cos()) * u.cos(), (RING + TUBE * v.cos()) * u.sin(), TUBE * v.sin());
+    // Tip the torus about the x axis.
+    let (y, z) = (y * TILT.cos() - z * TILT.sin(), y * TILT.sin() + z * TILT.cos());
+    let scale = DISTANCE / (DISTANCE - z);
+    (x * scale, y * scale, z)
+}
+
+/// SVG `points` for the surface curve traced by `point` over `0..=steps`.
+fn curve(steps: usize, point: impl Fn(f64) -> (f64, f64, f64)) -> String {
+    (0..=steps)
+        .map(|i| {
+            let (x, y, _) = point(i as f64 / steps as f64 * TAU);
+            format!("{x:.3},{y:.3}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The live cells of `universe` as dots on the torus spun by `spin`, far
+/// ones dimmed, as shades of an `IMAGE`-pixel square.
+fn dots(universe: &Universe, spin: f64) -> Thumbnail {
+    let (width, height) = (universe.width, universe.height);
+    let radius = (TAU * TUBE / height as f64).min(TAU * (RING - TUBE) / width as f64) * 0.6;
+    let pixels = IMAGE as f64 / (2.0 * VIEW);
+    // Far dots first, so near ones cover them.
+    let mut dots: Vec<(f64, f64, f64)> = (0..width * height)
+        .filter(|&idx| universe.cells[idx])
+        .map(|idx| project((idx % width) as f64 / width as f64 * TAU, (idx / width) as f64 / height as f64 * TAU, spin))
+        .collect();
+    dots.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let mut shades = vec![0; IMAGE * IMAGE];
+    for (x, y, z) in dots {
+        let (cx, cy) = ((x + VIEW) * pixels, (y + VIEW) * pixels);
+        // However small the cells, each dot covers a pixel.
+        let r = (radius * DISTANCE / (DISTANCE - z) * pixels).max(0.75);
+        // Dots on the far side show through, dimmed.
+        let shade = ((0.35 + 0.65 * (z + TUBE + RING) / (2.0 * (TUBE + RING))) * 255.0) as u8;
+        let span = |centre: f64| (centre - r).floor().max(0.0) as usize..((centre + r).ceil().max(0.0) as usize).min(IMAGE);
+        for py in span(cy) {
+            for px in span(cx) {
+                let (dx, dy) = (px as f64 + 0.5 - cx, py as f64 + 0.5 - cy);
+                if dx * dx + dy * dy <= r * r {
+                    shades[py * IMAGE + px] = shade;
+                }
+            }
+        }
+    }
+    Thumbnail { width: IMAGE, height: IMAGE, shades }
+}
+
+/// `universe` wrapped onto a turning torus.
+#[component]
+pub fn TorusView(universe: Universe) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut spin = use_signal(|| 0.0);
+    use_interval(Duration::from_millis(FRAME_MS), move |_| {
+        let next = (*spin.peek() + SPIN) % TAU;
+        spin.set(next);
+    });
+
+    let angle = spin();
+    let mask = png::mask_url(&dots(&universe, angle));
+
+    rsx! {
+        figure { class: "torus",
+            div { class: "torus-surface", role: "img", "aria-label": text.torus_view,
+                svg { view_box: "{-VIEW} {-VIEW} {2.0 * VIEW} {2.0 * VIEW}",
+                    g { class: "torus-wireframe",
+                        for meridian in 0..MERIDIANS {
+                            polyline {
+                                points: curve(PARALLELS * 2, |v| project(meridian as f64 / MERIDIANS as f64 * TAU, v, angle)),
+                            }
+                        }
+                        for parallel in 0..PARALLELS {
+                            polyline {
+                                points: curve(MERIDIANS * 2, |u| project(u, parallel as f64 / PARALLELS as f64 * TAU, angle)),
+                            }
+                        }
+                    }
+                }
+                div { class: "torus-dots", style: "mask-image: url({mask}); -webkit-mask-image: url({mask})" }
+            }
+            figcaption { "{text.torus_view}" }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Spaceship tracker
+//
+// Every few generations the board is scanned for spaceships. Each sighting is
+// matched to an existing track when it is the same kind of ship close to
+// where that track was predicted to be; unmatched sightings start new tracks
+// and tracks that vanish (collisions) are dropped. Between scans positions
+// are extrapolated from the ship's known velocity, so boxes glide smoothly.
+
+use dioxus::prelude::*;
+use life_engine::universe::Universe;
+
+use crate::census::{self, Kind, Label};
+use crate::i18n::Language;
+use crate::viewport::Viewport;
+
+/// Generations between full scans.
+pub const SCAN_EVERY: u64 = 8;
+/// How far (cells) a sighting may be from the prediction and still match.
+const MATCH_RADIUS: f64 = 4.0;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Track {
+    pub id: u32,
+    pub label: Label,
+    pub kind: Kind,
+    /// Bounding-box size `(rows, cols)`.
+    pub size: (i32, i32),
+    /// Top-left at the last sighting, and when that was.
+    anchor: (f64, f64),
+    anchor_generation: u64,
+    /// Unwrapped distance travelled since the first sighting, and when that was.
+    travelled: (f64, f64),
+    first_generation: u64,
+}
+
+impl Track {
+    /// Theoretical velocity in cells per generation, `(rows, cols)`.
+    pub fn velocity(&self) -> (f64, f64) {
+        match self.kind {
+            Kind::Spaceship { period, d_row, d_col } => (d_row as f64 / period as f64, d_col as f64 / period as f64),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Velocity actually observed between sightings, if it has moved yet.
+    pub fn measured_velocity(&self) -> Option<(f64, f64)> {
+        let elapsed = self.anchor_generation.checked_sub(self.first_generation).filter(|&g| g > 0)? as f64;
+        Some((self.travelled.0 / elapsed, self.travelled.1 / elapsed))
+    }
+
+    /// Predicted top-left at `generation`, not yet wrapped to the board.
+    pub fn position(&self, generation: u64) -> (f64, f64) {
+        let elapsed = generation.saturating_sub(self.anchor_generation) as f64;
+        let (v_row, v_col) = self.velocity();
+        (self.anchor.0 + v_row * elapsed, self.anchor.1 + v_col * elapsed)
+    }
+
+    /// Predicted centre at `generation`, wrapped onto a `width`×`height` board.
+    pub fn center(&self, generation: u64, width: usize, height: usize) -> (f64, f64) {
+        let (row, col) = self.position(generation);
+        let row = (row + self.size.0 as f64 / 2.0).rem_euclid(height as f64);
+        let col = (col + self.size.1 as f64 / 2.0).rem_euclid(width as f64);
+        (row, col)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Tracker {
+    pub tracks: Vec<Track>,
+    next_id: u32,
+    last_scan: Option<u64>,
+}
+
+impl Tracker {
+    /// Rescan if `SCAN_EVERY` generations have passed since the last scan.
+    pub fn observe(&mut self, universe: &Universe, generation: u64) {
+        if self.last_scan.is_some_and(|last| generation < last + SCAN_EVERY) {
+            return;
+        }
+        self.last_scan = Some(generation);
+        let (height, width) = (universe.height as f64, universe.width as f64);
+        // Shortest signed distance between two coordinates on a ring.
+        let ring = |delta: f64, size: f64| delta - size * (delta / size).round();
+
+        let mut previous = std::mem::take(&mut self.tracks);
+        for ship in census::spaceships(universe) {
+            let (top, left, bottom, right) = ship.bounds();
+            let anchor = (top as f64, left as f64);
+            let label = ship.label();
+
+            let matched = previous.iter().position(|track| {
+                let (row, col) = track.position(generation);
+                track.label == label
+                    && ring(anchor.0 - row, height).hypot(ring(anchor.1 - col, width)) <= MATCH_RADIUS
+            });
+            let track = match matched {
+                Some(index) => {
+                    let mut track = previous.swap_remove(index);
+                    track.travelled.0 += ring(anchor.0 - track.anchor.0, height);
+                    track.travelled.1 += ring(anchor.1 - track.anchor.1, width);
+                    track.anchor = anchor;
+                    track.anchor_generation = generation;
+                    track.kind = ship.kind;
+                    track
+                }
+                None => {
+                    self.next_id += 1;
+                    Track {
+                        id: self.next_id,
+                        label,
+                        kind: ship.kind,
+                        size: (bottom - top + 1, right - left + 1),
+                        anchor,
+                        anchor_generation: generation,
+                        travelled: (0.0, 0.0),
+                        first_generation: generation,
+                    }
+                }
+            };
+            self.tracks.push(track);
+        }
+        self.tracks.sort_by_key(|track| track.id);
+    }
+}
+
+/// Generations of travel drawn as each ship's velocity vector.
+const VECTOR_GENERATIONS: f64 = 16.0;
+
+/// Tracking boxes and velocity vectors, laid over the board's viewport.
+#[component]
+pub fn TrackingLayer(
+    tracks: Vec<Track>,
+    generation: u64,
+    viewport: Viewport,
+    width: usize,
+    height: usize,
+    cell_size: usize,
+    followed: Option<u32>,
+) -> Element {
+    let scale = cell_size as f64;
+    rsx! {
+        svg {
+            class: "tracking",
+            "aria-hidden": "true",
+            width: viewport.cols * cell_size,
+            height: viewport.rows * cell_size,
+            for track in tracks {
+                {
+                    let (row, col) = track.center(generation, width, height);
+                    // Position relative to the view's corner, wrapping round the torus.
+                    let row = (row - viewport.row as f64).rem_euclid(height as f64);
+                    let col = (col - viewport.col as f64).rem_euclid(width as f64);
+                    let visible = row < viewport.rows as f64 && col < viewport.cols as f64;
+                    let (v_row, v_col) = track.velocity();
+                    let (x, y) = (col * scale, row * scale);
+                    let (box_w, box_h) = ((track.size.1 as f64 + 2.0) * scale, (track.size.0 as f64 + 2.0) * scale);
+                    // Set as a style: SVG presentation attributes can't take `var()`.
+                    let stroke = if followed == Some(track.id) { "stroke: var(--gol-track-followed)" } else { "stroke: var(--gol-track)" };
+                    visible.then(|| rsx! {
+                        g { key: "{track.id}",
+                            rect {
+                                x: x - box_w / 2.0,
+                                y: y - box_h / 2.0,
+                                width: box_w,
+                                height: box_h,
+                                fill: "none",
+                                style: stroke,
+                            }
+                            line {
+                                x1: x,
+                                y1: y,
+                                x2: x + v_col * VECTOR_GENERATIONS * scale,
+                                y2: y + v_row * VECTOR_GENERATIONS * scale,
+                                style: stroke,
+                                stroke_width: 2,
+                            }
+                        }
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// The tracker's switch and its list of tracks, each with a button to make
+/// the camera follow it, or stop.
+#[component]
+pub fn TrackerPanel(mut tracker: Signal<Option<Tracker>>, mut followed: Signal<Option<u32>>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    rsx! {
+        div { class: "controls",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: tracker.read().is_some(),
+                    onchange: move |evt| {
+                        tracker.set(evt.checked().then(Tracker::default));
+                        followed.set(None);
+                    },
+                }
+                " {text.track_spaceships}"
+            }
+            if let Some(tracker) = tracker() {
+                ul { class: "census",
+                    for track in tracker.tracks {
+                        li {
+                            "#{track.id} {track.label.text(text)}"
+                            if let Some(speed) = track.kind.speed(text) {
+                                " · {speed}"
+                            }
+                            if let Some((v_row, v_col)) = track.measured_velocity() {
+                                " · {(text.measured_velocity)(v_col, v_row)}"
+                            }
+                            " "
+                            if followed() == Some(track.id) {
+                                button { onclick: move |_| followed.set(None), "{text.unfollow}" }
+                            } else {
+                                button { onclick: move |_| followed.set(Some(track.id)), "{text.follow}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
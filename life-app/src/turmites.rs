@@ -0,0 +1,110 @@
+// Turmite editor
+//
+// Settings for the turmites mode: an editable transition table per program,
+// each with presets to start from, and which program the turmites placed by
+// clicking the board will run.
+
+use dioxus::prelude::*;
+use life_engine::automata::turmite::{Program, PRESETS};
+use life_engine::automata::World;
+
+use crate::i18n::Language;
+
+/// The program editors for the turmites in `world`.
+#[component]
+pub fn TurmiteSettings(world: Signal<World>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let World::Turmites(turmites) = &*world.read() else { return rsx! {} };
+    let programs: Vec<String> = turmites.programs.iter().map(Program::to_text).collect();
+    let (brush, count) = (turmites.brush, turmites.turmites.len());
+
+    rsx! {
+        div { class: "turmite-programs",
+            for (index, program) in programs.iter().cloned().enumerate() {
+                ProgramEditor {
+                    key: "{index}",
+                    index,
+                    program,
+                    on_change: move |program| {
+                        if let World::Turmites(turmites) = &mut *world.write() {
+                            turmites.set_program(index, program);
+                        }
+                    },
+                }
+            }
+            div { class: "controls",
+                button {
+                    onclick: move |_| {
+                        if let World::Turmites(turmites) = &mut *world.write() {
+                            turmites.programs.push(Program::langtons_ant());
+                        }
+                    },
+                    "{text.add_program}"
+                }
+                label {
+                    "{text.place_with} "
+                    select {
+                        onchange: move |evt| {
+                            if let (Ok(index), World::Turmites(turmites)) = (evt.value().parse(), &mut *world.write()) {
+                                turmites.brush = index;
+                            }
+                        },
+                        for index in 0..programs.len() {
+                            option { value: "{index}", selected: index == brush, {(text.program_label)(index + 1)} }
+                        }
+                    }
+                }
+                span { {(text.turmite_count)(count)} }
+            }
+        }
+    }
+}
+
+/// One program as editable text, applied when it parses.
+#[component]
+fn ProgramEditor(index: usize, program: String, on_change: EventHandler<Program>) -> Element {
+    let text = use_context::<Signal<Language>>()().strings();
+    let mut draft = use_signal(|| program.clone());
+    let mut error = use_signal(|| None::<String>);
+
+    // Follow changes made from elsewhere, such as a preset.
+    use_effect(use_reactive!(|program| {
+        draft.set(program);
+        error.set(None);
+    }));
+
+    rsx! {
+        fieldset { class: "turmite-program",
+            legend { {(text.program_label)(index + 1)} }
+            select {
+                aria_label: text.turmite_preset,
+                onchange: move |evt| {
+                    if let Some(program) = PRESETS.iter().find(|(name, _)| *name == evt.value()).and_then(|(_, table)| Program::parse(table).ok()) {
+                        on_change.call(program);
+                    }
+                },
+                option { value: "", selected: true, disabled: true, "{text.turmite_preset}" }
+                for (name, _) in PRESETS {
+                    option { value: name, "{name}" }
+                }
+            }
+            textarea {
+                aria_label: text.transition_table,
+                rows: 2,
+                spellcheck: false,
+                value: "{draft}",
+                oninput: move |evt| draft.set(evt.value()),
+                onchange: move |_| match Program::parse(&draft.read()) {
+                    Ok(program) => {
+                        error.set(None);
+                        on_change.call(program);
+                    }
+                    Err(message) => error.set(Some(message)),
+                },
+            }
+            if let Some(message) = error() {
+                span { class: "error", role: "alert", "{message}" }
+            }
+        }
+    }
+}
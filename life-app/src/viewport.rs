@@ -0,0 +1,44 @@
+// Viewport
+//
+// Boards can be larger than what fits on screen; the viewport is the window
+// of cells actually rendered. It never wraps – it is clamped so it always sits
+// fully inside the universe.
+
+/// Window of `rows × cols` cells whose top-left corner is `(row, col)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Viewport {
+    pub row: usize,
+    pub col: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Viewport {
+    /// A viewport of at most `rows × cols` in the top-left of a `width × height` board.
+    pub fn new(rows: usize, cols: usize, width: usize, height: usize) -> Self {
+        Self { row: 0, col: 0, rows: rows.min(height), cols: cols.min(width) }
+    }
+
+    /// True when the whole board is visible and panning is pointless.
+    pub fn covers(&self, width: usize, height: usize) -> bool {
+        self.rows >= height && self.cols >= width
+    }
+
+    /// Move by `(d_row, d_col)` cells, stopping at the board edges.
+    pub fn pan(&mut self, d_row: isize, d_col: isize, width: usize, height: usize) {
+        self.row = self.row.saturating_add_signed(d_row).min(height - self.rows);
+        self.col = self.col.saturating_add_signed(d_col).min(width - self.cols);
+    }
+
+    /// Put `(row, col)` as close to the centre as the board edges allow.
+    pub fn center_on(&mut self, row: usize, col: usize, width: usize, height: usize) {
+        self.row = row.saturating_sub(self.rows / 2).min(height - self.rows);
+        self.col = col.saturating_sub(self.cols / 2).min(width - self.cols);
+    }
+
+    /// Scroll just far enough for `(row, col)` to be visible.
+    pub fn reveal(&mut self, row: usize, col: usize) {
+        self.row = self.row.min(row).max((row + 1).saturating_sub(self.rows));
+        self.col = self.col.min(col).max((col + 1).saturating_sub(self.cols));
+    }
+}
@@ -0,0 +1,221 @@
+// Other cellular automata
+//
+// Life is what the app is about, but the same grid, renderer and controls can
+// run other automata too. Each one implements `Automaton`: a grid of small
+// integer states that steps forward and can be poked at. `Mode` is the
+// registry of those on offer and `World` holds whichever one is running;
+// adding an automaton means a module here, a `Mode` and a `World` variant.
+// Automata list their tunable settings as `ParamSpec`s, and the app builds
+// their sliders, number boxes and checkboxes from those.
+
+pub mod elementary;
+pub mod epidemic;
+pub mod forest_fire;
+pub mod ising;
+pub mod sand;
+pub mod sandpile;
+pub mod table;
+pub mod turmite;
+pub mod wator;
+
+use alloc::vec::Vec;
+
+use elementary::Elementary;
+use epidemic::Epidemic;
+use forest_fire::ForestFire;
+use ising::Ising;
+use sand::Sand;
+use sandpile::Sandpile;
+use table::RuleTable;
+use turmite::Turmites;
+use wator::Wator;
+
+/// A grid automaton the app can run, draw and edit.
+pub trait Automaton {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    /// State of the cell, `0` being the empty background.
+    fn state(&self, row: usize, col: usize) -> u8;
+    /// Colour of a non-zero state as `0xRRGGBB`; `None` draws it in the
+    /// theme's alive colour.
+    fn color(&self, state: u8) -> Option<u32>;
+    fn step(&mut self);
+    /// The click/paint interaction at a cell; what it does is up to the
+    /// automaton.
+    fn poke(&mut self, row: usize, col: usize);
+    /// Cells holding moving agents, to be marked on the board.
+    fn agents(&self) -> Vec<(usize, usize)> {
+        Vec::new()
+    }
+    /// Whether dragging across the board keeps poking, like a paint brush,
+    /// rather than poking only where the button went down.
+    fn paints(&self) -> bool {
+        false
+    }
+    /// The settings the UI should offer, in display order.
+    fn params(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+    /// The current value of the setting called `key`.
+    fn param(&self, _key: &str) -> f64 {
+        0.0
+    }
+    /// Change the setting called `key`; `value` is already within its spec.
+    fn set_param(&mut self, _key: &str, _value: f64) {}
+}
+
+/// One tunable setting of an automaton.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ParamSpec {
+    /// Stable name, also how the UI finds the setting's label.
+    pub key: &'static str,
+    pub kind: ParamKind,
+}
+
+/// How a setting is edited.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ParamKind {
+    Slider { min: f64, max: f64, step: f64 },
+    /// A whole number from `min`, up to `max` if there is one.
+    Integer { min: f64, max: Option<f64> },
+    /// On (`1.0`) or off (`0.0`).
+    Toggle,
+}
+
+impl ParamSpec {
+    pub const fn slider(key: &'static str, min: f64, max: f64, step: f64) -> Self {
+        Self { key, kind: ParamKind::Slider { min, max, step } }
+    }
+
+    pub const fn integer(key: &'static str, min: f64, max: Option<f64>) -> Self {
+        Self { key, kind: ParamKind::Integer { min, max } }
+    }
+
+    pub const fn toggle(key: &'static str) -> Self {
+        Self { key, kind: ParamKind::Toggle }
+    }
+
+    /// `value` brought within the setting's range (and whole, for integers).
+    pub fn clamp(&self, value: f64) -> f64 {
+        match self.kind {
+            ParamKind::Slider { min, max, .. } => value.clamp(min, max),
+            ParamKind::Integer { min, max } => {
+                // `f64::round` needs `std`; settings are never negative.
+                let whole = (value.max(min) + 0.5) as u64 as f64;
+                max.map_or(whole, |max| whole.min(max))
+            }
+            ParamKind::Toggle => (value != 0.0) as u8 as f64,
+        }
+    }
+}
+
+/// The automata on offer, besides Life itself.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Elementary,
+    Turmites,
+    Sandpile,
+    ForestFire,
+    Epidemic,
+    Wator,
+    Sand,
+    Ising,
+    RuleTable,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 9] = [Mode::Elementary, Mode::Turmites, Mode::Sandpile, Mode::ForestFire, Mode::Epidemic, Mode::Wator, Mode::Sand, Mode::Ising, Mode::RuleTable];
+
+    /// Stable identifier for pickers and saves.
+    pub fn key(self) -> &'static str {
+        match self {
+            Mode::Elementary => "elementary",
+            Mode::Turmites => "turmites",
+            Mode::Sandpile => "sandpile",
+            Mode::ForestFire => "forest-fire",
+            Mode::Epidemic => "epidemic",
+            Mode::Wator => "wator",
+            Mode::Sand => "sand",
+            Mode::Ising => "ising",
+            Mode::RuleTable => "rule-table",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.key() == key)
+    }
+
+    /// A fresh `width × height` world of this kind; random ones draw on
+    /// `seed`.
+    pub fn create(self, width: usize, height: usize, seed: u64) -> World {
+        match self {
+            Mode::Elementary => World::Elementary(Elementary::new(width, height, elementary::DEFAULT_RULE)),
+            Mode::Turmites => World::Turmites(Turmites::new(width, height)),
+            Mode::Sandpile => World::Sandpile(Sandpile::new(width, height)),
+            Mode::ForestFire => World::ForestFire(ForestFire::new(width, height, seed)),
+            Mode::Epidemic => World::Epidemic(Epidemic::new(width, height, seed)),
+            Mode::Wator => World::Wator(Wator::new(width, height, seed)),
+            Mode::Sand => World::Sand(Sand::new(width, height, seed)),
+            Mode::Ising => World::Ising(Ising::new(width, height, seed)),
+            Mode::RuleTable => World::RuleTable(RuleTable::new(width, height)),
+        }
+    }
+}
+
+/// Whichever automaton is running.
+#[derive(Clone, PartialEq, Debug)]
+pub enum World {
+    Elementary(Elementary),
+    Turmites(Turmites),
+    Sandpile(Sandpile),
+    ForestFire(ForestFire),
+    Epidemic(Epidemic),
+    Wator(Wator),
+    Sand(Sand),
+    Ising(Ising),
+    RuleTable(RuleTable),
+}
+
+impl World {
+    pub fn mode(&self) -> Mode {
+        match self {
+            World::Elementary(_) => Mode::Elementary,
+            World::Turmites(_) => Mode::Turmites,
+            World::Sandpile(_) => Mode::Sandpile,
+            World::ForestFire(_) => Mode::ForestFire,
+            World::Epidemic(_) => Mode::Epidemic,
+            World::Wator(_) => Mode::Wator,
+            World::Sand(_) => Mode::Sand,
+            World::Ising(_) => Mode::Ising,
+            World::RuleTable(_) => Mode::RuleTable,
+        }
+    }
+
+    pub fn automaton(&self) -> &dyn Automaton {
+        match self {
+            World::Elementary(world) => world,
+            World::Turmites(world) => world,
+            World::Sandpile(world) => world,
+            World::ForestFire(world) => world,
+            World::Epidemic(world) => world,
+            World::Wator(world) => world,
+            World::Sand(world) => world,
+            World::Ising(world) => world,
+            World::RuleTable(world) => world,
+        }
+    }
+
+    pub fn automaton_mut(&mut self) -> &mut dyn Automaton {
+        match self {
+            World::Elementary(world) => world,
+            World::Turmites(world) => world,
+            World::Sandpile(world) => world,
+            World::ForestFire(world) => world,
+            World::Epidemic(world) => world,
+            World::Wator(world) => world,
+            World::Sand(world) => world,
+            World::Ising(world) => world,
+            World::RuleTable(world) => world,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Elementary cellular automata
+//
+// Wolfram's one-dimensional, two-state, nearest-neighbour automata. A cell's
+// next state depends on itself and its two neighbours; the eight possible
+// neighbourhoods, read as binary numbers 7 (`111`) down to 0 (`000`), index
+// the bits of the rule number, so every rule is a number 0–255 (Rule 30 is
+// chaotic, Rule 90 draws Sierpiński triangles, Rule 110 is Turing-complete).
+//
+// The board is the space-time diagram: each row is one generation, filled
+// from the top, and once the board is full it scrolls up a row per step. The
+// row ends wrap round.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+
+/// Rule a new world starts with.
+pub const DEFAULT_RULE: u8 = 30;
+/// Rules worth a button.
+pub const FAMOUS: [u8; 4] = [30, 90, 110, 184];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Elementary {
+    pub rule: u8,
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    /// Rows holding generations so far; the newest is the last of them.
+    filled: usize,
+}
+
+impl Elementary {
+    /// A world whose first generation is one live cell in the middle.
+    pub fn new(width: usize, height: usize, rule: u8) -> Self {
+        let mut cells = vec![false; width * height];
+        cells[width / 2] = true;
+        Self { rule, width, height, cells, filled: 1 }
+    }
+
+    fn newest_row(&self) -> usize {
+        self.filled - 1
+    }
+}
+
+impl Automaton for Elementary {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col] as u8
+    }
+
+    fn color(&self, _: u8) -> Option<u32> {
+        None
+    }
+
+    fn step(&mut self) {
+        let width = self.width;
+        let current = self.newest_row() * width;
+        let next: Vec<bool> = (0..width)
+            .map(|col| {
+                let left = self.cells[current + (col + width - 1) % width] as u8;
+                let middle = self.cells[current + col] as u8;
+                let right = self.cells[current + (col + 1) % width] as u8;
+                self.rule >> (left << 2 | middle << 1 | right) & 1 == 1
+            })
+            .collect();
+        if self.filled < self.height {
+            self.filled += 1;
+        } else {
+            self.cells.copy_within(width.., 0);
+        }
+        let start = self.newest_row() * width;
+        self.cells[start..start + width].copy_from_slice(&next);
+    }
+
+    /// Toggle a cell of the newest generation, to edit what comes next.
+    fn poke(&mut self, row: usize, col: usize) {
+        if row == self.newest_row() {
+            self.cells[row * self.width + col] ^= true;
+        }
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("rule", 0.0, Some(255.0))]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "rule" => self.rule as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        if key == "rule" {
+            self.rule = value as u8;
+        }
+    }
+}
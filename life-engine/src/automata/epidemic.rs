@@ -0,0 +1,152 @@
+// SIR epidemic
+//
+// Every cell is a person who is susceptible, infected or recovered. Each
+// step an infected neighbour (of the eight) passes the infection on with
+// probability `infection`; an infected person recovers after `duration`
+// steps; a recovered one loses their immunity again with probability
+// `immunity_loss` per step. Low immunity loss gives one wave that burns out,
+// higher values give recurring waves and spirals.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+use crate::rng::Rng;
+
+const SUSCEPTIBLE: u8 = 0;
+const INFECTED: u8 = 1;
+const RECOVERED: u8 = 2;
+const INFECTED_COLOR: u32 = 0xd62828;
+const RECOVERED_COLOR: u32 = 0x4361ee;
+/// Infected people in a new world.
+const FIRST_CASES: usize = 5;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Epidemic {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    /// Steps each infected person has been ill for.
+    days: Vec<u32>,
+    rng: Rng,
+    /// Chance of catching it from each infected neighbour, per step.
+    pub infection: f64,
+    /// Steps until an infected person recovers.
+    pub duration: u32,
+    /// Chance of a recovered person becoming susceptible again, per step.
+    pub immunity_loss: f64,
+}
+
+impl Epidemic {
+    /// Everyone susceptible but a few random first cases.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        let mut world = Self {
+            width,
+            height,
+            cells: vec![SUSCEPTIBLE; width * height],
+            days: vec![0; width * height],
+            rng: Rng::new(seed),
+            infection: 0.2,
+            duration: 10,
+            immunity_loss: 0.005,
+        };
+        for _ in 0..FIRST_CASES {
+            let idx = world.rng.next_u64() as usize % world.cells.len();
+            world.cells[idx] = INFECTED;
+        }
+        world
+    }
+
+    /// Susceptible, infected and recovered people.
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let count = |state| self.cells.iter().filter(|&&cell| cell == state).count();
+        (count(SUSCEPTIBLE), count(INFECTED), count(RECOVERED))
+    }
+}
+
+impl Automaton for Epidemic {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        Some(if state == INFECTED { INFECTED_COLOR } else { RECOVERED_COLOR })
+    }
+
+    fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let mut next = self.cells.clone();
+        for (idx, cell) in next.iter_mut().enumerate() {
+            match self.cells[idx] {
+                SUSCEPTIBLE => {
+                    let (row, col) = (idx / width, idx % width);
+                    let mut sick = 0;
+                    for d_row in [height - 1, 0, 1] {
+                        for d_col in [width - 1, 0, 1] {
+                            let neighbor = (row + d_row) % height * width + (col + d_col) % width;
+                            sick += (neighbor != idx && self.cells[neighbor] == INFECTED) as usize;
+                        }
+                    }
+                    // One chance per infected neighbour.
+                    if (0..sick).any(|_| self.rng.gen_bool(self.infection)) {
+                        *cell = INFECTED;
+                        self.days[idx] = 0;
+                    }
+                }
+                INFECTED => {
+                    self.days[idx] += 1;
+                    if self.days[idx] >= self.duration {
+                        *cell = RECOVERED;
+                    }
+                }
+                _ => {
+                    if self.rng.gen_bool(self.immunity_loss) {
+                        *cell = SUSCEPTIBLE;
+                    }
+                }
+            }
+        }
+        self.cells = next;
+    }
+
+    /// Infect the cell.
+    fn poke(&mut self, row: usize, col: usize) {
+        let idx = row * self.width + col;
+        self.cells[idx] = INFECTED;
+        self.days[idx] = 0;
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::slider("infection", 0.0, 1.0, 0.01),
+            ParamSpec::integer("duration", 1.0, Some(50.0)),
+            ParamSpec::slider("immunity_loss", 0.0, 0.05, 0.001),
+        ]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "infection" => self.infection,
+            "duration" => self.duration as f64,
+            "immunity_loss" => self.immunity_loss,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        match key {
+            "infection" => self.infection = value,
+            "duration" => self.duration = value as u32,
+            "immunity_loss" => self.immunity_loss = value,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Forest-fire model
+//
+// Drossel and Schwabl's model of self-organised criticality. Every step a
+// burning tree burns out, a tree next to a fire (up, down, left or right)
+// catches, any other tree is struck by lightning with probability `f`, and
+// an empty cell grows a tree with probability `p`. With `f` far below `p`
+// the forest keeps building up to the point where fires of every size break
+// out.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+use crate::rng::Rng;
+
+const EMPTY: u8 = 0;
+const TREE: u8 = 1;
+const FIRE: u8 = 2;
+const TREE_COLOR: u32 = 0x2e8b57;
+const FIRE_COLOR: u32 = 0xff6a00;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ForestFire {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    rng: Rng,
+    /// Chance of an empty cell growing a tree each step.
+    pub growth: f64,
+    /// Chance of a tree being struck by lightning each step.
+    pub lightning: f64,
+}
+
+impl ForestFire {
+    /// An empty field; trees grow in from nothing.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        Self { width, height, cells: vec![EMPTY; width * height], rng: Rng::new(seed), growth: 0.01, lightning: 0.00001 }
+    }
+
+    /// Trees and fires on the board.
+    pub fn counts(&self) -> (usize, usize) {
+        let trees = self.cells.iter().filter(|&&cell| cell == TREE).count();
+        let fires = self.cells.iter().filter(|&&cell| cell == FIRE).count();
+        (trees, fires)
+    }
+}
+
+impl Automaton for ForestFire {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        Some(if state == FIRE { FIRE_COLOR } else { TREE_COLOR })
+    }
+
+    fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let burning = |row: usize, col: usize| self.cells[row * width + col] == FIRE;
+        let next: Vec<u8> = (0..width * height)
+            .map(|idx| match self.cells[idx] {
+                FIRE => EMPTY,
+                TREE => {
+                    let (row, col) = (idx / width, idx % width);
+                    let caught = (row > 0 && burning(row - 1, col))
+                        || (row + 1 < height && burning(row + 1, col))
+                        || (col > 0 && burning(row, col - 1))
+                        || (col + 1 < width && burning(row, col + 1));
+                    if caught || self.rng.gen_bool(self.lightning) {
+                        FIRE
+                    } else {
+                        TREE
+                    }
+                }
+                _ if self.rng.gen_bool(self.growth) => TREE,
+                _ => EMPTY,
+            })
+            .collect();
+        self.cells = next;
+    }
+
+    /// Set the cell alight.
+    fn poke(&mut self, row: usize, col: usize) {
+        self.cells[row * self.width + col] = FIRE;
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::slider("growth", 0.0, 0.1, 0.001), ParamSpec::slider("lightning", 0.0, 0.001, 0.00001)]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "growth" => self.growth,
+            "lightning" => self.lightning,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        match key {
+            "growth" => self.growth = value,
+            "lightning" => self.lightning = value,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Ising model
+//
+// Spins pointing up or down on a torus, each one wanting to line up with its
+// four neighbours. Every step is one Metropolis sweep: as many times as there
+// are cells, a random spin is flipped if that lowers the energy, and
+// otherwise with probability `e^(-ΔE / T)`. Below the critical temperature
+// (about 2.269) domains grow until one direction takes over; above it the
+// spins stay a disordered speckle.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+use crate::rng::Rng;
+
+const UP_COLOR: u32 = 0xf4a261;
+/// Onsager's critical temperature, `2 / ln(1 + √2)`.
+pub const CRITICAL_TEMPERATURE: f64 = 2.269185314213022;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ising {
+    width: usize,
+    height: usize,
+    /// `true` for spin up.
+    spins: Vec<bool>,
+    rng: Rng,
+    /// In units of the coupling over Boltzmann's constant.
+    pub temperature: f64,
+}
+
+impl Ising {
+    /// Random spins, as if quenched from infinite temperature.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let spins = (0..width * height).map(|_| rng.gen_bool(0.5)).collect();
+        Self { width, height, spins, rng, temperature: 2.0 }
+    }
+
+    /// Mean spin, from -1 (all down) to 1 (all up).
+    pub fn magnetization(&self) -> f64 {
+        let up = self.spins.iter().filter(|&&spin| spin).count() as f64;
+        2.0 * up / self.spins.len() as f64 - 1.0
+    }
+}
+
+/// `e^(-x)` for `x ≥ 0`, without `std`: halve `x` until it is small, sum the
+/// series there and square back up.
+fn exp_neg(x: f64) -> f64 {
+    let mut halvings = 0;
+    let mut small = x;
+    while small > 0.5 {
+        small /= 2.0;
+        halvings += 1;
+    }
+    let (mut sum, mut term) = (1.0, 1.0);
+    for n in 1..12 {
+        term *= -small / n as f64;
+        sum += term;
+    }
+    for _ in 0..halvings {
+        sum *= sum;
+    }
+    sum
+}
+
+impl Automaton for Ising {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.spins[row * self.width + col] as u8
+    }
+
+    fn color(&self, _state: u8) -> Option<u32> {
+        Some(UP_COLOR)
+    }
+
+    fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        // ΔE of a flip is 4 or 8 when it costs anything at all.
+        let temperature = self.temperature.max(f64::MIN_POSITIVE);
+        let accept = [exp_neg(4.0 / temperature), exp_neg(8.0 / temperature)];
+        for _ in 0..self.spins.len() {
+            let idx = self.rng.next_u64() as usize % self.spins.len();
+            let (row, col) = (idx / width, idx % width);
+            let around = [
+                (row + height - 1) % height * width + col,
+                (row + 1) % height * width + col,
+                row * width + (col + width - 1) % width,
+                row * width + (col + 1) % width,
+            ];
+            // Neighbours agreeing with the spin, 0 to 4; ΔE = 4 × (agreeing − 2).
+            let agreeing = around.iter().filter(|&&neighbor| self.spins[neighbor] == self.spins[idx]).count();
+            let flip = match agreeing {
+                0..=2 => true,
+                3 => self.rng.gen_bool(accept[0]),
+                _ => self.rng.gen_bool(accept[1]),
+            };
+            if flip {
+                self.spins[idx] = !self.spins[idx];
+            }
+        }
+    }
+
+    /// Flip the spin.
+    fn poke(&mut self, row: usize, col: usize) {
+        let idx = row * self.width + col;
+        self.spins[idx] = !self.spins[idx];
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::slider("temperature", 0.1, 5.0, 0.01)]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "temperature" => self.temperature,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        if key == "temperature" {
+            self.temperature = value;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// Falling sand
+//
+// A sandbox of materials rather than a classic automaton: sand falls and
+// piles up at a slope, water falls and then spreads sideways to find its
+// level, and walls stay put. Sand sinks through water. The board's edges
+// behave like walls. Everything is painted in with a brush.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+use crate::rng::Rng;
+
+const SAND_COLOR: u32 = 0xe2c044;
+const WATER_COLOR: u32 = 0x3a86ff;
+const WALL_COLOR: u32 = 0x808080;
+/// Largest brush radius.
+pub const MAX_RADIUS: usize = 8;
+
+/// What a cell can hold; the discriminant is the cell's state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Material {
+    Empty,
+    Sand,
+    Water,
+    Wall,
+}
+
+impl Material {
+    pub const ALL: [Material; 4] = [Material::Sand, Material::Water, Material::Wall, Material::Empty];
+
+    fn from_state(state: u8) -> Self {
+        match state {
+            1 => Material::Sand,
+            2 => Material::Water,
+            3 => Material::Wall,
+            _ => Material::Empty,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sand {
+    width: usize,
+    height: usize,
+    cells: Vec<Material>,
+    rng: Rng,
+    /// What painting puts down; `Empty` erases.
+    pub brush: Material,
+    /// Cells painted round the pointer, `0` for just the one.
+    pub radius: usize,
+}
+
+impl Sand {
+    /// An empty box.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        Self { width, height, cells: vec![Material::Empty; width * height], rng: Rng::new(seed), brush: Material::Sand, radius: 2 }
+    }
+
+    /// Whether a material can move into the cell at `(row, col)`, swapping
+    /// with what is there.
+    fn gives_way(&self, row: usize, col: isize, material: Material) -> bool {
+        if row >= self.height || col < 0 || col as usize >= self.width {
+            return false;
+        }
+        match self.cells[row * self.width + col as usize] {
+            Material::Empty => true,
+            Material::Water => material == Material::Sand,
+            _ => false,
+        }
+    }
+}
+
+impl Automaton for Sand {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col] as u8
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        Some(match Material::from_state(state) {
+            Material::Water => WATER_COLOR,
+            Material::Wall => WALL_COLOR,
+            _ => SAND_COLOR,
+        })
+    }
+
+    fn step(&mut self) {
+        let width = self.width;
+        // Bottom row first so a falling column moves as one. Whatever has
+        // been swapped into a cell this step sits out the rest of it.
+        let mut moved = vec![false; self.cells.len()];
+        for row in (0..self.height).rev() {
+            // Alternate the scan direction so nothing drifts to one side.
+            let flip = self.rng.gen_bool(0.5);
+            for i in 0..width {
+                let col = if flip { width - 1 - i } else { i };
+                let idx = row * width + col;
+                let material = self.cells[idx];
+                if moved[idx] || !matches!(material, Material::Sand | Material::Water) {
+                    continue;
+                }
+                let side = if self.rng.gen_bool(0.5) { 1 } else { -1 };
+                let col = col as isize;
+                // Down, then diagonally down; water also tries sideways.
+                let targets = [(row + 1, col), (row + 1, col + side), (row + 1, col - side), (row, col + side), (row, col - side)];
+                let reach = if material == Material::Water { 5 } else { 3 };
+                if let Some(&(to_row, to_col)) = targets[..reach].iter().find(|&&(r, c)| self.gives_way(r, c, material)) {
+                    let to = to_row * width + to_col as usize;
+                    self.cells.swap(idx, to);
+                    moved[to] = true;
+                    moved[idx] = self.cells[idx] != Material::Empty;
+                }
+            }
+        }
+    }
+
+    /// Paint a disc of `brush` round the cell.
+    fn poke(&mut self, row: usize, col: usize) {
+        let radius = self.radius.min(MAX_RADIUS) as isize;
+        for d_row in -radius..=radius {
+            for d_col in -radius..=radius {
+                let (r, c) = (row as isize + d_row, col as isize + d_col);
+                let inside = (0..self.height as isize).contains(&r) && (0..self.width as isize).contains(&c);
+                if inside && d_row * d_row + d_col * d_col <= radius * radius {
+                    self.cells[r as usize * self.width + c as usize] = self.brush;
+                }
+            }
+        }
+    }
+
+    fn paints(&self) -> bool {
+        true
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::slider("radius", 0.0, MAX_RADIUS as f64, 1.0)]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "radius" => self.radius as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        if key == "radius" {
+            self.radius = value as usize;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Abelian sandpile
+//
+// Every cell holds a pile of grains. A pile of four or more topples: it
+// sends a grain to each of its four neighbours, which may topple in turn,
+// and grains pushed over the edge are lost. However grains are dropped, the
+// pile always settles into the same stable state (it is "abelian"), and a
+// big drop on one cell settles into a striking fractal.
+//
+// Each step every unstable pile topples as often as it can at once, so a
+// large drop settles in far fewer steps than one toppling at a time.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+
+/// Grains at which a pile topples.
+pub const TOPPLE_AT: u32 = 4;
+/// Most grains a poke drops; a million already settles nearly 800 cells
+/// across.
+pub const MAX_DROP: u32 = 1_000_000;
+/// Most grains fed to the centre each step.
+pub const MAX_FEED: u32 = 10_000;
+/// Heights 1, 2 and 3, then anything still toppling.
+const RAMP: [u32; 4] = [0x3b6ea5, 0x6cc3d5, 0xf3d250, 0xe4572e];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sandpile {
+    width: usize,
+    height: usize,
+    grains: Vec<u32>,
+    /// Grains a poke drops.
+    pub drop: u32,
+    /// Grains dropped on the centre every step.
+    pub feed: u32,
+}
+
+impl Sandpile {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, grains: vec![0; width * height], drop: 1000, feed: 0 }
+    }
+
+    pub fn drop_grains(&mut self, row: usize, col: usize, grains: u32) {
+        let idx = row * self.width + col;
+        self.grains[idx] = self.grains[idx].saturating_add(grains);
+    }
+
+    pub fn drop_at_center(&mut self, grains: u32) {
+        self.drop_grains(self.height / 2, self.width / 2, grains);
+    }
+
+    /// True when no pile is due to topple.
+    pub fn is_stable(&self) -> bool {
+        self.grains.iter().all(|&grains| grains < TOPPLE_AT)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.grains.iter().map(|&grains| grains as u64).sum()
+    }
+}
+
+impl Automaton for Sandpile {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.grains[row * self.width + col].min(TOPPLE_AT) as u8
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        RAMP.get((state as usize).checked_sub(1)?).copied()
+    }
+
+    fn step(&mut self) {
+        if self.feed > 0 {
+            self.drop_at_center(self.feed);
+        }
+        let (width, height) = (self.width, self.height);
+        let mut next = self.grains.clone();
+        for (idx, &grains) in self.grains.iter().enumerate() {
+            let topples = grains / TOPPLE_AT;
+            if topples == 0 {
+                continue;
+            }
+            next[idx] -= topples * TOPPLE_AT;
+            let (row, col) = (idx / width, idx % width);
+            // Piles stop growing at `u32::MAX` rather than wrap round.
+            if row > 0 {
+                next[idx - width] = next[idx - width].saturating_add(topples);
+            }
+            if row + 1 < height {
+                next[idx + width] = next[idx + width].saturating_add(topples);
+            }
+            if col > 0 {
+                next[idx - 1] = next[idx - 1].saturating_add(topples);
+            }
+            if col + 1 < width {
+                next[idx + 1] = next[idx + 1].saturating_add(topples);
+            }
+        }
+        self.grains = next;
+    }
+
+    /// Drop `drop` grains on the cell.
+    fn poke(&mut self, row: usize, col: usize) {
+        self.drop_grains(row, col, self.drop);
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("drop", 1.0, Some(MAX_DROP as f64)), ParamSpec::integer("feed", 0.0, Some(MAX_FEED as f64))]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "drop" => self.drop as f64,
+            "feed" => self.feed as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        match key {
+            "drop" => self.drop = (value as u32).clamp(1, MAX_DROP),
+            "feed" => self.feed = (value as u32).min(MAX_FEED),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,506 @@
+// Rule tables
+//
+// Arbitrary n-state rules, read from Golly's `.rule` files. The `@TABLE`
+// section lists transitions `center, neighbours…, new` over the von Neumann
+// (N, E, S, W) or Moore (N, NE, E, SE, S, SW, W, NW) neighbourhood, where
+// any entry may be a variable standing for a set of states. A variable used
+// more than once in a transition takes the same value everywhere in it, and
+// the new state may be a variable from the left-hand side. The declared
+// symmetries (`none`, `rotate4`, `rotate8`, `reflect_horizontal`,
+// `rotate4reflect`, `rotate8reflect`, `permute`) match each transition in
+// every rotated or reflected form as well. The first transition that
+// matches wins; a cell nothing matches keeps its state. An optional
+// `@COLORS` section (`state r g b` per line) colours the states.
+//
+// Looking up a neighbourhood walks the whole table, so results are memoised
+// per neighbourhood; the board wraps at the edges.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::Automaton;
+
+/// Most states a table may declare.
+pub const MAX_STATES: usize = 256;
+/// The table a new rule-table world starts with.
+pub const WIREWORLD: &str = "@RULE WireWorld
+# 0 empty, 1 electron head, 2 electron tail, 3 conductor.
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+# Anything but an electron head.
+var a={0,2,3}
+var b=a
+var c=a
+var d=a
+var e=a
+var f=a
+var g=a
+# Anything at all.
+var p={0,1,2,3}
+var q=p
+var r=p
+var s=p
+var t=p
+var u=p
+var v=p
+var w=p
+1,p,q,r,s,t,u,v,w,2
+2,p,q,r,s,t,u,v,w,3
+3,1,a,b,c,d,e,f,g,1
+3,1,1,a,b,c,d,e,f,1
+@COLORS
+1 0 128 255
+2 255 255 255
+3 255 128 0
+";
+/// Colours for states a table leaves uncoloured, cycled.
+const PALETTE: [u32; 8] = [0xf4d35e, 0xee964b, 0xf95738, 0x0d3b66, 0x7fb069, 0x9b5de5, 0x00bbf9, 0xe0e0e0];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Neighborhood {
+    VonNeumann,
+    Moore,
+}
+
+impl Neighborhood {
+    fn len(self) -> usize {
+        match self {
+            Neighborhood::VonNeumann => 4,
+            Neighborhood::Moore => 8,
+        }
+    }
+}
+
+/// One entry of a transition.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Term {
+    State(u8),
+    /// By index into the table's variables.
+    Var(usize),
+}
+
+/// A parsed `@TABLE`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Table {
+    /// From the `@RULE` line.
+    pub name: String,
+    pub states: usize,
+    neighborhood: Neighborhood,
+    /// Whether neighbours match in any order (`permute`).
+    permute: bool,
+    /// The neighbour orders each transition is tried in, unless `permute`.
+    orders: Vec<Vec<usize>>,
+    /// The states each variable stands for.
+    vars: Vec<Vec<u8>>,
+    /// Center, neighbours, new state.
+    transitions: Vec<Vec<Term>>,
+    colors: Vec<Option<u32>>,
+}
+
+impl Table {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut name = String::new();
+        let mut section = "";
+        let mut states = None;
+        let mut neighborhood = None;
+        let mut symmetry = "none".to_string();
+        let mut var_names: Vec<String> = Vec::new();
+        let mut vars: Vec<Vec<u8>> = Vec::new();
+        let mut rows: Vec<(usize, Vec<Term>)> = Vec::new();
+        let mut colors = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('@') {
+                let (tag, rest) = header.split_once(char::is_whitespace).unwrap_or((header, ""));
+                section = match tag {
+                    "RULE" => {
+                        name = rest.trim().to_string();
+                        "RULE"
+                    }
+                    "TABLE" => "TABLE",
+                    "COLORS" => "COLORS",
+                    // `@TREE`, `@ICONS`, `@NAMES`…: not needed to run the table.
+                    _ => "",
+                };
+                continue;
+            }
+            match section {
+                "TABLE" => {
+                    if let Some((key, value)) = line.split_once(':') {
+                        let value = value.trim();
+                        match key.trim() {
+                            "n_states" => {
+                                let count: usize = value.parse().map_err(|_| format!("line {number}: `{value}` is not a state count"))?;
+                                if !(2..=MAX_STATES).contains(&count) {
+                                    return Err(format!("line {number}: n_states must be 2–{MAX_STATES}"));
+                                }
+                                states = Some(count);
+                            }
+                            "neighborhood" => {
+                                neighborhood = Some(match value {
+                                    "vonNeumann" => Neighborhood::VonNeumann,
+                                    "Moore" => Neighborhood::Moore,
+                                    other => return Err(format!("line {number}: the {other} neighbourhood is not supported")),
+                                })
+                            }
+                            "symmetries" => symmetry = value.to_string(),
+                            other => return Err(format!("line {number}: unknown setting `{other}`")),
+                        }
+                    } else if let Some(declaration) = line.strip_prefix("var ") {
+                        let (var, set) =
+                            declaration.split_once('=').ok_or_else(|| format!("line {number}: a variable needs `name={{states}}`"))?;
+                        let states = states.ok_or_else(|| format!("line {number}: n_states must come before variables"))?;
+                        let set = set.trim().trim_start_matches('{').trim_end_matches('}');
+                        let mut members = Vec::new();
+                        for item in set.split(',').map(str::trim) {
+                            match var_names.iter().rposition(|known| known == item) {
+                                Some(other) => members.extend_from_slice(&vars[other]),
+                                None => members.push(state(item, states).map_err(|err| format!("line {number}: {err}"))?),
+                            }
+                        }
+                        members.sort_unstable();
+                        members.dedup();
+                        // A redeclared name is a new variable from here on;
+                        // names are looked up latest first.
+                        var_names.push(var.trim().to_string());
+                        vars.push(members);
+                    } else {
+                        let states = states.ok_or_else(|| format!("line {number}: n_states must come before transitions"))?;
+                        // Single-digit states may be run together: `0123…`.
+                        let items: Vec<&str> = if line.contains(',') {
+                            line.split(',').map(str::trim).collect()
+                        } else {
+                            line.split("").filter(|item| !item.is_empty()).collect()
+                        };
+                        let mut terms = Vec::new();
+                        for item in items {
+                            terms.push(match var_names.iter().rposition(|known| known == item) {
+                                Some(var) => Term::Var(var),
+                                None => Term::State(state(item, states).map_err(|err| format!("line {number}: {err}"))?),
+                            });
+                        }
+                        rows.push((number, terms));
+                    }
+                }
+                "COLORS" => {
+                    let numbers: Vec<u32> = line.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+                    // Gradient lines (`r g b r g b`) don't name a state.
+                    if let &[state, r, g, b] = numbers.as_slice() {
+                        colors.push((state as usize, r.min(255) << 16 | g.min(255) << 8 | b.min(255)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let states = states.ok_or("the table has no n_states")?;
+        let neighborhood = neighborhood.ok_or("the table has no neighborhood")?;
+        let size = neighborhood.len();
+        let (permute, orders) = symmetries(&symmetry, neighborhood).ok_or_else(|| format!("unknown symmetries `{symmetry}`"))?;
+        let mut transitions = Vec::new();
+        for (number, terms) in rows {
+            if terms.len() != size + 2 {
+                return Err(format!("line {number}: a transition needs {} entries, not {}", size + 2, terms.len()));
+            }
+            if let Some(&Term::Var(var)) = terms.last() {
+                if !terms[..=size].contains(&Term::Var(var)) {
+                    return Err(format!("line {number}: the new state's variable isn't bound on the left"));
+                }
+            }
+            transitions.push(terms);
+        }
+
+        let mut palette = vec![None; states];
+        for (state, rgb) in colors {
+            if let Some(slot) = palette.get_mut(state) {
+                *slot = Some(rgb);
+            }
+        }
+        Ok(Self { name, states, neighborhood, permute, orders, vars, transitions, colors: palette })
+    }
+
+    /// The new state of `center` among `neighbors` (in the table's order);
+    /// `None` if no transition matches.
+    pub fn lookup(&self, center: u8, neighbors: &[u8]) -> Option<u8> {
+        self.transitions.iter().find_map(|terms| {
+            let (inputs, new) = (&terms[1..terms.len() - 1], terms[terms.len() - 1]);
+            let mut bound = vec![None; self.vars.len()];
+            if !self.matches(terms[0], center, &mut bound) {
+                return None;
+            }
+            let bound = if self.permute {
+                self.permuted(inputs, neighbors, 0, &mut bound).then_some(bound)?
+            } else {
+                self.orders.iter().find_map(|order| {
+                    let mut trial = bound.clone();
+                    inputs.iter().zip(order).all(|(&term, &at)| self.matches(term, neighbors[at], &mut trial)).then_some(trial)
+                })?
+            };
+            Some(match new {
+                Term::State(state) => state,
+                Term::Var(var) => bound[var].expect("parse checks the new state's variable is bound"),
+            })
+        })
+    }
+
+    /// Whether `term` can be `state`, binding it if it is a free variable.
+    fn matches(&self, term: Term, state: u8, bound: &mut [Option<u8>]) -> bool {
+        match term {
+            Term::State(wanted) => wanted == state,
+            Term::Var(var) => match bound[var] {
+                Some(value) => value == state,
+                None if self.vars[var].contains(&state) => {
+                    bound[var] = Some(state);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Whether `inputs` match the neighbours not yet in `used`, in any order.
+    fn permuted(&self, inputs: &[Term], neighbors: &[u8], used: u16, bound: &mut Vec<Option<u8>>) -> bool {
+        let Some((&term, rest)) = inputs.split_first() else { return true };
+        for (at, &state) in neighbors.iter().enumerate() {
+            if used & 1 << at != 0 {
+                continue;
+            }
+            let mut trial = bound.clone();
+            if self.matches(term, state, &mut trial) && self.permuted(rest, neighbors, used | 1 << at, &mut trial) {
+                *bound = trial;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The highest state, which with `MAX_STATES` states is 255, so it is
+    /// worked out in `usize` before narrowing.
+    pub fn last_state(&self) -> u8 {
+        (self.states - 1) as u8
+    }
+
+    /// A state's colour: the table's, or one from a fixed palette.
+    pub fn color(&self, state: u8) -> u32 {
+        self.colors.get(state as usize).copied().flatten().unwrap_or(PALETTE[(state as usize - 1) % PALETTE.len()])
+    }
+}
+
+/// A state number below `states`.
+fn state(item: &str, states: usize) -> Result<u8, String> {
+    match item.parse::<usize>() {
+        Ok(state) if state < states => Ok(state as u8),
+        Ok(state) => Err(format!("state {state} is past n_states")),
+        Err(_) => Err(format!("unknown variable `{item}`")),
+    }
+}
+
+/// Whether `name` permutes the neighbours freely, and otherwise the orders
+/// its rotations and reflections visit the neighbours in.
+fn symmetries(name: &str, neighborhood: Neighborhood) -> Option<(bool, Vec<Vec<usize>>)> {
+    let size = neighborhood.len();
+    // Moore rotates by two positions per quarter turn, von Neumann by one.
+    let quarter = size / 4;
+    let (turns, reflect): (Vec<usize>, bool) = match name {
+        "none" => (vec![0], false),
+        "permute" => return Some((true, Vec::new())),
+        "rotate4" => ((0..4).map(|turn| turn * quarter).collect(), false),
+        "rotate4reflect" => ((0..4).map(|turn| turn * quarter).collect(), true),
+        "rotate8" if neighborhood == Neighborhood::Moore => ((0..8).collect(), false),
+        "rotate8reflect" if neighborhood == Neighborhood::Moore => ((0..8).collect(), true),
+        "reflect_horizontal" => (vec![0], true),
+        _ => return None,
+    };
+    let mut orders = Vec::new();
+    for turn in turns {
+        orders.push((0..size).map(|j| (j + turn) % size).collect());
+        if reflect {
+            orders.push((0..size).map(|j| (size - j + turn) % size).collect());
+        }
+    }
+    Some((false, orders))
+}
+
+/// A board run by a `Table`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RuleTable {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    table: Table,
+    /// Center and neighbours to new state, as looked up so far.
+    cache: BTreeMap<[u8; 9], u8>,
+    /// The state painting puts down.
+    pub brush: u8,
+}
+
+impl RuleTable {
+    /// An empty board under WireWorld.
+    pub fn new(width: usize, height: usize) -> Self {
+        let table = Table::parse(WIREWORLD).expect("the bundled table parses");
+        Self { width, height, cells: vec![0; width * height], table, cache: BTreeMap::new(), brush: 1 }
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Switch to `table`, clearing the board.
+    pub fn set_table(&mut self, table: Table) {
+        self.cells.fill(0);
+        self.cache.clear();
+        self.brush = self.brush.min(table.last_state()).max(1);
+        self.table = table;
+    }
+}
+
+impl Automaton for RuleTable {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        Some(self.table.color(state))
+    }
+
+    fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let (up, down) = (height - 1, 1);
+        let (left, right) = (width - 1, 1);
+        // (row, col) offsets in the table's neighbour order.
+        let offsets: &[(usize, usize)] = match self.table.neighborhood {
+            Neighborhood::VonNeumann => &[(up, 0), (0, right), (down, 0), (0, left)],
+            Neighborhood::Moore => {
+                &[(up, 0), (up, right), (0, right), (down, right), (down, 0), (down, left), (0, left), (up, left)]
+            }
+        };
+        let mut next = self.cells.clone();
+        for (idx, cell) in next.iter_mut().enumerate() {
+            let (row, col) = (idx / width, idx % width);
+            let mut key = [0; 9];
+            key[0] = self.cells[idx];
+            for (slot, &(d_row, d_col)) in key[1..].iter_mut().zip(offsets) {
+                *slot = self.cells[(row + d_row) % height * width + (col + d_col) % width];
+            }
+            let table = &self.table;
+            *cell = *self
+                .cache
+                .entry(key)
+                .or_insert_with(|| table.lookup(key[0], &key[1..=offsets.len()]).unwrap_or(key[0]));
+        }
+        self.cells = next;
+    }
+
+    /// Paint the brush state.
+    fn poke(&mut self, row: usize, col: usize) {
+        self.cells[row * self.width + col] = self.brush;
+    }
+
+    fn paints(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Von Neumann, three states: a dead cell next to a 1 becomes one, a 2
+    /// with the same state all round becomes 0, and a 2 with a 0 to its west
+    /// takes its northern neighbour's state, all in any rotation.
+    const SPREAD: &str = "@RULE Spread
+@TABLE
+n_states:3
+neighborhood:vonNeumann
+symmetries:rotate4
+var a={0,1,2}
+var b=a
+var c=a
+var x={1,2}
+0,1,a,b,c,1
+2,x,x,x,x,0
+2,a,b,c,0,a
+@COLORS
+1 255 0 0
+";
+
+    #[test]
+    fn parses_variables_and_symmetries() {
+        let table = Table::parse(SPREAD).unwrap();
+        assert_eq!((table.name.as_str(), table.states), ("Spread", 3));
+        // The 1 may be on any side under `rotate4`.
+        for side in 0..4 {
+            let mut neighbors = [0; 4];
+            neighbors[side] = 1;
+            assert_eq!(table.lookup(0, &neighbors), Some(1), "1 on side {side}");
+        }
+        assert_eq!(table.lookup(0, &[2, 2, 0, 0]), None);
+        // `x` four times is one state four times.
+        assert_eq!(table.lookup(2, &[1, 1, 1, 1]), Some(0));
+        // The third transition binds `a` to whatever is a quarter turn on
+        // from the 0.
+        assert_eq!(table.lookup(2, &[1, 2, 1, 2]), None);
+        assert_eq!(table.lookup(2, &[1, 2, 2, 0]), Some(1));
+        assert_eq!(table.lookup(2, &[1, 2, 0, 2]), Some(2));
+        assert_eq!(table.lookup(1, &[0, 0, 0, 0]), None);
+        assert_eq!(table.color(1), 0xff0000);
+    }
+
+    #[test]
+    fn permute_matches_neighbours_in_any_order() {
+        let table = Table::parse("@TABLE\nn_states:2\nneighborhood:Moore\nsymmetries:permute\n0,1,1,1,0,0,0,0,0,1\n").unwrap();
+        assert_eq!(table.lookup(0, &[0, 1, 0, 0, 1, 0, 0, 1]), Some(1));
+        assert_eq!(table.lookup(0, &[0, 1, 0, 0, 1, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn wireworld_moves_an_electron_along_a_wire() {
+        let mut board = RuleTable::new(6, 3);
+        for (col, state) in [2, 1, 3, 3, 3, 3].into_iter().enumerate() {
+            board.brush = state;
+            board.poke(1, col);
+        }
+        board.step();
+        let row: Vec<u8> = (0..6).map(|col| board.state(1, col)).collect();
+        assert_eq!(row, [3, 2, 1, 3, 3, 3]);
+        assert!((0..6).all(|col| board.state(0, col) == 0 && board.state(2, col) == 0));
+    }
+
+    #[test]
+    fn refuses_malformed_tables() {
+        let table = |body: &str| Table::parse(&format!("@TABLE\nn_states:3\nneighborhood:vonNeumann\n{body}\n"));
+        for body in [
+            "0,1,q,0,0,1",
+            "0,1,0,0,0,3",
+            "0,1,0,0,1",
+            "var a={0,1}\n0,0,0,0,0,a",
+            "symmetries:rotate8",
+            "var a",
+            "var a={0,9}",
+            "colour:red",
+        ] {
+            assert!(table(body).is_err(), "{body}");
+        }
+        for text in ["", "@TABLE\nn_states:1\nneighborhood:Moore\n", "@TABLE\nn_states:300\n", "@TABLE\nn_states:2\n", "@TABLE\n0,1,0,0,0,1\n"] {
+            assert!(Table::parse(text).is_err(), "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// Turmites
+//
+// Turing machines on a grid: each turmite has a direction and an internal
+// state, and every step it reads the colour under it, looks up its program's
+// transition for (state, colour), writes a new colour, turns, switches state
+// and moves one cell forward. Langton's ant is the one-state, two-colour
+// turmite "on white turn right, on black turn left".
+//
+// Programs use the nested-brace notation Golly and Ed Pegg's turmite pages
+// use, `{{{write, turn, next}, …per colour}, …per state}`, with turns coded
+// 1 = none, 2 = right, 4 = U-turn, 8 = left. Langton's ant is
+// `{{{1, 2, 0}, {0, 8, 0}}}`. Several turmites can share the grid, each with
+// its own program.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::Automaton;
+
+/// Most colours a program may use.
+pub const MAX_COLORS: usize = 8;
+/// Named programs to start from.
+pub const PRESETS: [(&str, &str); 4] = [
+    ("Langton's ant", "{{{1, 2, 0}, {0, 8, 0}}}"),
+    ("RLR ant", "{{{1, 2, 0}, {2, 8, 0}, {0, 2, 0}}}"),
+    ("LLRR ant", "{{{1, 8, 0}, {2, 8, 0}, {3, 2, 0}, {0, 2, 0}}}"),
+    ("Fibonacci spiral", "{{{1, 8, 1}, {1, 8, 1}}, {{1, 2, 1}, {0, 1, 0}}}"),
+];
+/// Colours past 1 (which is drawn in the theme's alive colour).
+const PALETTE: [u32; MAX_COLORS - 2] = [0xe4572e, 0x29a3a3, 0xf3a712, 0x8a4fff, 0x5dbb63, 0xd6336c];
+
+/// Quarter turns to the right.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Turn {
+    None,
+    Right,
+    UTurn,
+    Left,
+}
+
+impl Turn {
+    fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            1 => Turn::None,
+            2 => Turn::Right,
+            4 => Turn::UTurn,
+            8 => Turn::Left,
+            _ => return None,
+        })
+    }
+
+    fn code(self) -> u32 {
+        match self {
+            Turn::None => 1,
+            Turn::Right => 2,
+            Turn::UTurn => 4,
+            Turn::Left => 8,
+        }
+    }
+
+    fn quarters(self) -> u8 {
+        self as u8
+    }
+}
+
+/// What a turmite does on one (state, colour).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Transition {
+    pub write: u8,
+    pub turn: Turn,
+    pub next: u8,
+}
+
+/// A turmite's transition table.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Program {
+    colors: usize,
+    /// Row per state, colour per column.
+    table: Vec<Transition>,
+}
+
+impl Program {
+    pub fn langtons_ant() -> Self {
+        Self::parse(PRESETS[0].1).expect("the presets parse")
+    }
+
+    /// Read the nested-brace notation.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut states: Vec<Vec<Transition>> = Vec::new();
+        let mut numbers: Vec<u32> = Vec::new();
+        let mut depth = 0usize;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    match depth {
+                        2 => states.push(Vec::new()),
+                        3 => numbers.clear(),
+                        n if n > 3 => return Err("braces nested too deep".to_string()),
+                        _ => {}
+                    }
+                }
+                '}' => {
+                    if depth == 3 {
+                        let &[write, turn, next] = numbers.as_slice() else {
+                            return Err(format!("a transition needs 3 numbers, not {}", numbers.len()));
+                        };
+                        let turn = Turn::from_code(turn).ok_or_else(|| format!("`{turn}` is not a turn (1, 2, 4 or 8)"))?;
+                        let transition = Transition { write: write.min(255) as u8, turn, next: next.min(255) as u8 };
+                        states.last_mut().expect("depth 3 is inside a state").push(transition);
+                    }
+                    depth = depth.checked_sub(1).ok_or("unbalanced `}`")?;
+                }
+                '0'..='9' => {
+                    if depth != 3 {
+                        return Err("numbers belong inside a {write, turn, next} triple".to_string());
+                    }
+                    let mut number = c.to_digit(10).expect("matched a digit");
+                    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                        number = number.saturating_mul(10).saturating_add(digit);
+                        chars.next();
+                    }
+                    numbers.push(number);
+                }
+                ',' => {}
+                c if c.is_whitespace() => {}
+                c => return Err(format!("unexpected `{c}`")),
+            }
+        }
+        if depth != 0 {
+            return Err("unbalanced `{`".to_string());
+        }
+
+        let colors = states.first().map_or(0, Vec::len);
+        if states.is_empty() || !(2..=MAX_COLORS).contains(&colors) {
+            return Err(format!("a program needs at least one state and 2–{MAX_COLORS} colours"));
+        }
+        if states.iter().any(|state| state.len() != colors) {
+            return Err("every state needs a transition for each colour".to_string());
+        }
+        let table: Vec<Transition> = states.into_iter().flatten().collect();
+        let count = table.len() / colors;
+        for transition in &table {
+            if transition.write as usize >= colors {
+                return Err(format!("colour {} is past the table's {colors} colours", transition.write));
+            }
+            if transition.next as usize >= count {
+                return Err(format!("state {} is past the table's {count} states", transition.next));
+            }
+        }
+        Ok(Self { colors, table })
+    }
+
+    pub fn colors(&self) -> usize {
+        self.colors
+    }
+
+    pub fn states(&self) -> usize {
+        self.table.len() / self.colors
+    }
+
+    /// The transition for `state` on a cell of `color`. Colours the program
+    /// doesn't know (left by another program) wrap round.
+    pub fn get(&self, state: u8, color: u8) -> Transition {
+        self.table[state as usize % self.states() * self.colors + color as usize % self.colors]
+    }
+
+    /// Back to the nested-brace notation.
+    pub fn to_text(&self) -> String {
+        let states: Vec<String> = self
+            .table
+            .chunks(self.colors)
+            .map(|state| {
+                let transitions: Vec<String> =
+                    state.iter().map(|t| format!("{{{}, {}, {}}}", t.write, t.turn.code(), t.next)).collect();
+                format!("{{{}}}", transitions.join(", "))
+            })
+            .collect();
+        format!("{{{}}}", states.join(", "))
+    }
+}
+
+/// One turmite on the grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Turmite {
+    pub row: usize,
+    pub col: usize,
+    /// 0 = up, then clockwise.
+    pub direction: u8,
+    pub state: u8,
+    /// Index into `Turmites::programs`.
+    pub program: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Turmites {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    pub programs: Vec<Program>,
+    pub turmites: Vec<Turmite>,
+    /// Program given to turmites placed by `poke`.
+    pub brush: usize,
+}
+
+impl Turmites {
+    /// An empty grid with one Langton's ant in the middle, facing up.
+    pub fn new(width: usize, height: usize) -> Self {
+        let ant = Turmite { row: height / 2, col: width / 2, direction: 0, state: 0, program: 0 };
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+            programs: vec![Program::langtons_ant()],
+            turmites: vec![ant],
+            brush: 0,
+        }
+    }
+
+    /// Swap in a new program at `index`; its turmites start over in state 0.
+    pub fn set_program(&mut self, index: usize, program: Program) {
+        self.programs[index] = program;
+        for turmite in self.turmites.iter_mut().filter(|turmite| turmite.program == index) {
+            turmite.state = 0;
+        }
+    }
+}
+
+impl Automaton for Turmites {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        PALETTE.get((state as usize).checked_sub(2)?).copied()
+    }
+
+    fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        for turmite in &mut self.turmites {
+            let idx = turmite.row * width + turmite.col;
+            let transition = self.programs[turmite.program].get(turmite.state, self.cells[idx]);
+            self.cells[idx] = transition.write;
+            turmite.direction = (turmite.direction + transition.turn.quarters()) % 4;
+            turmite.state = transition.next;
+            (turmite.row, turmite.col) = match turmite.direction {
+                0 => ((turmite.row + height - 1) % height, turmite.col),
+                1 => (turmite.row, (turmite.col + 1) % width),
+                2 => ((turmite.row + 1) % height, turmite.col),
+                _ => (turmite.row, (turmite.col + width - 1) % width),
+            };
+        }
+    }
+
+    /// Put a new turmite running the brush program on the cell.
+    fn poke(&mut self, row: usize, col: usize) {
+        self.turmites.push(Turmite { row, col, direction: 0, state: 0, program: self.brush });
+    }
+
+    fn agents(&self) -> Vec<(usize, usize)> {
+        self.turmites.iter().map(|turmite| (turmite.row, turmite.col)).collect()
+    }
+}
@@ -0,0 +1,207 @@
+// Wa-Tor
+//
+// Dewdney's predator–prey world on a torus. Each step every fish swims to a
+// random free cell next to it (up, down, left or right) and, once it is
+// `fish_breed` steps old, leaves a young fish behind where it was. Every
+// shark eats a neighbouring fish if there is one and otherwise swims like a
+// fish; it breeds after `shark_breed` steps and starves if it goes
+// `starve` steps without eating. The two populations rise and fall in turn.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Automaton, ParamSpec};
+use crate::rng::Rng;
+
+const WATER: u8 = 0;
+const FISH: u8 = 1;
+const SHARK: u8 = 2;
+const FISH_COLOR: u32 = 0x3bb273;
+const SHARK_COLOR: u32 = 0xe15554;
+/// Share of the cells a new world fills with fish, and with sharks.
+const FISH_DENSITY: f64 = 0.3;
+const SHARK_DENSITY: f64 = 0.05;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Wator {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    /// Steps since each creature was born or last bred.
+    age: Vec<u32>,
+    /// Steps since each shark last ate.
+    hunger: Vec<u32>,
+    rng: Rng,
+    /// Steps a fish lives before it breeds.
+    pub fish_breed: u32,
+    /// Steps a shark lives before it breeds.
+    pub shark_breed: u32,
+    /// Steps a shark survives without eating.
+    pub starve: u32,
+}
+
+impl Wator {
+    /// An ocean scattered with fish and a few sharks, of random ages.
+    pub fn new(width: usize, height: usize, seed: u64) -> Self {
+        let mut world = Self {
+            width,
+            height,
+            cells: vec![WATER; width * height],
+            age: vec![0; width * height],
+            hunger: vec![0; width * height],
+            rng: Rng::new(seed),
+            fish_breed: 3,
+            shark_breed: 10,
+            starve: 3,
+        };
+        for idx in 0..world.cells.len() {
+            let roll = world.rng.next_f64();
+            if roll < SHARK_DENSITY {
+                world.cells[idx] = SHARK;
+                world.age[idx] = world.rng.next_u64() as u32 % world.shark_breed;
+            } else if roll < SHARK_DENSITY + FISH_DENSITY {
+                world.cells[idx] = FISH;
+                world.age[idx] = world.rng.next_u64() as u32 % world.fish_breed;
+            }
+        }
+        world
+    }
+
+    /// Fish and sharks in the ocean.
+    pub fn counts(&self) -> (usize, usize) {
+        let fish = self.cells.iter().filter(|&&cell| cell == FISH).count();
+        let sharks = self.cells.iter().filter(|&&cell| cell == SHARK).count();
+        (fish, sharks)
+    }
+
+    /// A random one of the four cells next to `idx` holding `state`.
+    fn pick_neighbor(&mut self, idx: usize, state: u8) -> Option<usize> {
+        let (width, height) = (self.width, self.height);
+        let (row, col) = (idx / width, idx % width);
+        let around = [
+            (row + height - 1) % height * width + col,
+            (row + 1) % height * width + col,
+            row * width + (col + width - 1) % width,
+            row * width + (col + 1) % width,
+        ];
+        let mut found = [0; 4];
+        let mut count = 0;
+        for neighbor in around {
+            if self.cells[neighbor] == state {
+                found[count] = neighbor;
+                count += 1;
+            }
+        }
+        (count > 0).then(|| found[self.rng.next_u64() as usize % count])
+    }
+
+    /// Move the creature at `from` to `to`, leaving a newborn behind if it is
+    /// old enough to breed.
+    fn swim(&mut self, from: usize, to: usize, breed: u32) {
+        let kind = self.cells[from];
+        self.cells[to] = kind;
+        self.hunger[to] = self.hunger[from];
+        if self.age[from] >= breed {
+            self.age[to] = 0;
+            self.age[from] = 0;
+            self.hunger[from] = 0;
+        } else {
+            self.age[to] = self.age[from];
+            self.cells[from] = WATER;
+        }
+    }
+}
+
+impl Automaton for Wator {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn state(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    fn color(&self, state: u8) -> Option<u32> {
+        Some(if state == FISH { FISH_COLOR } else { SHARK_COLOR })
+    }
+
+    fn step(&mut self) {
+        // Sharks first, each creature once: anything that has moved (or been
+        // born) this step is skipped when the scan reaches it again.
+        let mut moved = vec![false; self.cells.len()];
+        for kind in [SHARK, FISH] {
+            for idx in 0..self.cells.len() {
+                if self.cells[idx] != kind || moved[idx] {
+                    continue;
+                }
+                self.age[idx] += 1;
+                let to = if kind == SHARK {
+                    self.hunger[idx] += 1;
+                    match self.pick_neighbor(idx, FISH) {
+                        Some(prey) => {
+                            self.hunger[idx] = 0;
+                            Some(prey)
+                        }
+                        None if self.hunger[idx] >= self.starve => {
+                            self.cells[idx] = WATER;
+                            continue;
+                        }
+                        None => self.pick_neighbor(idx, WATER),
+                    }
+                } else {
+                    self.pick_neighbor(idx, WATER)
+                };
+                let breed = if kind == SHARK { self.shark_breed } else { self.fish_breed };
+                match to {
+                    Some(to) => {
+                        self.swim(idx, to, breed);
+                        moved[to] = true;
+                        moved[idx] = self.cells[idx] != WATER;
+                    }
+                    None => moved[idx] = true,
+                }
+            }
+        }
+    }
+
+    /// Drop a fish on water, or turn a fish into a shark.
+    fn poke(&mut self, row: usize, col: usize) {
+        let idx = row * self.width + col;
+        self.cells[idx] = match self.cells[idx] {
+            WATER => FISH,
+            _ => SHARK,
+        };
+        self.age[idx] = 0;
+        self.hunger[idx] = 0;
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("fish_breed", 1.0, None),
+            ParamSpec::integer("shark_breed", 1.0, None),
+            ParamSpec::integer("starve", 1.0, None),
+        ]
+    }
+
+    fn param(&self, key: &str) -> f64 {
+        match key {
+            "fish_breed" => self.fish_breed as f64,
+            "shark_breed" => self.shark_breed as f64,
+            "starve" => self.starve as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: f64) {
+        match key {
+            "fish_breed" => self.fish_breed = value as u32,
+            "shark_breed" => self.shark_breed = value as u32,
+            "starve" => self.starve = value as u32,
+            _ => {}
+        }
+    }
+}
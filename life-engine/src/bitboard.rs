@@ -0,0 +1,302 @@
+// Bitboard stepping
+//
+// The fast path behind `Universe::tick` on wrapping and dead edges. Each row
+// is packed into `u64` words, so 64 cells advance at once with full-word
+// boolean arithmetic: the eight neighbour rows (above and below, and all
+// three shifted a cell either way) are summed by carry-save adders into four
+// bit planes of the neighbour count, and the rule keeps the cells whose
+// count is in its birth or survival set. Twisted edges still go cell by
+// cell.
+//
+// The board is also cut into tiles one word wide and `TILE_ROWS` tall. A
+// step reports which tiles changed, and given that report from the step
+// before, the next one copies every tile whose whole neighbourhood stood
+// still: cells that saw the same neighbours as last time turn out the same.
+// Long sparse runs, mostly settled ash, only pay for the few active tiles.
+
+use crate::rule::Rule;
+use crate::universe::{Changes, TickStats};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Rows in a tile; tiles are one word (64 columns) wide.
+const TILE_ROWS: usize = 64;
+
+/// A board packed one bit per cell, row by row; bit `c % 64` of word
+/// `c / 64` is column `c`, and bits past the last column stay clear.
+pub struct Bitboard {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    words: usize,
+    bits: Vec<u64>,
+}
+
+impl Bitboard {
+    pub fn pack(cells: &[bool], width: usize, height: usize) -> Self {
+        let words = width.div_ceil(64);
+        let mut bits = vec![0; words * height];
+        for row in 0..height {
+            for (col, _) in cells[row * width..(row + 1) * width].iter().enumerate().filter(|(_, &alive)| alive) {
+                bits[row * words + col / 64] |= 1 << (col % 64);
+            }
+        }
+        Self { width, height, words, bits }
+    }
+
+    /// Write the board back out one `bool` per cell.
+    pub fn unpack_into(&self, cells: &mut [bool]) {
+        for row in 0..self.height {
+            let words = self.row(row);
+            for (col, cell) in cells[row * self.width..(row + 1) * self.width].iter_mut().enumerate() {
+                *cell = words[col / 64] & (1 << (col % 64)) != 0;
+            }
+        }
+    }
+
+    /// The next generation under `rule`, with edges that wrap or are dead,
+    /// what changed on the way, and which tiles changed. `changed` is that
+    /// last report, from the step that produced this board, if there was one.
+    pub fn step(&self, rule: Rule, wrap: bool, changed: Option<&[bool]>) -> (Bitboard, TickStats, Vec<bool>) {
+        let (birth, survival) = masks(rule);
+        let last = self.last_word_mask();
+        let empty = vec![0; self.words];
+        let mut next = vec![0; self.bits.len()];
+        let mut stats = TickStats::default();
+        // Each row shifted so a cell's west and east neighbours line up with it.
+        let mut shifted = [(); 3].map(|_| [vec![0; self.words], vec![0; self.words]]);
+        let quiet: Vec<bool> = match changed {
+            Some(changed) => (0..changed.len()).map(|tile| self.quiet(tile, changed, wrap)).collect(),
+            None => vec![false; self.tiles()],
+        };
+        let mut now_changed = vec![false; self.tiles()];
+        for row in 0..self.height {
+            let band = row / TILE_ROWS * self.words;
+            let tiles = &quiet[band..band + self.words];
+            if tiles.iter().all(|&quiet| quiet) {
+                next[row * self.words..(row + 1) * self.words].copy_from_slice(self.row(row));
+                stats.population += self.row(row).iter().map(|word| word.count_ones() as usize).sum::<usize>();
+                continue;
+            }
+            let neighbour_row = |row: Option<usize>| row.map_or(&empty[..], |row| self.row(row));
+            let (above, below) = if wrap {
+                (Some((row + self.height - 1) % self.height), Some((row + 1) % self.height))
+            } else {
+                (row.checked_sub(1), (row + 1 < self.height).then_some(row + 1))
+            };
+            let (above, middle, below) = (neighbour_row(above), self.row(row), neighbour_row(below));
+            for (source, [west, east]) in [above, middle, below].into_iter().zip(shifted.iter_mut()) {
+                self.shift_west(source, west, wrap);
+                self.shift_east(source, east, wrap);
+            }
+            let [[w_above, e_above], [w_middle, e_middle], [w_below, e_below]] = &shifted;
+            for word in 0..self.words {
+                if tiles[word] {
+                    next[row * self.words + word] = middle[word];
+                    stats.population += middle[word].count_ones() as usize;
+                    continue;
+                }
+                let [b0, b1, b2, b3] = count_planes([
+                    w_above[word],
+                    above[word],
+                    e_above[word],
+                    w_middle[word],
+                    e_middle[word],
+                    w_below[word],
+                    below[word],
+                    e_below[word],
+                ]);
+                let alive = middle[word];
+                let mut born = 0;
+                for count in 0..=8 {
+                    let eq = plane(b0, count & 1) & plane(b1, count & 2) & plane(b2, count & 4) & plane(b3, count & 8);
+                    if birth & (1 << count) != 0 {
+                        born |= eq & !alive;
+                    }
+                    if survival & (1 << count) != 0 {
+                        born |= eq & alive;
+                    }
+                }
+                if word == self.words - 1 {
+                    born &= last;
+                }
+                next[row * self.words + word] = born;
+                now_changed[band + word] |= born != alive;
+                stats.births += (born & !alive).count_ones() as usize;
+                stats.deaths += (alive & !born).count_ones() as usize;
+                stats.population += born.count_ones() as usize;
+            }
+        }
+        (Bitboard { width: self.width, height: self.height, words: self.words, bits: next }, stats, now_changed)
+    }
+
+    /// Number of tiles, row band by row band.
+    pub fn tiles(&self) -> usize {
+        self.height.div_ceil(TILE_ROWS) * self.words
+    }
+
+    /// Whether neither `tile` nor any tile round it is in `changed`.
+    fn quiet(&self, tile: usize, changed: &[bool], wrap: bool) -> bool {
+        let (bands, across) = (self.height.div_ceil(TILE_ROWS), self.words);
+        let (band, col) = ((tile / across) as isize, (tile % across) as isize);
+        (-1..=1).all(|d_band| {
+            (-1..=1).all(|d_col| {
+                let (mut b, mut c) = (band + d_band, col + d_col);
+                if wrap {
+                    (b, c) = (b.rem_euclid(bands as isize), c.rem_euclid(across as isize));
+                } else if b < 0 || c < 0 || b >= bands as isize || c >= across as isize {
+                    return true;
+                }
+                !changed[b as usize * across + c as usize]
+            })
+        })
+    }
+
+    /// Copy the tiles marked in `changed` out to `cells`, one `bool` per
+    /// cell; the rest are taken to be there already. Cells that flip are
+    /// added to `changes`, if given.
+    pub fn unpack_tiles_into(&self, cells: &mut [bool], changed: &[bool], mut changes: Option<&mut Changes>) {
+        for row in 0..self.height {
+            let band = row / TILE_ROWS * self.words;
+            let words = self.row(row);
+            for (word, _) in changed[band..band + self.words].iter().enumerate().filter(|(_, &changed)| changed) {
+                let cols = word * 64..(word * 64 + 64).min(self.width);
+                for col in cols {
+                    let idx = row * self.width + col;
+                    let alive = words[word] & (1 << (col % 64)) != 0;
+                    if let Some(changes) = changes.as_deref_mut().filter(|_| cells[idx] != alive) {
+                        if alive { changes.born.push(idx) } else { changes.died.push(idx) }
+                    }
+                    cells[idx] = alive;
+                }
+            }
+        }
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.bits[row * self.words..(row + 1) * self.words]
+    }
+
+    /// Bits of the last word of a row that are on the board.
+    fn last_word_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn get(&self, row: &[u64], col: usize) -> u64 {
+        row[col / 64] >> (col % 64) & 1
+    }
+
+    /// `out[c]` = `row[c - 1]`; column 0 gets the last column on a torus.
+    fn shift_west(&self, row: &[u64], out: &mut [u64], wrap: bool) {
+        let mut carry = if wrap { self.get(row, self.width - 1) } else { 0 };
+        for (word, out) in row.iter().zip(out.iter_mut()) {
+            *out = word << 1 | carry;
+            carry = word >> 63;
+        }
+        out[self.words - 1] &= self.last_word_mask();
+    }
+
+    /// `out[c]` = `row[c + 1]`; the last column gets column 0 on a torus.
+    fn shift_east(&self, row: &[u64], out: &mut [u64], wrap: bool) {
+        for i in 0..self.words {
+            out[i] = row[i] >> 1 | row.get(i + 1).map_or(0, |next| next << 63);
+        }
+        if wrap {
+            let col = self.width - 1;
+            out[col / 64] |= self.get(row, 0) << (col % 64);
+        }
+    }
+}
+
+/// Birth and survival sets of `rule` as bitmasks, bit `n` for count `n`.
+fn masks(rule: Rule) -> (u16, u16) {
+    let mask = |alive| (0..=8u8).filter(|&count| rule.next(alive, count)).fold(0, |mask, count| mask | 1 << count);
+    (mask(false), mask(true))
+}
+
+/// `bits` where `set`, its complement where not: one term of "count == n".
+fn plane(bits: u64, set: u16) -> u64 {
+    if set != 0 { bits } else { !bits }
+}
+
+/// `(sum, carry)` of three one-bit inputs, 64 lanes at a time.
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let partial = a ^ b;
+    (partial ^ c, a & b | partial & c)
+}
+
+/// The neighbour count of every lane as four bit planes, lowest first.
+fn count_planes(n: [u64; 8]) -> [u64; 4] {
+    let (ones_a, twos_a) = full_add(n[0], n[1], n[2]);
+    let (ones_b, twos_b) = full_add(n[3], n[4], n[5]);
+    let (ones_c, twos_c) = (n[6] ^ n[7], n[6] & n[7]);
+    let (ones, twos_d) = full_add(ones_a, ones_b, ones_c);
+    let (twos_abc, fours_a) = full_add(twos_a, twos_b, twos_c);
+    let (twos, fours_b) = (twos_abc ^ twos_d, twos_abc & twos_d);
+    [ones, twos, fours_a ^ fours_b, fours_a & fours_b]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::{Boundary, Universe, UniverseBuilder};
+
+    /// Sizes either side of a word and of a tile band.
+    const SIZES: [(usize, usize); 7] = [(1, 1), (3, 70), (63, 5), (64, 64), (65, 129), (130, 3), (200, 66)];
+
+    const RULES: [&str; 4] = ["B3/S23", "B36/S23", "B2/S", "B3678/S34678"];
+
+    /// The next generation cell by cell, as `Universe::inspect` sees it.
+    fn reference(universe: &Universe) -> Vec<bool> {
+        let width = universe.width;
+        (0..universe.cells.len()).map(|idx| universe.inspect(idx / width, idx % width).unwrap().next.is_alive()).collect()
+    }
+
+    fn soup(width: usize, height: usize, boundary: Boundary, rule: &str, seed: u64) -> Universe {
+        let rule = Rule::parse(rule).unwrap();
+        UniverseBuilder::new().size(width, height).boundary(boundary).rule(rule).seed(seed).density(0.35).build()
+    }
+
+    #[test]
+    fn steps_match_neighbour_lookups_on_random_soups() {
+        for (seed, &(width, height)) in SIZES.iter().enumerate() {
+            for rule in RULES {
+                for (boundary, wrap) in [(Boundary::Torus, true), (Boundary::Dead, false)] {
+                    let mut universe = soup(width, height, boundary, rule, seed as u64);
+                    let mut board = Bitboard::pack(&universe.cells, width, height);
+                    let mut changed = None;
+                    // Later steps skip the tiles that settled, from the report.
+                    for generation in 0..12 {
+                        let expected = reference(&universe);
+                        let (next, stats, tiles) = board.step(universe.rule, wrap, changed.as_deref());
+                        let context = format!("{width}×{height} {rule} {boundary:?} generation {generation}");
+                        assert_eq!(stats, universe.tick(), "{context}");
+                        assert_eq!(universe.cells, expected, "{context}");
+                        let mut unpacked = vec![false; width * height];
+                        next.unpack_into(&mut unpacked);
+                        assert_eq!(unpacked, expected, "{context}");
+                        (board, changed) = (next, Some(tiles));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn step_n_matches_neighbour_lookups_on_every_boundary() {
+        for boundary in Boundary::ALL.into_iter().chain([Boundary::Shifted(-2), Boundary::Shifted(5)]) {
+            for (seed, &(width, height)) in SIZES.iter().enumerate() {
+                let mut stepped = soup(width, height, boundary, "B3/S23", seed as u64 + 100);
+                let mut expected = stepped.clone();
+                for _ in 0..20 {
+                    *expected.cells_mut() = reference(&expected);
+                }
+                stepped.step_n(20);
+                assert_eq!(stepped.cells, expected.cells, "{width}×{height} {boundary:?}");
+            }
+        }
+    }
+}
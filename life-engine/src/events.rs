@@ -0,0 +1,86 @@
+// Generation hooks
+//
+// Whatever drives a board – the app's interval, a headless run, a script –
+// knows when a generation has passed; the things that react to it (charts,
+// sonification, recorders) shouldn't each have to watch the board for
+// changes. `GenerationHooks` is the list they subscribe to: the driver calls
+// `emit` once per generation with its number and `TickStats`, and every
+// subscriber is called in the order it subscribed. Renderers and anything
+// syncing the board elsewhere can take the cells that changed instead, with
+// `on_changes`, so they don't diff the whole board again themselves.
+
+use crate::universe::{Changes, TickStats};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Handle for taking a subscription back out again.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HookId(u64);
+
+type Hook = Box<dyn FnMut(u64, TickStats)>;
+type ChangeHook = Box<dyn FnMut(u64, &Changes)>;
+
+#[derive(Default)]
+pub struct GenerationHooks {
+    next_id: u64,
+    hooks: Vec<(HookId, Hook)>,
+    change_hooks: Vec<(HookId, ChangeHook)>,
+}
+
+impl GenerationHooks {
+    /// Call `hook` with the generation number and its stats after every
+    /// generation from now on.
+    pub fn on_generation(&mut self, hook: impl FnMut(u64, TickStats) + 'static) -> HookId {
+        let id = self.next_id();
+        self.hooks.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Call `hook` with the generation number and the cells it changed after
+    /// every generation from now on.
+    pub fn on_changes(&mut self, hook: impl FnMut(u64, &Changes) + 'static) -> HookId {
+        let id = self.next_id();
+        self.change_hooks.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Drop the subscription `id`; `false` if it was already gone.
+    pub fn remove(&mut self, id: HookId) -> bool {
+        let before = self.len();
+        self.hooks.retain(|(hook, _)| *hook != id);
+        self.change_hooks.retain(|(hook, _)| *hook != id);
+        self.len() != before
+    }
+
+    /// Generation `generation` has just been reached with `stats`.
+    pub fn emit(&mut self, generation: u64, stats: TickStats) {
+        for (_, hook) in &mut self.hooks {
+            hook(generation, stats);
+        }
+    }
+
+    /// Generation `generation` has just changed `changes`.
+    pub fn emit_changes(&mut self, generation: u64, changes: &Changes) {
+        for (_, hook) in &mut self.change_hooks {
+            hook(generation, changes);
+        }
+    }
+
+    /// Whether anyone listens for changes, so they're worth working out.
+    pub fn wants_changes(&self) -> bool {
+        !self.change_hooks.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len() + self.change_hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_id(&mut self) -> HookId {
+        self.next_id += 1;
+        HookId(self.next_id - 1)
+    }
+}
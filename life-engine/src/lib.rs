@@ -0,0 +1,26 @@
+// Life engine
+//
+// The simulation core – rules, the seeded RNG, boards and patterns, plus the
+// other automata the app can run, thumbnails of saved grids and hooks for
+// following a run – with no UI in it. With the default `std` feature turned
+// off it is `no_std` and only needs an allocator, so the same engine can
+// drive, say, an LED matrix on a microcontroller. `std` adds the platform
+// seed source, the statistics that need floating-point maths and the
+// hash-based `SparsePlane`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod automata;
+mod bitboard;
+pub mod events;
+pub mod pattern;
+pub mod rng;
+pub mod rule;
+mod simd;
+#[cfg(feature = "std")]
+pub mod sparse;
+pub mod stepper;
+pub mod thumbnail;
+pub mod universe;
@@ -0,0 +1,89 @@
+// Patterns
+//
+// A rectangular patch of cells that isn't tied to a board, plus what's known
+// about it. Reading and writing the file formats lives with the app; this is
+// just the data and how it lands on a `Universe`.
+
+use crate::rule::Rule;
+use crate::universe::{MergeMode, Universe};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// A rectangular patch of cells, independent of any board, with whatever
+/// the file said about it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Pattern {
+    /// Name from the `#N` line, if any.
+    pub name: Option<String>,
+    /// Author from the `#O` line, if any.
+    pub author: Option<String>,
+    /// Free-text comment lines (`#C`, `!`, later `#D`), in file order.
+    pub comments: Vec<String>,
+    /// Rule from the header, if any.
+    pub rule: Option<Rule>,
+    pub width: usize,
+    pub height: usize,
+    /// Row-major, `width * height` long.
+    pub cells: Vec<bool>,
+}
+
+impl Pattern {
+    /// A bare `width × height` pattern with no metadata.
+    pub fn new(width: usize, height: usize, cells: Vec<bool>) -> Self {
+        Self { name: None, author: None, comments: Vec::new(), rule: None, width, height, cells }
+    }
+
+    /// The whole board as a pattern, rule included.
+    pub fn from_universe(universe: &Universe) -> Self {
+        Self { rule: Some(universe.rule), ..Self::new(universe.width, universe.height, universe.cells.clone()) }
+    }
+
+    /// The `rows × cols` block of `universe` starting at `(top, left)`, rule included.
+    pub fn from_region(universe: &Universe, top: usize, left: usize, rows: usize, cols: usize) -> Self {
+        let cells = (top..top + rows)
+            .flat_map(|row| (left..left + cols).map(move |col| row * universe.width + col))
+            .map(|idx| universe.cells[idx])
+            .collect();
+        Self { rule: Some(universe.rule), ..Self::new(cols, rows, cells) }
+    }
+
+    /// The smallest pattern holding every live cell; empty if there are none.
+    pub fn trimmed(&self) -> Self {
+        let live = || (0..self.cells.len()).filter(|&i| self.cells[i]);
+        let (Some(top), Some(bottom)) = (live().map(|i| i / self.width).min(), live().map(|i| i / self.width).max()) else {
+            return Self { width: 0, height: 0, cells: Vec::new(), ..self.clone() };
+        };
+        let left = live().map(|i| i % self.width).min().unwrap_or(0);
+        let right = live().map(|i| i % self.width).max().unwrap_or(0);
+        let (width, height) = (right - left + 1, bottom - top + 1);
+        let cells = (top..=bottom)
+            .flat_map(|row| (left..=right).map(move |col| row * self.width + col))
+            .map(|i| self.cells[i])
+            .collect();
+        Self { width, height, cells, ..self.clone() }
+    }
+
+    /// Top-left corner that centres the pattern on `(row, col)` of a
+    /// `width × height` torus.
+    pub fn origin_centered_on(&self, (row, col): (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        let top = (row + height - self.height / 2 % height) % height;
+        let left = (col + width - self.width / 2 % width) % width;
+        (top, left)
+    }
+
+    /// State of the pattern cell landing on `(row, col)` when stamped at
+    /// `origin` on a `width × height` torus; `None` outside the pattern.
+    pub fn cell_over(&self, origin: (usize, usize), (row, col): (usize, usize), width: usize, height: usize) -> Option<bool> {
+        let d_row = (row + height - origin.0) % height;
+        let d_col = (col + width - origin.1) % width;
+        (d_row < self.height && d_col < self.width).then(|| self.cells[d_row * self.width + d_col])
+    }
+
+    /// Copy the pattern onto `universe` with its top-left at `(top, left)`,
+    /// wrapping round the edges. Dead cells of the pattern overwrite too.
+    pub fn stamp(&self, universe: &mut Universe, top: usize, left: usize) {
+        universe.overlay(self, top, left, MergeMode::Replace);
+    }
+}
+
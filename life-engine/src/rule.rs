@@ -0,0 +1,190 @@
+// Life-like rules
+//
+// Every outer-totalistic two-state rule is a pair of neighbour-count sets in
+// "B/S" notation: a dead cell is **B**orn with a count in the first set, a live
+// cell **S**urvives with a count in the second. Conway's Life is `B3/S23`:
+// * Rule 1: Any live cell with fewer than two live neighbours dies, as if by underpopulation.
+// * Rule 2: Any live cell with two or three live neighbours lives on to the next generation.
+// * Rule 3: Any live cell with more than three live neighbours dies, as if by overpopulation.
+// * Rule 4: Any dead cell with exactly three live neighbours becomes a live cell, as if by reproduction.
+
+use crate::rng::Rng;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Birth/survival neighbour counts stored as bitmasks (bit `n` = count `n`).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+/// The part of a rule that decides a cell's next state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Clause {
+    /// Dead, with a count in the birth set.
+    Birth,
+    /// Dead, with a count outside the birth set.
+    StaysDead,
+    /// Alive, with a count in the survival set.
+    Survival,
+    /// Alive, with a count outside the survival set.
+    Death,
+}
+
+impl Clause {
+    /// Whether the cell is alive after the clause applies.
+    pub fn alive_next(self) -> bool {
+        matches!(self, Clause::Birth | Clause::Survival)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+/// Named rules offered in the rule pickers. The last two are majority votes
+/// over the cell and its eight neighbours: Vote keeps the majority (five or
+/// more of the nine), Anneal flips the result of the 4–5 near-ties, which
+/// lets the boundaries between domains keep shrinking.
+pub const PRESETS: [(&str, &str); 9] = [
+    ("Conway's Life", "B3/S23"),
+    ("HighLife", "B36/S23"),
+    ("Seeds", "B2/S"),
+    ("Day & Night", "B3678/S34678"),
+    ("Life without Death", "B3/S012345678"),
+    ("Maze", "B3/S12345"),
+    ("2×2", "B36/S125"),
+    ("Vote", "B5678/S45678"),
+    ("Anneal", "B4678/S35678"),
+];
+
+impl Rule {
+    pub const CONWAY: Rule = Rule { birth: 1 << 3, survival: 1 << 2 | 1 << 3 };
+
+    /// Parse `B3/S23`-style notation (case-insensitive; `S23/B3` and the
+    /// classic `23/3` survival/birth form are accepted too).
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (first, second) = text
+            .split_once('/')
+            .ok_or_else(|| format!("`{text}` is missing the `/` between birth and survival"))?;
+
+        let (birth, survival) = match (tagged(first, 'B'), tagged(second, 'S')) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            _ => match (tagged(first, 'S'), tagged(second, 'B')) {
+                (Some(survival), Some(birth)) => (birth, survival),
+                // Untagged: the old survival/birth order.
+                _ => (second, first),
+            },
+        };
+        Ok(Self { birth: counts(birth)?, survival: counts(survival)? })
+    }
+
+    /// State of a cell next generation given its state and live-neighbour count.
+    pub fn next(&self, alive: bool, live_neighbors: u8) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        mask & (1 << live_neighbors) != 0
+    }
+
+    /// A random rule with a non-empty birth set. B0 is never picked: under
+    /// it the empty background is itself born, which needs strobing the
+    /// board to display sensibly.
+    pub fn random(rng: &mut Rng) -> Self {
+        loop {
+            let bits = rng.next_u64();
+            let (birth, survival) = (bits as u16 & 0x1FE, (bits >> 16) as u16 & 0x1FF);
+            if birth != 0 {
+                return Self { birth, survival };
+            }
+        }
+    }
+
+    /// Which clause `next` applies to a cell in this state with this count.
+    pub fn clause(&self, alive: bool, live_neighbors: u8) -> Clause {
+        match (alive, self.next(alive, live_neighbors)) {
+            (false, true) => Clause::Birth,
+            (false, false) => Clause::StaysDead,
+            (true, true) => Clause::Survival,
+            (true, false) => Clause::Death,
+        }
+    }
+}
+
+/// `part` without its leading `tag` letter (either case), if it has one.
+fn tagged(part: &str, tag: char) -> Option<&str> {
+    part.strip_prefix(tag).or_else(|| part.strip_prefix(tag.to_ascii_lowercase()))
+}
+
+/// Digits `0..=8` → bitmask.
+fn counts(digits: &str) -> Result<u16, String> {
+    digits.chars().try_fold(0u16, |mask, digit| match digit.to_digit(10) {
+        Some(n) if n <= 8 => Ok(mask | 1 << n),
+        _ => Err(format!("`{digit}` is not a neighbour count (0–8)")),
+    })
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |mask: u16| (0..=8).filter(|n| mask & (1 << n) != 0).map(|n| n.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+// Saved as its B/S string, which stays readable and won't change if the
+// bitmask layout does.
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Rule::parse(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parses_every_notation() {
+        for text in ["B3/S23", "b3/s23", "S23/B3", "23/3", " B3/S23 "] {
+            assert_eq!(Rule::parse(text), Ok(Rule::CONWAY), "{text}");
+        }
+        assert_eq!(Rule::parse("B2/S").map(|rule| rule.to_string()), Ok("B2/S".to_string()));
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!(Rule::parse("B3S23").is_err());
+        assert!(Rule::parse("B39/S23").is_err());
+        assert!(Rule::parse("Bx/S23").is_err());
+    }
+
+    #[test]
+    fn presets_print_as_they_parse() {
+        for (name, text) in PRESETS {
+            assert_eq!(Rule::parse(text).map(|rule| rule.to_string()), Ok(text.to_string()), "{name}");
+        }
+    }
+
+    #[test]
+    fn conway_follows_the_four_rules() {
+        let rule = Rule::CONWAY;
+        for count in 0..=8 {
+            assert_eq!(rule.next(false, count), count == 3, "dead, {count}");
+            assert_eq!(rule.next(true, count), count == 2 || count == 3, "alive, {count}");
+        }
+        assert_eq!(rule.clause(false, 3), Clause::Birth);
+        assert_eq!(rule.clause(true, 4), Clause::Death);
+    }
+}
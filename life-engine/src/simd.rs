@@ -0,0 +1,224 @@
+// SIMD neighbour counts
+//
+// Stepping needs the live-neighbour count of every cell in a row. Rather
+// than pick cells out one at a time, the three rows round it are laid out a
+// byte per cell and added up 16 lanes at a time with SSE2 or wasm
+// `simd128`, or 32 with AVX2. The sparse plane spreads its 64-cell `u64`
+// rows out to bytes for this; the dense board's twisted edges, which the
+// bitboard can't take, build byte rows straight from its cells.
+//
+// On x86-64 AVX2 is picked at runtime when the CPU has it (with `std`; a
+// `no_std` build takes it only when compiled for it). WebAssembly can't
+// detect features at runtime, so wasm builds only take the SIMD path when
+// compiled with `-C target-feature=+simd128`. Anything else uses the plain
+// loop.
+
+/// Columns −1 to 64 of a sparse-plane row, a byte per cell.
+#[cfg(feature = "std")]
+const SPREAD: usize = 66;
+
+/// Live-neighbour counts of columns 0–63, given the rows above, at and
+/// below them with one extra cell either side (bit `c + 1` is column `c`).
+#[cfg(feature = "std")]
+pub fn row_counts(around: &[u128; 3]) -> [u8; 64] {
+    let [above, at, below] = around.map(spread);
+    let mut counts = [0; 64];
+    counts_into([&above, &at, &below], &mut counts);
+    counts
+}
+
+/// The low `SPREAD` bits of `bits`, a byte each.
+#[cfg(feature = "std")]
+fn spread(bits: u128) -> [u8; SPREAD] {
+    let mut bytes = [0; SPREAD];
+    for (chunk, byte) in bytes.chunks_mut(8).zip(bits.to_le_bytes()) {
+        // Copy the byte into every lane, keep bit `i` in lane `i`, then
+        // carry anything left in a lane up to its top bit.
+        let lanes = (byte as u64).wrapping_mul(0x0101_0101_0101_0101) & 0x8040_2010_0804_0201;
+        let lanes = ((lanes + 0x7f7f_7f7f_7f7f_7f7f) >> 7) & 0x0101_0101_0101_0101;
+        chunk.copy_from_slice(&lanes.to_le_bytes()[..chunk.len()]);
+    }
+    bytes
+}
+
+/// Live-neighbour counts of a row of `counts.len()` cells into `counts`,
+/// given the rows above, at and below it a byte per cell, each two longer:
+/// byte `c + 1` is column `c`, from −1 to `counts.len()`.
+pub fn counts_into(rows: [&[u8]; 3], counts: &mut [u8]) {
+    assert!(rows.iter().all(|row| row.len() == counts.len() + 2));
+    let done = lanes(rows, counts);
+    scalar(rows, &mut counts[done..], done);
+}
+
+#[cfg(target_arch = "x86_64")]
+fn lanes(rows: [&[u8]; 3], counts: &mut [u8]) -> usize {
+    if avx2() {
+        // SAFETY: the CPU has AVX2.
+        unsafe { x86::counts_avx2(rows, counts) }
+    } else {
+        // SAFETY: every x86-64 CPU has SSE2.
+        unsafe { x86::counts_sse2(rows, counts) }
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+fn avx2() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+fn avx2() -> bool {
+    cfg!(target_feature = "avx2")
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn lanes(rows: [&[u8]; 3], counts: &mut [u8]) -> usize {
+    wasm::counts(rows, counts)
+}
+
+/// No vector unit: nothing done here, all of it left to `scalar`.
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "wasm32", target_feature = "simd128"))))]
+fn lanes(_rows: [&[u8]; 3], _counts: &mut [u8]) -> usize {
+    0
+}
+
+/// The plain loop, for the counts from column `from` on: each cell's 3×3
+/// block, less the cell itself.
+fn scalar(rows: [&[u8]; 3], counts: &mut [u8], from: usize) {
+    for (count, c) in counts.iter_mut().zip(from..) {
+        *count = rows.iter().map(|row| row[c] + row[c + 1] + row[c + 2]).sum::<u8>() - rows[1][c + 1];
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    /// As many counts as whole vectors cover, returning how many: for each
+    /// cell, the 3×3 block of the three rows less the cell itself.
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn counts_sse2(rows: [&[u8]; 3], counts: &mut [u8]) -> usize {
+        let whole = counts.len() / 16 * 16;
+        for at in (0..whole).step_by(16) {
+            let mut block = _mm_setzero_si128();
+            for row in rows {
+                block = _mm_add_epi8(block, _mm_add_epi8(_mm_add_epi8(load_128(row, at), load_128(row, at + 1)), load_128(row, at + 2)));
+            }
+            _mm_storeu_si128(counts[at..at + 16].as_mut_ptr() as *mut __m128i, _mm_sub_epi8(block, load_128(rows[1], at + 1)));
+        }
+        whole
+    }
+
+    /// `counts_sse2`, 32 lanes at a time.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn counts_avx2(rows: [&[u8]; 3], counts: &mut [u8]) -> usize {
+        let whole = counts.len() / 32 * 32;
+        for at in (0..whole).step_by(32) {
+            let mut block = _mm256_setzero_si256();
+            for row in rows {
+                block = _mm256_add_epi8(block, _mm256_add_epi8(_mm256_add_epi8(load_256(row, at), load_256(row, at + 1)), load_256(row, at + 2)));
+            }
+            _mm256_storeu_si256(counts[at..at + 32].as_mut_ptr() as *mut __m256i, _mm256_sub_epi8(block, load_256(rows[1], at + 1)));
+        }
+        whole
+    }
+
+    /// 16 bytes from `bytes[at..]`.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn load_128(bytes: &[u8], at: usize) -> __m128i {
+        _mm_loadu_si128(bytes[at..at + 16].as_ptr() as *const __m128i)
+    }
+
+    /// 32 bytes from `bytes[at..]`.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn load_256(bytes: &[u8], at: usize) -> __m256i {
+        _mm256_loadu_si256(bytes[at..at + 32].as_ptr() as *const __m256i)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm {
+    use core::arch::wasm32::*;
+
+    /// As `x86::counts_sse2`.
+    pub fn counts(rows: [&[u8]; 3], counts: &mut [u8]) -> usize {
+        // SAFETY: every load and store is of 16 bytes inside its slice.
+        let load = |bytes: &[u8], at: usize| unsafe { v128_load(bytes[at..at + 16].as_ptr() as *const v128) };
+        let whole = counts.len() / 16 * 16;
+        for at in (0..whole).step_by(16) {
+            let mut block = u8x16_splat(0);
+            for row in rows {
+                block = u8x16_add(block, u8x16_add(u8x16_add(load(row, at), load(row, at + 1)), load(row, at + 2)));
+            }
+            unsafe { v128_store(counts[at..at + 16].as_mut_ptr() as *mut v128, u8x16_sub(block, load(rows[1], at + 1))) };
+        }
+        whole
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use alloc::vec::Vec;
+
+    fn random_row(rng: &mut Rng, len: usize) -> Vec<u8> {
+        (0..len).map(|_| rng.gen_bool(0.4) as u8).collect()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn spread_puts_each_bit_in_its_own_byte() {
+        let mut rng = Rng::new(7);
+        for _ in 0..100 {
+            let bits = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            let bytes = spread(bits);
+            for (i, &byte) in bytes.iter().enumerate() {
+                assert_eq!(byte, (bits >> i & 1) as u8);
+            }
+        }
+    }
+
+    #[test]
+    fn vector_counts_match_the_plain_loop() {
+        let mut rng = Rng::new(42);
+        for len in [0, 1, 15, 16, 17, 31, 32, 33, 64, 100, 257] {
+            let rows: Vec<Vec<u8>> = (0..3).map(|_| random_row(&mut rng, len + 2)).collect();
+            let rows = [&rows[0][..], &rows[1][..], &rows[2][..]];
+            let mut expected = alloc::vec![0; len];
+            scalar(rows, &mut expected, 0);
+            let mut counts = alloc::vec![0; len];
+            counts_into(rows, &mut counts);
+            assert_eq!(counts, expected, "{len} cells");
+            #[cfg(target_arch = "x86_64")]
+            {
+                let mut sse2 = alloc::vec![0; len];
+                // SAFETY: every x86-64 CPU has SSE2.
+                let done = unsafe { x86::counts_sse2(rows, &mut sse2) };
+                assert_eq!(sse2[..done], expected[..done], "{len} cells, SSE2");
+                if avx2() {
+                    let mut avx2 = alloc::vec![0; len];
+                    // SAFETY: the CPU has AVX2.
+                    let done = unsafe { x86::counts_avx2(rows, &mut avx2) };
+                    assert_eq!(avx2[..done], expected[..done], "{len} cells, AVX2");
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn row_counts_match_the_bits() {
+        let mut rng = Rng::new(3);
+        for _ in 0..50 {
+            let around: [u128; 3] = core::array::from_fn(|_| ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) & ((1 << SPREAD) - 1));
+            let counts = row_counts(&around);
+            for (c, &count) in counts.iter().enumerate() {
+                let block: u32 = around.iter().map(|row| (row >> c & 0b111).count_ones()).sum();
+                assert_eq!(count as u32, block - (around[1] >> (c + 1) & 1) as u32);
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// Sparse infinite plane
+//
+// Headless runs (soup search, analysis) need a board without edges: a soup
+// that throws gliders would otherwise wrap around the torus and crash into
+// itself. The plane is cut into 64×64 chunks, one bit per cell and one `u64`
+// per chunk row, and only chunks with live cells in them are allocated, so
+// memory follows the occupied area rather than paying a hash entry per cell.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::rule::Rule;
+use crate::simd;
+
+/// Cells along each side of a chunk.
+const CHUNK: i32 = 64;
+
+type Chunk = [u64; CHUNK as usize];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SparsePlane {
+    /// Chunk `(row, col)` → its rows of cells, bit `c` for column `c`.
+    /// Chunks never stay allocated once empty.
+    chunks: HashMap<(i32, i32), Box<Chunk>>,
+    rule: Rule,
+}
+
+impl SparsePlane {
+    pub fn new(cells: impl IntoIterator<Item = (i32, i32)>, rule: Rule) -> Self {
+        let mut plane = Self { chunks: HashMap::new(), rule };
+        for (row, col) in cells {
+            plane.set(row, col, true);
+        }
+        plane
+    }
+
+    /// Whether the cell at `(row, col)` is alive.
+    pub fn get(&self, row: i32, col: i32) -> bool {
+        self.chunks.get(&(row >> 6, col >> 6)).is_some_and(|chunk| chunk[(row & (CHUNK - 1)) as usize] >> (col & (CHUNK - 1)) & 1 != 0)
+    }
+
+    /// Make the cell at `(row, col)` alive or dead. A chunk left empty is
+    /// freed.
+    pub fn set(&mut self, row: i32, col: i32, alive: bool) {
+        let key = (row >> 6, col >> 6);
+        let bit = 1 << (col & (CHUNK - 1));
+        if alive {
+            self.chunks.entry(key).or_insert_with(|| Box::new([0; CHUNK as usize]))[(row & (CHUNK - 1)) as usize] |= bit;
+        } else if let Some(chunk) = self.chunks.get_mut(&key) {
+            chunk[(row & (CHUNK - 1)) as usize] &= !bit;
+            if chunk.iter().all(|&bits| bits == 0) {
+                self.chunks.remove(&key);
+            }
+        }
+    }
+
+    pub fn population(&self) -> usize {
+        self.chunks.values().flat_map(|chunk| chunk.iter()).map(|row| row.count_ones() as usize).sum()
+    }
+
+    /// Live cells as `(row, col)`, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.chunks.iter().flat_map(|(&(chunk_row, chunk_col), chunk)| {
+            chunk.iter().enumerate().flat_map(move |(r, &bits)| {
+                (0..CHUNK)
+                    .filter(move |c| bits & (1 << c) != 0)
+                    .map(move |c| (chunk_row * CHUNK + r as i32, chunk_col * CHUNK + c))
+            })
+        })
+    }
+
+    /// `(min_row, min_col, max_row, max_col)` of the live cells, if any.
+    pub fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        let mut cells = self.cells();
+        let (row, col) = cells.next()?;
+        Some(cells.fold((row, col, row, col), |(r0, c0, r1, c1), (r, c)| {
+            (r0.min(r), c0.min(c), r1.max(r), c1.max(c))
+        }))
+    }
+
+    /// Advance one generation. `B0` rules would fill the whole plane and are
+    /// treated as if birth on zero neighbours were off.
+    pub fn step(&mut self) {
+        // Births can only happen in an allocated chunk or right next to one.
+        let candidates: HashSet<(i32, i32)> = self
+            .chunks
+            .keys()
+            .flat_map(|&(row, col)| (-1..=1).flat_map(move |d_row| (-1..=1).map(move |d_col| (row + d_row, col + d_col))))
+            .collect();
+        let next = candidates
+            .into_iter()
+            .filter_map(|key| {
+                let chunk = self.next_chunk(key);
+                chunk.iter().any(|&row| row != 0).then_some((key, chunk))
+            })
+            .collect();
+        self.chunks = next;
+    }
+
+    /// Chunk `key` one generation on.
+    fn next_chunk(&self, (chunk_row, chunk_col): (i32, i32)) -> Box<Chunk> {
+        let mut next = Box::new([0; CHUNK as usize]);
+        for r in 0..CHUNK {
+            let row = chunk_row * CHUNK + r;
+            let around = [self.wide_row(row - 1, chunk_col), self.wide_row(row, chunk_col), self.wide_row(row + 1, chunk_col)];
+            if around.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+            let counts = simd::row_counts(&around);
+            let mut bits = 0;
+            for c in 0..CHUNK {
+                let alive = around[1] >> (c + 1) & 1 != 0;
+                let count = counts[c as usize];
+                if (alive || count > 0) && self.rule.next(alive, count) {
+                    bits |= 1 << c;
+                }
+            }
+            next[r as usize] = bits;
+        }
+        next
+    }
+
+    /// Row `row` of chunk column `chunk_col` with one cell of each neighbour
+    /// chunk either side: bit `c + 1` holds column `c`, from −1 to 64.
+    fn wide_row(&self, row: i32, chunk_col: i32) -> u128 {
+        let bits = |col: i32| {
+            self.chunks.get(&(row >> 6, col)).map_or(0, |chunk| chunk[(row & (CHUNK - 1)) as usize])
+        };
+        (bits(chunk_col - 1) >> 63) as u128 | (bits(chunk_col) as u128) << 1 | ((bits(chunk_col + 1) & 1) as u128) << 65
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cells either side of the chunk corners round (0, 0) and (64, 64).
+    const EDGES: [(i32, i32); 8] = [(-1, -1), (-1, 0), (0, -1), (0, 0), (63, 63), (63, 64), (64, 63), (64, 64)];
+
+    #[test]
+    fn cells_set_across_chunk_edges_read_back() {
+        for &(row, col) in &EDGES {
+            let mut plane = SparsePlane::new([], Rule::CONWAY);
+            plane.set(row, col, true);
+            for d_row in -1..=1 {
+                for d_col in -1..=1 {
+                    assert_eq!(plane.get(row + d_row, col + d_col), (d_row, d_col) == (0, 0), "({row}, {col}) + ({d_row}, {d_col})");
+                }
+            }
+            assert_eq!(plane.cells().collect::<Vec<_>>(), [(row, col)]);
+        }
+        let plane = SparsePlane::new(EDGES, Rule::CONWAY);
+        assert_eq!(plane.chunks.len(), 7);
+        assert_eq!(plane.population(), EDGES.len());
+        assert_eq!(plane.bounds(), Some((-1, -1, 64, 64)));
+    }
+
+    #[test]
+    fn clearing_the_last_cell_of_a_chunk_frees_it() {
+        let mut plane = SparsePlane::new([(-1, -1), (-1, -2), (5, 5)], Rule::CONWAY);
+        assert_eq!(plane.chunks.len(), 2);
+        plane.set(-1, -1, false);
+        assert_eq!(plane.chunks.len(), 2);
+        plane.set(-1, -2, false);
+        assert_eq!(plane.chunks.len(), 1);
+        assert_eq!((plane.population(), plane.bounds()), (1, Some((5, 5, 5, 5))));
+        // Clearing a dead cell, in a chunk or out of one, changes nothing.
+        plane.set(5, 6, false);
+        plane.set(-100, 7, false);
+        assert_eq!(plane.chunks.len(), 1);
+        plane.set(5, 5, false);
+        assert!(plane.chunks.is_empty());
+        assert_eq!((plane.population(), plane.bounds()), (0, None));
+    }
+
+    #[test]
+    fn gliders_cross_chunk_corners() {
+        // Heading up and left, a cell every four generations, over the
+        // corner at (64, 64) and the one at (0, 0).
+        let glider = [(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)];
+        for start in [66, 2] {
+            let mut plane = SparsePlane::new(glider.map(|(row, col)| (row + start, col + start)), Rule::CONWAY);
+            for moved in 1..=8 {
+                for _ in 0..4 {
+                    plane.step();
+                }
+                let mut cells: Vec<_> = plane.cells().collect();
+                cells.sort_unstable();
+                let at = start - moved;
+                assert_eq!(cells, glider.map(|(row, col)| (row + at, col + at)), "from {start}, moved {moved}");
+            }
+            assert_eq!(plane.chunks.len(), 1, "from {start}");
+        }
+    }
+}
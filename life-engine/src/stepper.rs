@@ -0,0 +1,102 @@
+// Tile-skipping stepper
+//
+// `Universe::tick` starts from scratch every generation, so it can't know
+// which parts of the board just stood still. A `Stepper` steps a board
+// generation after generation and keeps the packed board and the tiles that
+// changed between calls, so each tick only recomputes tiles with activity
+// around them and only writes those back. Anything else touching the board
+// in between (an edit, a new soup, a different rule) is spotted by the
+// board's revision or its settings no longer being what the stepper left
+// there, and the next tick starts from scratch again. The same tiles are all
+// that need looking at for the cells a tick changed, which renderers can ask
+// for to redraw just those.
+
+use crate::bitboard::Bitboard;
+use crate::rule::Rule;
+use crate::universe::{Boundary, Changes, TickStats, Universe};
+use alloc::vec::Vec;
+
+/// What the last tick left behind.
+struct Last {
+    /// The board's revision as the tick left it.
+    revision: u64,
+    /// Its size, rule and boundary then.
+    settings: (usize, usize, Rule, Boundary),
+    packed: Bitboard,
+    changed: Vec<bool>,
+}
+
+#[derive(Default)]
+pub struct Stepper {
+    last: Option<Last>,
+}
+
+impl Stepper {
+    /// Advance `universe` one generation, as `Universe::tick` does.
+    pub fn tick(&mut self, universe: &mut Universe) -> TickStats {
+        self.step(universe, None)
+    }
+
+    /// `tick`, and list the cells it changed in `changes` (cleared first).
+    /// Only the tiles that changed are looked at for them.
+    pub fn tick_with_changes(&mut self, universe: &mut Universe, changes: &mut Changes) -> TickStats {
+        changes.clear();
+        self.step(universe, Some(changes))
+    }
+
+    fn step(&mut self, universe: &mut Universe, changes: Option<&mut Changes>) -> TickStats {
+        let Some(wrap) = universe.bitboard_wrap() else {
+            self.last = None;
+            let Some(changes) = changes else { return universe.tick() };
+            let before = universe.cells.clone();
+            let stats = universe.tick();
+            *changes = Changes::between(&before, &universe.cells);
+            return stats;
+        };
+        let settings = (universe.width, universe.height, universe.rule, universe.boundary);
+        let last = self.last.take().filter(|last| last.revision == universe.revision && last.settings == settings);
+        let (packed, changed) = match last {
+            Some(last) => (last.packed, Some(last.changed)),
+            None => (Bitboard::pack(&universe.cells, universe.width, universe.height), None),
+        };
+        let (next, stats, now_changed) = packed.step(universe.rule, wrap, changed.as_deref());
+        next.unpack_tiles_into(universe.cells_mut(), &now_changed, changes);
+        self.last = Some(Last { revision: universe.revision, settings, packed: next, changed: now_changed });
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::{CellState, UniverseBuilder};
+
+    fn soup(seed: u64) -> Universe {
+        UniverseBuilder::new().size(100, 70).seed(seed).density(0.3).build()
+    }
+
+    #[test]
+    fn notices_the_board_changing_between_ticks() {
+        let mut stepper = Stepper::default();
+        let mut stepped = soup(1);
+        let mut ticked = stepped.clone();
+        for generation in 0..30 {
+            match generation % 10 {
+                3 => {
+                    stepped.toggle(10, 10).unwrap();
+                    ticked.toggle(10, 10).unwrap();
+                }
+                5 => {
+                    stepped.cells_mut()[69 * 100 + 99] = true;
+                    ticked.set(69, 99, CellState::Alive).unwrap();
+                }
+                // A whole new board of the same size.
+                7 => (stepped, ticked) = (soup(generation), soup(generation)),
+                9 => (stepped.boundary, ticked.boundary) = (Boundary::Dead, Boundary::Dead),
+                _ => {}
+            }
+            assert_eq!(stepper.tick(&mut stepped), ticked.tick(), "generation {generation}");
+            assert_eq!(stepped, ticked, "generation {generation}");
+        }
+    }
+}
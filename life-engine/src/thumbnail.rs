@@ -0,0 +1,64 @@
+// Thumbnails
+//
+// Small pictures of boards and patterns for lists of saved things. The grid
+// is scaled to fit a square `size` pixels across, whole-number magnified if
+// it is smaller and shrunk if it is larger, and each pixel is shaded by the
+// share of the cells under it that are alive, so a glider still reads as a
+// glider and a big soup as a grey texture. Turning the shades into an image
+// file is left to the formats crate.
+
+use alloc::vec::Vec;
+
+use crate::pattern::Pattern;
+use crate::universe::Universe;
+
+/// A rasterised grid.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Thumbnail {
+    pub width: usize,
+    pub height: usize,
+    /// Share of each pixel's cells that are alive, `0` to `255`, row-major.
+    pub shades: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// `cells`, a `width × height` row-major grid, fitted into `size × size`
+    /// pixels. Empty grids give a single dead pixel.
+    pub fn new(cells: &[bool], width: usize, height: usize, size: usize) -> Self {
+        let (width, height, size) = (width.max(1), height.max(1), size.max(1));
+        let side = width.max(height);
+        // Magnify by a whole factor, or shrink to fit, keeping the aspect.
+        let (pixels_wide, pixels_high) = if side <= size {
+            let factor = size / side;
+            (width * factor, height * factor)
+        } else {
+            ((width * size).div_ceil(side), (height * size).div_ceil(side))
+        };
+        let alive = |row: usize, col: usize| cells.get(row * width + col).copied().unwrap_or(false);
+        // Cells `span(pixel)` of an axis `cells` long drawn `pixels` across.
+        let span = |pixel: usize, cells: usize, pixels: usize| {
+            let start = pixel * cells / pixels;
+            start..((pixel + 1) * cells / pixels).max(start + 1)
+        };
+
+        let mut shades = Vec::with_capacity(pixels_wide * pixels_high);
+        for y in 0..pixels_high {
+            let rows = span(y, height, pixels_high);
+            for x in 0..pixels_wide {
+                let cols = span(x, width, pixels_wide);
+                let total = rows.len() * cols.len();
+                let live = rows.clone().flat_map(|row| cols.clone().map(move |col| (row, col))).filter(|&(row, col)| alive(row, col)).count();
+                shades.push((live * 255 / total) as u8);
+            }
+        }
+        Self { width: pixels_wide, height: pixels_high, shades }
+    }
+
+    pub fn of_pattern(pattern: &Pattern, size: usize) -> Self {
+        Self::new(&pattern.cells, pattern.width, pattern.height, size)
+    }
+
+    pub fn of_universe(universe: &Universe, size: usize) -> Self {
+        Self::new(&universe.cells, universe.width, universe.height, size)
+    }
+}
@@ -0,0 +1,862 @@
+use crate::bitboard::Bitboard;
+use crate::pattern::Pattern;
+use crate::rng::Rng;
+use crate::rule::Rule;
+use crate::simd;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use serde::{Deserialize, Serialize};
+
+/// What lies beyond the board's edges.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Boundary {
+    /// Edges wrap round: the board is a torus.
+    #[default]
+    Torus,
+    /// Everything past the edges is permanently dead.
+    Dead,
+    /// Left and right wrap as on a torus; top and bottom wrap mirrored, so
+    /// whatever crosses them comes back on the other side of the board.
+    Klein,
+    /// Both pairs of edges wrap mirrored: the real projective plane.
+    #[serde(rename = "cross-surface")]
+    CrossSurface,
+    /// A torus whose left and right edges join `k` rows apart, so whatever
+    /// leaves the right edge comes back `k` rows further down (Golly's
+    /// `T+k` topologies).
+    Shifted(i32),
+}
+
+impl Boundary {
+    pub const ALL: [Boundary; 5] =
+        [Boundary::Torus, Boundary::Dead, Boundary::Klein, Boundary::CrossSurface, Boundary::Shifted(1)];
+
+    /// Stable identifier used as the `<option>` value and in saves.
+    pub fn key(self) -> &'static str {
+        match self {
+            Boundary::Torus => "torus",
+            Boundary::Dead => "dead",
+            Boundary::Klein => "klein",
+            Boundary::CrossSurface => "cross-surface",
+            Boundary::Shifted(_) => "shifted",
+        }
+    }
+
+    /// The board cell that `(row, col)`, possibly past the edges of a
+    /// `width × height` board, stands for; `None` past a dead edge.
+    pub fn wrap(self, row: isize, col: isize, width: usize, height: usize) -> Option<(usize, usize)> {
+        let (width, height) = (width as isize, height as isize);
+        let (mut r, mut c) = (row.rem_euclid(height), col.rem_euclid(width));
+        // An odd number of trips across a twisted edge leaves things mirrored.
+        let (across_rows, across_cols) = (row.div_euclid(height) % 2 != 0, col.div_euclid(width) % 2 != 0);
+        match self {
+            Boundary::Torus => {}
+            Boundary::Dead if r != row || c != col => return None,
+            Boundary::Dead => {}
+            Boundary::Klein => {
+                if across_rows {
+                    c = width - 1 - c;
+                }
+            }
+            Boundary::CrossSurface => {
+                if across_rows {
+                    c = width - 1 - c;
+                }
+                if across_cols {
+                    r = height - 1 - r;
+                }
+            }
+            Boundary::Shifted(k) => r = (row + col.div_euclid(width) * k as isize).rem_euclid(height),
+        }
+        Some((r as usize, c as usize))
+    }
+
+    /// The boundary saved as `key`. A shifted torus comes back with a
+    /// shift of 1.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|boundary| boundary.key() == key).unwrap_or_default()
+    }
+}
+
+/// State of a single cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CellState {
+    #[default]
+    Dead,
+    Alive,
+}
+
+impl CellState {
+    pub fn is_alive(self) -> bool {
+        self == CellState::Alive
+    }
+
+    /// The opposite state.
+    pub fn toggled(self) -> Self {
+        match self {
+            CellState::Dead => CellState::Alive,
+            CellState::Alive => CellState::Dead,
+        }
+    }
+}
+
+impl From<bool> for CellState {
+    fn from(alive: bool) -> Self {
+        if alive { CellState::Alive } else { CellState::Dead }
+    }
+}
+
+/// A `(row, col)` that isn't on the board. The accessors never wrap: a
+/// caller that wants the torus has to wrap the coordinates itself.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OutOfBounds {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl core::fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "cell ({}, {}) is off the board", self.row, self.col)
+    }
+}
+
+/// How [`Universe::overlay`] combines a pattern with the cells under it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MergeMode {
+    /// The pattern's cells, dead ones included, overwrite the board.
+    #[default]
+    Replace,
+    /// Alive where either is alive.
+    Or,
+    /// Alive where both are alive.
+    And,
+    /// Alive where exactly one is alive.
+    Xor,
+}
+
+impl MergeMode {
+    pub const ALL: [MergeMode; 4] = [MergeMode::Replace, MergeMode::Or, MergeMode::And, MergeMode::Xor];
+
+    /// Stable identifier used as the `<option>` value.
+    pub fn key(self) -> &'static str {
+        match self {
+            MergeMode::Replace => "replace",
+            MergeMode::Or => "or",
+            MergeMode::And => "and",
+            MergeMode::Xor => "xor",
+        }
+    }
+
+    /// The mode whose `key` is `key`; anything unknown is `Replace`.
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|mode| mode.key() == key).unwrap_or_default()
+    }
+
+    /// The new state of a cell that was `board` when `pattern` lands on it.
+    pub fn apply(self, board: bool, pattern: bool) -> bool {
+        match self {
+            MergeMode::Replace => pattern,
+            MergeMode::Or => board || pattern,
+            MergeMode::And => board && pattern,
+            MergeMode::Xor => board != pattern,
+        }
+    }
+}
+
+/// A cell-by-cell set operation between two boards, for [`Universe::combine`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SetOp {
+    /// Alive on either board.
+    Union,
+    /// Alive on both boards.
+    Intersection,
+    /// Alive on this board but not the other.
+    Difference,
+    /// Alive on exactly one board.
+    Xor,
+}
+
+impl SetOp {
+    pub const ALL: [SetOp; 4] = [SetOp::Union, SetOp::Intersection, SetOp::Difference, SetOp::Xor];
+
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            SetOp::Union => a || b,
+            SetOp::Intersection => a && b,
+            SetOp::Difference => a && !b,
+            SetOp::Xor => a != b,
+        }
+    }
+}
+
+// Universe model
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Universe {
+    pub width: usize,
+    pub height: usize,
+    /// Flattened 2‑D grid – `true` = alive, `false` = dead. Read it freely,
+    /// but write through [`Universe::cells_mut`], so the change is noticed.
+    pub cells: Vec<bool>,
+    /// Birth/survival rule applied by `tick`.
+    pub rule: Rule,
+    /// What the edge cells see beyond the board.
+    pub boundary: Boundary,
+    /// Seed the initial soup was generated from.
+    pub seed: u64,
+    /// Changes whenever the cells do, to a number no other board has had,
+    /// so a `Stepper` can tell its board was left alone without comparing
+    /// every cell.
+    #[serde(skip, default = "next_revision")]
+    pub(crate) revision: u64,
+}
+
+/// Revisions handed out so far, across every board.
+static REVISIONS: AtomicU64 = AtomicU64::new(0);
+
+fn next_revision() -> u64 {
+    REVISIONS.fetch_add(1, Ordering::Relaxed)
+}
+
+// The revision is bookkeeping, not part of the board.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        (self.width, self.height, self.rule, self.boundary, self.seed) == (other.width, other.height, other.rule, other.boundary, other.seed)
+            && self.cells == other.cells
+    }
+}
+
+/// Step-by-step construction of a [`Universe`]. Anything left unset keeps
+/// its default: 128×128, Conway's rule, a torus, a fresh random seed and a
+/// 50 % soup.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct UniverseBuilder {
+    width: usize,
+    height: usize,
+    rule: Rule,
+    boundary: Boundary,
+    seed: Option<u64>,
+    density: f64,
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> Self {
+        Self { width: 128, height: 128, rule: Rule::CONWAY, boundary: Boundary::Torus, seed: None, density: 0.5 }
+    }
+}
+
+impl UniverseBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn size(self, width: usize, height: usize) -> Self {
+        Self { width, height, ..self }
+    }
+
+    pub fn rule(self, rule: Rule) -> Self {
+        Self { rule, ..self }
+    }
+
+    pub fn boundary(self, boundary: Boundary) -> Self {
+        Self { boundary, ..self }
+    }
+
+    /// Seed for the soup; the same seed and density always give the same
+    /// cells, whatever the rule.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { seed: Some(seed), ..self }
+    }
+
+    /// Chance of each cell starting alive, `0.0` for an empty board.
+    pub fn density(self, density: f64) -> Self {
+        Self { density: density.clamp(0.0, 1.0), ..self }
+    }
+
+    pub fn build(self) -> Universe {
+        #[cfg(feature = "std")]
+        let seed = self.seed.unwrap_or_else(random_seed);
+        // No platform RNG to draw from: unseeded soups all use seed 0.
+        #[cfg(not(feature = "std"))]
+        let seed = self.seed.unwrap_or_default();
+        let mut rng = Rng::new(seed);
+        let cells = (0..self.width * self.height).map(|_| rng.gen_bool(self.density)).collect();
+        self.with_cells(cells, seed)
+    }
+
+    /// A board with `cells`, one per cell row by row, rather than a soup;
+    /// the seed is kept for the record. Panics if `cells` doesn't fit the
+    /// size.
+    pub fn build_with(self, cells: Vec<bool>, seed: u64) -> Universe {
+        assert_eq!(cells.len(), self.width * self.height, "cells don't fit a {}×{} board", self.width, self.height);
+        self.with_cells(cells, seed)
+    }
+
+    fn with_cells(self, cells: Vec<bool>, seed: u64) -> Universe {
+        Universe { width: self.width, height: self.height, cells, rule: self.rule, boundary: self.boundary, seed, revision: next_revision() }
+    }
+}
+
+/// One cell as [`Universe::inspect`] sees it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CellInfo {
+    pub row: usize,
+    pub col: usize,
+    pub state: CellState,
+    pub live_neighbors: u8,
+    /// The state the next `tick` gives the cell.
+    pub next: CellState,
+}
+
+/// What happened during one call to [`Universe::tick`].
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct TickStats {
+    /// Dead cells that came alive.
+    pub births: usize,
+    /// Live cells that died.
+    pub deaths: usize,
+    /// Live cells after the tick.
+    pub population: usize,
+}
+
+/// Which cells one generation changed, as indices into
+/// [`Universe::cells`], each list in increasing order.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Changes {
+    pub born: Vec<usize>,
+    pub died: Vec<usize>,
+}
+
+impl Changes {
+    /// Every cell that differs between two boards of the same size, the
+    /// slow way.
+    pub fn between(before: &[bool], after: &[bool]) -> Self {
+        let mut changes = Self::default();
+        for (idx, (&was, &now)) in before.iter().zip(after).enumerate() {
+            match (was, now) {
+                (false, true) => changes.born.push(idx),
+                (true, false) => changes.died.push(idx),
+                _ => {}
+            }
+        }
+        changes
+    }
+
+    /// The same cells on a board grown from `width` to `new_width` columns
+    /// by `top` rows above and `left` columns to the left.
+    pub fn moved(&mut self, width: usize, new_width: usize, top: usize, left: usize) {
+        for idx in self.born.iter_mut().chain(&mut self.died) {
+            *idx = (*idx / width + top) * new_width + *idx % width + left;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.born.clear();
+        self.died.clear();
+    }
+}
+
+// Cross‑platform *seed* helper
+// * `#[cfg(target_arch = "wasm32")]` — the item *below* the attribute
+//   is **compiled only** when the *current* `--target` triple’s
+//   `target_arch` field equals `"wasm32"` (that is, you are building
+//   for `wasm32‑unknown‑unknown` or `wasm32‑wasi`).
+// * `#[cfg(not(target_arch = "wasm32"))]` — the inverse: compile this
+//   item for every other architecture (x86_64, aarch64, etc.).
+//
+// Because the two functions share the same *symbol* (`random_seed`) but
+// live behind **mutually‑exclusive** `#[cfg]` gates, exactly **one** of
+// them is present in the final binary; the other is discarded at compile‑time.
+//
+// * **Web**→ `Math.random()` scaled to 53 bits  (fast, no‑std)
+// * **Native**→ `rand::Rng::gen::<u64>()` (OS RNG)
+//
+// The seed then drives the portable [`Rng`], so a soup can be replayed.
+// Both need `std`; without it there is no `random_seed` at all.
+
+/// Fresh seed for [`UniverseBuilder::seed`], drawn from the platform RNG.
+#[cfg(all(feature = "std", target_arch = "wasm32"))]
+pub fn random_seed() -> u64 {
+    (js_sys::Math::random() * (1u64 << 53) as f64) as u64
+}
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub fn random_seed() -> u64 {
+    use rand::Rng;
+    rand::thread_rng().gen()
+}
+
+impl Universe {
+    fn index(&self, row: usize, col: usize) -> Result<usize, OutOfBounds> {
+        if row < self.height && col < self.width {
+            Ok(row * self.width + col)
+        } else {
+            Err(OutOfBounds { row, col })
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Result<CellState, OutOfBounds> {
+        Ok(self.cells[self.index(row, col)?].into())
+    }
+
+    /// The cells to write to, one per cell row by row. Going through here
+    /// gives the board a new revision.
+    pub fn cells_mut(&mut self) -> &mut Vec<bool> {
+        self.revision = next_revision();
+        &mut self.cells
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, state: CellState) -> Result<(), OutOfBounds> {
+        let idx = self.index(row, col)?;
+        self.cells_mut()[idx] = state.is_alive();
+        Ok(())
+    }
+
+    /// The cell's state, its live neighbours (counted the way `tick` counts
+    /// them) and what the rule makes of it next generation.
+    pub fn inspect(&self, row: usize, col: usize) -> Result<CellInfo, OutOfBounds> {
+        let state = self.get(row, col)?;
+        let live_neighbors = self.live_neighbor_count(row, col);
+        let next = self.rule.next(state.is_alive(), live_neighbors).into();
+        Ok(CellInfo { row, col, state, live_neighbors, next })
+    }
+
+    /// Flip the cell, returning its new state.
+    pub fn toggle(&mut self, row: usize, col: usize) -> Result<CellState, OutOfBounds> {
+        let state = self.get(row, col)?.toggled();
+        self.set(row, col, state)?;
+        Ok(state)
+    }
+
+    /// Combine `pattern` into the board with its top-left at `(row, col)`,
+    /// wrapping round the edges. Only the pattern's bounding box is touched.
+    pub fn overlay(&mut self, pattern: &Pattern, row: usize, col: usize, mode: MergeMode) {
+        let (width, height) = (self.width, self.height);
+        let cells = self.cells_mut();
+        for (i, &alive) in pattern.cells.iter().enumerate() {
+            let (r, c) = ((row + i / pattern.width) % height, (col + i % pattern.width) % width);
+            let idx = r * width + c;
+            cells[idx] = mode.apply(cells[idx], alive);
+        }
+    }
+
+    /// Move every cell `dx` columns right and `dy` rows down. Cells wrap
+    /// round the way the boundary joins the edges; past a dead edge they're
+    /// lost.
+    pub fn shift(&mut self, dx: isize, dy: isize) {
+        let mut next = vec![false; self.cells.len()];
+        for (row, col) in self.live_cells() {
+            let Some((row, col)) = self.boundary.wrap(row as isize + dy, col as isize + dx, self.width, self.height) else { continue };
+            next[row * self.width + col] = true;
+        }
+        *self.cells_mut() = next;
+    }
+
+    /// `(row, col)` of every live cell, row by row.
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(|(idx, _)| (idx / self.width, idx % self.width))
+    }
+
+    /// 64-bit FNV-1a hash of the dimensions and the live cells' positions,
+    /// row by row. It depends only on what's on the board, so it stays the
+    /// same across builds and storage layouts; rule, boundary and seed
+    /// aren't part of it.
+    pub fn state_hash(&self) -> u64 {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        let words = [self.width as u64, self.height as u64]
+            .into_iter()
+            .chain(self.live_cells().flat_map(|(row, col)| [row as u64, col as u64]));
+        words.fold(OFFSET, |hash, word| {
+            word.to_le_bytes().iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+        })
+    }
+
+    /// Number of live cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+
+    /// `(top, left, bottom, right)` of the live cells, inclusive; `None` on
+    /// an empty board. Edges aren't unwrapped, so a pattern straddling the
+    /// torus seam spans the whole board.
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let mut cells = self.live_cells();
+        let (row, col) = cells.next()?;
+        Some(cells.fold((row, col, row, col), |(top, left, bottom, right), (row, col)| {
+            (top.min(row), left.min(col), bottom.max(row), right.max(col))
+        }))
+    }
+
+    /// Shrink (or grow) the board to the live cells plus `padding` dead
+    /// cells on every side. Returns `false`, leaving the board alone, when
+    /// it is empty.
+    pub fn crop_to_bounding_box(&mut self, padding: usize) -> bool {
+        let Some((top, left, bottom, right)) = self.bounding_box() else { return false };
+        let width = right - left + 1 + 2 * padding;
+        let height = bottom - top + 1 + 2 * padding;
+        let mut cells = vec![false; width * height];
+        for (row, col) in self.live_cells() {
+            cells[(row - top + padding) * width + (col - left + padding)] = true;
+        }
+        (self.width, self.height) = (width, height);
+        *self.cells_mut() = cells;
+        true
+    }
+
+    /// Grow a dead-edged board by `margin` cells on every side with live
+    /// cells on its outermost row or column, so that nothing gets clipped,
+    /// but never past `max_side` cells across. Returns the rows and columns
+    /// added above and to the left, or `None` when the board kept its size
+    /// (always, on boundaries whose edges join up).
+    pub fn grow_to_fit(&mut self, margin: usize, max_side: usize) -> Option<(usize, usize)> {
+        if self.boundary != Boundary::Dead {
+            return None;
+        }
+        let (top, left, bottom, right) = self.bounding_box()?;
+        let (spare_cols, spare_rows) = (max_side.saturating_sub(self.width), max_side.saturating_sub(self.height));
+        let add_left = if left == 0 { margin.min(spare_cols) } else { 0 };
+        let add_right = if right == self.width - 1 { margin.min(spare_cols - add_left) } else { 0 };
+        let add_top = if top == 0 { margin.min(spare_rows) } else { 0 };
+        let add_bottom = if bottom == self.height - 1 { margin.min(spare_rows - add_top) } else { 0 };
+        if add_left + add_right + add_top + add_bottom == 0 {
+            return None;
+        }
+        let (width, height) = (self.width + add_left + add_right, self.height + add_top + add_bottom);
+        let mut cells = vec![false; width * height];
+        for (row, col) in self.live_cells() {
+            cells[(row + add_top) * width + col + add_left] = true;
+        }
+        (self.width, self.height) = (width, height);
+        *self.cells_mut() = cells;
+        Some((add_top, add_left))
+    }
+
+    /// Shift the live cells so their bounding box sits in the middle of the
+    /// board. Returns `false` when there is nothing to move.
+    pub fn center(&mut self) -> bool {
+        let Some((top, left, bottom, right)) = self.bounding_box() else { return false };
+        let dx = ((self.width - (right - left + 1)) / 2) as isize - left as isize;
+        let dy = ((self.height - (bottom - top + 1)) / 2) as isize - top as isize;
+        self.shift(dx, dy);
+        dx != 0 || dy != 0
+    }
+
+    /// Advance one generation according to the universe's rule, reporting the churn.
+    pub fn tick(&mut self) -> TickStats {
+        let mut next = vec![false; self.cells.len()];
+        let stats = self.next_into(&mut next);
+        *self.cells_mut() = next;
+        stats
+    }
+
+    /// Advance `n` generations in one call, reusing a single scratch board.
+    /// Births and deaths are totals over all `n`; the population is the final one.
+    pub fn step_n(&mut self, n: u64) -> TickStats {
+        let mut total = TickStats { population: self.population(), ..Default::default() };
+        // Stay packed for the whole run on the fast path.
+        if let Some(wrap) = self.bitboard_wrap() {
+            let mut board = Bitboard::pack(&self.cells, self.width, self.height);
+            let mut changed = None;
+            for _ in 0..n {
+                let (next, stats, tiles) = board.step(self.rule, wrap, changed.as_deref());
+                (board, changed) = (next, Some(tiles));
+                total.births += stats.births;
+                total.deaths += stats.deaths;
+                total.population = stats.population;
+            }
+            board.unpack_into(self.cells_mut());
+            return total;
+        }
+        let mut next = vec![false; self.cells.len()];
+        for _ in 0..n {
+            let stats = self.next_into(&mut next);
+            core::mem::swap(self.cells_mut(), &mut next);
+            total.births += stats.births;
+            total.deaths += stats.deaths;
+            total.population = stats.population;
+        }
+        total
+    }
+
+    /// Whether the edges wrap, when the board can take the bitboard fast
+    /// path; `None` for twisted edges (and empty boards), which go cell by
+    /// cell.
+    pub(crate) fn bitboard_wrap(&self) -> Option<bool> {
+        match self.boundary {
+            _ if self.cells.is_empty() => None,
+            Boundary::Torus => Some(true),
+            Boundary::Dead => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Write the next generation into `next`, which must be as long as `cells`.
+    fn next_into(&self, next: &mut [bool]) -> TickStats {
+        if let Some(wrap) = self.bitboard_wrap() {
+            let (board, stats, _) = Bitboard::pack(&self.cells, self.width, self.height).step(self.rule, wrap, None);
+            board.unpack_into(next);
+            return stats;
+        }
+        // A row at a time: the rows above, at and below as bytes, counted
+        // with SIMD.
+        let mut stats = TickStats::default();
+        if self.cells.is_empty() {
+            return stats;
+        }
+        let mut rows = [vec![0; self.width + 2], vec![0; self.width + 2], vec![0; self.width + 2]];
+        let mut counts = vec![0; self.width];
+        self.byte_row(-1, &mut rows[0]);
+        self.byte_row(0, &mut rows[1]);
+        for row in 0..self.height {
+            self.byte_row(row as isize + 1, &mut rows[2]);
+            simd::counts_into([&rows[0], &rows[1], &rows[2]], &mut counts);
+            for (col, &live_neighbors) in counts.iter().enumerate() {
+                let idx = row * self.width + col;
+                next[idx] = self.rule.next(self.cells[idx], live_neighbors);
+                match (self.cells[idx], next[idx]) {
+                    (false, true) => stats.births += 1,
+                    (true, false) => stats.deaths += 1,
+                    _ => {}
+                }
+                stats.population += next[idx] as usize;
+            }
+            rows.rotate_left(1);
+        }
+        stats
+    }
+
+    /// Row `row` a byte per cell into `out`, columns −1 to `width`, with
+    /// whatever the boundary puts past the edges (and `row` itself may be
+    /// just off the board).
+    fn byte_row(&self, row: isize, out: &mut [u8]) {
+        let (width, height) = (self.width, self.height);
+        let cell = |col: isize| self.boundary.wrap(row, col, width, height).is_some_and(|(r, c)| self.cells[r * width + c]) as u8;
+        if (0..height as isize).contains(&row) {
+            let start = row as usize * width;
+            for (byte, &alive) in out[1..=width].iter_mut().zip(&self.cells[start..start + width]) {
+                *byte = alive as u8;
+            }
+            out[0] = cell(-1);
+            out[width + 1] = cell(width as isize);
+        } else {
+            for (byte, col) in out.iter_mut().zip(-1..) {
+                *byte = cell(col);
+            }
+        }
+    }
+
+    /// Cell-by-cell comparison: `true` wherever `self` and `other` disagree.
+    /// `None` when the two boards have different dimensions.
+    pub fn diff(&self, other: &Universe) -> Option<Vec<bool>> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+        Some(self.cells.iter().zip(&other.cells).map(|(a, b)| a != b).collect())
+    }
+
+    /// This board with `op` applied cell by cell against `other`; rule,
+    /// boundary and seed are kept from `self`. `None` when the two boards
+    /// have different dimensions.
+    pub fn combine(&self, other: &Universe, op: SetOp) -> Option<Universe> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+        let cells = self.cells.iter().zip(&other.cells).map(|(&a, &b)| op.apply(a, b)).collect();
+        Some(Universe { cells, revision: next_revision(), ..self.clone() })
+    }
+
+    /// Shannon entropy of the non-overlapping 2×2 blocks, normalised to `0..=1`.
+    ///
+    /// An empty or uniformly striped board scores 0; noise scores close to 1.
+    /// Needs `std` for the logarithm.
+    #[cfg(feature = "std")]
+    pub fn block_entropy(&self) -> f64 {
+        let mut counts = [0usize; 16];
+        for row in (0..self.height - self.height % 2).step_by(2) {
+            for col in (0..self.width - self.width % 2).step_by(2) {
+                let idx = row * self.width + col;
+                let block = self.cells[idx] as usize
+                    | (self.cells[idx + 1] as usize) << 1
+                    | (self.cells[idx + self.width] as usize) << 2
+                    | (self.cells[idx + self.width + 1] as usize) << 3;
+                counts[block] += 1;
+            }
+        }
+        let blocks: usize = counts.iter().sum();
+        if blocks == 0 {
+            return 0.0;
+        }
+        let bits: f64 = counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / blocks as f64;
+                -p * p.log2()
+            })
+            .sum();
+        bits / 4.0
+    }
+
+    /// Count the live neighbours around `(row, col)`, as the boundary sees them.
+    fn live_neighbor_count(&self, row: usize, col: usize) -> u8 {
+        match self.boundary {
+            Boundary::Torus => self
+                .neighbor_indices(row, col)
+                .iter()
+                .map(|&idx| self.cells[idx] as u8) // Add 1 if true (alive), 0 if false (dead)
+                .sum(),
+            // Only the neighbours actually on the board can be alive.
+            Boundary::Dead => {
+                let rows = row.saturating_sub(1)..=(row + 1).min(self.height - 1);
+                rows.flat_map(|r| (col.saturating_sub(1)..=(col + 1).min(self.width - 1)).map(move |c| (r, c)))
+                    .filter(|&cell| cell != (row, col))
+                    .map(|(r, c)| self.cells[r * self.width + c] as u8)
+                    .sum()
+            }
+            // Twisted or shifted edges: look each neighbour up the long way.
+            Boundary::Klein | Boundary::CrossSurface | Boundary::Shifted(_) => {
+                let (row, col) = (row as isize, col as isize);
+                let around = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+                around
+                    .iter()
+                    .filter_map(|&(d_row, d_col)| self.boundary.wrap(row + d_row, col + d_col, self.width, self.height))
+                    .map(|(r, c)| self.cells[r * self.width + c] as u8)
+                    .sum()
+            }
+        }
+    }
+
+    /// Indices of the eight neighbours around `(row, col)` (edges wrap,
+    /// whatever the boundary).
+    pub fn neighbor_indices(&self, row: usize, col: usize) -> [usize; 8] {
+        let mut indices = [0; 8];
+        let mut n = 0;
+        for delta_row in [self.height - 1, 0, 1].iter().cloned() {
+            for delta_col in [self.width - 1, 0, 1].iter().cloned() {
+                // Skip the cell itself
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+                let neighbor_row = (row + delta_row) % self.height;
+                let neighbor_col = (col + delta_col) % self.width;
+                indices[n] = neighbor_row * self.width + neighbor_col;
+                n += 1;
+            }
+        }
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty `width × height` board with `live` cells.
+    fn board(width: usize, height: usize, boundary: Boundary, live: &[(usize, usize)]) -> Universe {
+        let mut universe = UniverseBuilder::new().size(width, height).boundary(boundary).seed(0).density(0.0).build();
+        for &(row, col) in live {
+            universe.set(row, col, CellState::Alive).unwrap();
+        }
+        universe
+    }
+
+    fn live(universe: &Universe) -> Vec<(usize, usize)> {
+        universe.live_cells().collect()
+    }
+
+    const GLIDER: [(usize, usize); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+    #[test]
+    fn block_is_still() {
+        let mut universe = board(6, 6, Boundary::Torus, &[(2, 2), (2, 3), (3, 2), (3, 3)]);
+        let before = universe.clone();
+        let stats = universe.tick();
+        assert_eq!(universe, before);
+        assert_eq!(stats, TickStats { births: 0, deaths: 0, population: 4 });
+    }
+
+    #[test]
+    fn blinker_has_period_two() {
+        let mut universe = board(5, 5, Boundary::Dead, &[(2, 1), (2, 2), (2, 3)]);
+        let before = universe.clone();
+        universe.tick();
+        assert_eq!(live(&universe), [(1, 2), (2, 2), (3, 2)]);
+        universe.tick();
+        assert_eq!(universe, before);
+    }
+
+    #[test]
+    fn glider_moves_one_cell_diagonally_every_four_generations() {
+        let mut universe = board(8, 8, Boundary::Torus, &GLIDER);
+        universe.step_n(4);
+        let moved: Vec<_> = GLIDER.iter().map(|&(row, col)| (row + 1, col + 1)).collect();
+        assert_eq!(live(&universe), moved);
+    }
+
+    #[test]
+    fn glider_wraps_round_a_torus() {
+        let mut universe = board(8, 8, Boundary::Torus, &GLIDER);
+        let before = universe.clone();
+        // Eight cells diagonally is a full trip.
+        universe.step_n(32);
+        assert_eq!(universe, before);
+    }
+
+    #[test]
+    fn dead_edges_stop_a_glider() {
+        let mut universe = board(8, 8, Boundary::Dead, &GLIDER);
+        universe.step_n(40);
+        // It ends as a block in the far corner.
+        assert_eq!(live(&universe), [(6, 6), (6, 7), (7, 6), (7, 7)]);
+    }
+
+    #[test]
+    fn boundaries_wrap_as_documented() {
+        let (width, height) = (5, 4);
+        assert_eq!(Boundary::Torus.wrap(-1, -1, width, height), Some((3, 4)));
+        assert_eq!(Boundary::Dead.wrap(-1, 2, width, height), None);
+        assert_eq!(Boundary::Dead.wrap(1, 2, width, height), Some((1, 2)));
+        // Over the top of a Klein bottle comes back mirrored.
+        assert_eq!(Boundary::Klein.wrap(-1, 0, width, height), Some((3, 4)));
+        assert_eq!(Boundary::Klein.wrap(1, 5, width, height), Some((1, 0)));
+        assert_eq!(Boundary::CrossSurface.wrap(0, 5, width, height), Some((3, 0)));
+        assert_eq!(Boundary::Shifted(1).wrap(0, 5, width, height), Some((1, 0)));
+        assert_eq!(Boundary::Shifted(1).wrap(0, -1, width, height), Some((3, 4)));
+        for boundary in [Boundary::Torus, Boundary::Dead, Boundary::Klein, Boundary::CrossSurface, Boundary::Shifted(2)] {
+            assert_eq!(boundary.wrap(2, 3, width, height), Some((2, 3)), "{boundary:?}");
+        }
+    }
+
+    #[test]
+    fn twisted_edges_match_neighbour_lookups() {
+        for boundary in [Boundary::Klein, Boundary::CrossSurface, Boundary::Shifted(3)] {
+            let mut universe = UniverseBuilder::new().size(19, 7).boundary(boundary).seed(5).density(0.4).build();
+            for _ in 0..10 {
+                let expected: Vec<bool> =
+                    (0..universe.cells.len()).map(|idx| universe.inspect(idx / 19, idx % 19).unwrap().next.is_alive()).collect();
+                universe.tick();
+                assert_eq!(universe.cells, expected, "{boundary:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn step_n_adds_up_the_ticks() {
+        let mut stepped = UniverseBuilder::new().size(20, 20).seed(11).build();
+        let mut ticked = stepped.clone();
+        let total = stepped.step_n(5);
+        let (mut births, mut deaths) = (0, 0);
+        for _ in 0..5 {
+            let stats = ticked.tick();
+            (births, deaths) = (births + stats.births, deaths + stats.deaths);
+        }
+        assert_eq!(stepped, ticked);
+        assert_eq!(total, TickStats { births, deaths, population: ticked.population() });
+    }
+}
@@ -0,0 +1,210 @@
+// Zip archives
+//
+// Pattern collections such as Golly's come as zip files. This reads the
+// central directory and unpacks entries one at a time, so a caller can report
+// progress between them. Only what pattern archives use is supported: stored
+// and deflated entries, no encryption, no zip64.
+
+use crate::inflate::inflate;
+use crate::png::crc32;
+
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+/// End-of-directory record without its comment.
+const END_LEN: usize = 22;
+/// Largest entry unpacked, whatever its header claims. Pattern files are
+/// rarely more than a few hundred KiB.
+pub const MAX_ENTRY: usize = 16 << 20;
+
+/// One file in an archive.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, `/`-separated.
+    pub name: String,
+    method: u16,
+    crc: u32,
+    compressed: usize,
+    /// Size once unpacked, as the directory gives it.
+    pub size: usize,
+    /// Where the entry's local header starts.
+    offset: usize,
+}
+
+/// An open zip archive.
+pub struct Archive<'a> {
+    bytes: &'a [u8],
+    pub entries: Vec<ArchiveEntry>,
+}
+
+impl<'a> Archive<'a> {
+    /// Read the list of files in `bytes`. Directories are left out.
+    pub fn open(bytes: &'a [u8]) -> Result<Self, String> {
+        // The end record is last, after a comment of up to 64 KiB.
+        let end = (0..=bytes.len().saturating_sub(END_LEN))
+            .rev()
+            .take(u16::MAX as usize + 1)
+            .find(|&at| u32_at(bytes, at, 0) == Some(END_OF_DIRECTORY))
+            .ok_or("not a zip archive")?;
+        let count = u16_at(bytes, end, 10).ok_or("truncated zip archive")? as usize;
+        let mut at = u32_at(bytes, end, 16).ok_or("truncated zip archive")? as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if u32_at(bytes, at, 0) != Some(DIRECTORY_ENTRY) {
+                return Err("damaged zip directory".to_string());
+            }
+            let field = |offset: usize| u16_at(bytes, at, offset).map(usize::from).ok_or("truncated zip archive");
+            let wide = |offset: usize| u32_at(bytes, at, offset).ok_or("truncated zip archive");
+            let (flags, method) = (field(8)?, field(10)? as u16);
+            let (crc, compressed, size) = (wide(16)?, wide(20)?, wide(24)?);
+            let (name_len, extra_len, comment_len) = (field(28)?, field(30)?, field(32)?);
+            let offset = wide(42)?;
+            let name = slice(bytes, at, 46, name_len).ok_or("truncated zip archive")?;
+            let name = String::from_utf8_lossy(name).into_owned();
+            if flags & 1 != 0 {
+                return Err(format!("`{name}` is encrypted"));
+            }
+            if compressed == u32::MAX || size == u32::MAX || offset == u32::MAX {
+                return Err("zip64 archives aren't supported".to_string());
+            }
+            if !name.ends_with('/') {
+                let (compressed, size, offset) = (compressed as usize, size as usize, offset as usize);
+                entries.push(ArchiveEntry { name, method, crc, compressed, size, offset });
+            }
+            at = [46, name_len, extra_len, comment_len].into_iter().try_fold(at, usize::checked_add).ok_or("damaged zip directory")?;
+        }
+        Ok(Self { bytes, entries })
+    }
+
+    /// The contents of `entry`, checked against its size and CRC. Entries
+    /// over `MAX_ENTRY` are refused, and so is one that unpacks to more
+    /// than its size says, as soon as it does.
+    pub fn read(&self, entry: &ArchiveEntry) -> Result<Vec<u8>, String> {
+        if entry.size > MAX_ENTRY {
+            return Err(format!("`{}` is bigger than {} MiB", entry.name, MAX_ENTRY >> 20));
+        }
+        let at = entry.offset;
+        if u32_at(self.bytes, at, 0) != Some(LOCAL_HEADER) {
+            return Err(format!("`{}` is damaged", entry.name));
+        }
+        let name_len = u16_at(self.bytes, at, 26).ok_or("truncated zip archive")? as usize;
+        let extra_len = u16_at(self.bytes, at, 28).ok_or("truncated zip archive")? as usize;
+        let data = slice(self.bytes, at, 30 + name_len + extra_len, entry.compressed).ok_or("truncated zip archive")?;
+        let contents = match entry.method {
+            0 => data.to_vec(),
+            8 => inflate(data, entry.size).map_err(|err| format!("`{}`: {err}", entry.name))?,
+            other => return Err(format!("`{}` uses unsupported compression method {other}", entry.name)),
+        };
+        if contents.len() != entry.size || crc32(&contents) != entry.crc {
+            return Err(format!("`{}` is damaged", entry.name));
+        }
+        Ok(contents)
+    }
+}
+
+/// `len` bytes from `offset` past `at`, or `None` if that runs off the end.
+/// Offsets come from the archive itself, so the sums are checked: with a
+/// 32-bit `usize` (wasm) they can overflow.
+fn slice(bytes: &[u8], at: usize, offset: usize, len: usize) -> Option<&[u8]> {
+    let start = at.checked_add(offset)?;
+    bytes.get(start..start.checked_add(len)?)
+}
+
+fn u16_at(bytes: &[u8], at: usize, offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(slice(bytes, at, offset, 2)?.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], at: usize, offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(slice(bytes, at, offset, 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &[u8] = b"x = 3, y = 3\nbo$2bo$3o!\n";
+
+    /// A zip of `files`, each `(name, method, stored bytes, unpacked bytes)`.
+    fn zip(files: &[(&str, u16, &[u8], &[u8])]) -> Vec<u8> {
+        let (mut bytes, mut directory) = (Vec::new(), Vec::new());
+        for &(name, method, data, contents) in files {
+            let offset = bytes.len() as u32;
+            let fields = |record: &mut Vec<u8>| {
+                record.extend_from_slice(&[20, 0, 0, 0]);
+                record.extend_from_slice(&method.to_le_bytes());
+                record.extend_from_slice(&[0; 4]);
+                record.extend_from_slice(&crc32(contents).to_le_bytes());
+                record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+                record.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+                record.extend_from_slice(&(name.len() as u16).to_le_bytes());
+                record.extend_from_slice(&[0, 0]);
+            };
+            bytes.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+            fields(&mut bytes);
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(data);
+            directory.extend_from_slice(&DIRECTORY_ENTRY.to_le_bytes());
+            directory.extend_from_slice(&[20, 0]);
+            fields(&mut directory);
+            directory.extend_from_slice(&[0; 10]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+        let start = bytes.len() as u32;
+        bytes.extend_from_slice(&directory);
+        bytes.extend_from_slice(&END_OF_DIRECTORY.to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&start.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn reads_stored_and_deflated_entries() {
+        // `bo$2bo$3o!` in one fixed-Huffman block.
+        let deflated = [0x4b, 0xca, 0x57, 0x31, 0x4a, 0xca, 0x57, 0x31, 0xce, 0x57, 0x04, 0x00];
+        let bytes = zip(&[("glider.rle", 0, GLIDER, GLIDER), ("ships/", 0, b"", b""), ("ships/glider.rle", 8, &deflated, b"bo$2bo$3o!")]);
+        let archive = Archive::open(&bytes).unwrap();
+        let names: Vec<&str> = archive.entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["glider.rle", "ships/glider.rle"]);
+        assert_eq!(archive.read(&archive.entries[0]).as_deref(), Ok(GLIDER));
+        assert_eq!(archive.read(&archive.entries[1]).as_deref(), Ok(&b"bo$2bo$3o!"[..]));
+    }
+
+    #[test]
+    fn refuses_a_bad_central_directory() {
+        let bytes = zip(&[("glider.rle", 0, GLIDER, GLIDER)]);
+        let directory = bytes.len() - END_LEN - 56;
+        assert!(Archive::open(&bytes[..bytes.len() - 1]).is_err());
+        let mut damaged = bytes.clone();
+        damaged[directory] ^= 0xff;
+        assert!(Archive::open(&damaged).is_err());
+        // More entries than the directory holds.
+        let mut short = bytes.clone();
+        short[bytes.len() - END_LEN + 10] = 2;
+        assert!(Archive::open(&short).is_err());
+        // A directory, or an entry, said to start past the end.
+        let mut far = bytes.clone();
+        far[bytes.len() - 6..bytes.len() - 2].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Archive::open(&far).is_err());
+        let mut far = bytes.clone();
+        far[directory + 42..directory + 46].copy_from_slice(&(u32::MAX - 1).to_le_bytes());
+        let archive = Archive::open(&far).unwrap();
+        assert!(archive.read(&archive.entries[0]).is_err());
+    }
+
+    #[test]
+    fn refuses_damaged_entries() {
+        let mut bytes = zip(&[("glider.rle", 0, GLIDER, GLIDER)]);
+        bytes[30 + "glider.rle".len()] ^= 1;
+        let archive = Archive::open(&bytes).unwrap();
+        assert!(archive.read(&archive.entries[0]).is_err());
+        let bytes = zip(&[("glider.rle", 9, GLIDER, GLIDER)]);
+        let archive = Archive::open(&bytes).unwrap();
+        assert!(archive.read(&archive.entries[0]).is_err());
+    }
+}
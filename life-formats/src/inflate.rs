@@ -0,0 +1,266 @@
+// DEFLATE decoding
+//
+// Just enough of RFC 1951 to unpack zip archives: stored, fixed-Huffman and
+// dynamic-Huffman blocks. Codes are decoded canonically, a bit at a time, as
+// in zlib's `puff`; slower than a table-driven decoder, but pattern archives
+// are small.
+
+/// Most bits in a code.
+const MAX_BITS: usize = 15;
+/// Base lengths and extra bits of length symbols 257–285.
+const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+/// Base distances and extra bits of distance symbols 0–29.
+const DISTANCE_BASE: [u16; 30] =
+    [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+/// Order the code-length code lengths are sent in.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decompress a raw DEFLATE stream, failing as soon as it comes to more
+/// than `limit` bytes: a few KiB can inflate to gigabytes.
+pub fn inflate(data: &[u8], limit: usize) -> Result<Vec<u8>, String> {
+    let mut bits = Bits { data, pos: 0, buffer: 0, count: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => stored(&mut bits, &mut out, limit)?,
+            1 => {
+                let (lengths, distances) = fixed();
+                codes(&mut bits, &mut out, &lengths, &distances, limit)?;
+            }
+            2 => {
+                let (lengths, distances) = dynamic(&mut bits)?;
+                codes(&mut bits, &mut out, &lengths, &distances, limit)?;
+            }
+            _ => return Err("bad deflate block type".to_string()),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+/// Reads the stream least significant bit first.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn take(&mut self, need: u32) -> Result<u32, String> {
+        while self.count < need {
+            let byte = *self.data.get(self.pos).ok_or("deflate stream ends early")?;
+            self.pos += 1;
+            self.buffer |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1 << need) - 1);
+        self.buffer >>= need;
+        self.count -= need;
+        Ok(value)
+    }
+}
+
+/// A canonical Huffman code: how many codes there are of each length, and
+/// the symbols in code order.
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        // Over-subscribed codes can't be decoded; incomplete ones are allowed.
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = left * 2 - count as i32;
+            if left < 0 {
+                return Err("bad Huffman code".to_string());
+            }
+        }
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; offsets[MAX_BITS + 1] as usize];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16, String> {
+        // First code and first symbol index of the current length.
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..=MAX_BITS {
+            code |= bits.take(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("bad Huffman code".to_string())
+    }
+}
+
+fn stored(bits: &mut Bits, out: &mut Vec<u8>, limit: usize) -> Result<(), String> {
+    // Skip to the byte boundary; whole bytes still buffered go back.
+    bits.pos -= (bits.count / 8) as usize;
+    bits.buffer = 0;
+    bits.count = 0;
+    let ends_early = || "deflate stream ends early".to_string();
+    let start = bits.pos.checked_add(4).ok_or_else(ends_early)?;
+    let header = bits.data.get(bits.pos..start).ok_or_else(ends_early)?;
+    let len = u16::from_le_bytes([header[0], header[1]]);
+    if !len != u16::from_le_bytes([header[2], header[3]]) {
+        return Err("bad stored block length".to_string());
+    }
+    if out.len().checked_add(len as usize).is_none_or(|total| total > limit) {
+        return Err(too_long(limit));
+    }
+    let end = start.checked_add(len as usize).ok_or_else(ends_early)?;
+    out.extend_from_slice(bits.data.get(start..end).ok_or_else(ends_early)?);
+    bits.pos = end;
+    Ok(())
+}
+
+fn fixed() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    let lengths = Huffman::new(&lengths).expect("the fixed code is valid");
+    let distances = Huffman::new(&[5; 30]).expect("the fixed code is valid");
+    (lengths, distances)
+}
+
+fn dynamic(bits: &mut Bits) -> Result<(Huffman, Huffman), String> {
+    let literals = bits.take(5)? as usize + 257;
+    let distances = bits.take(5)? as usize + 1;
+    let code_lengths = bits.take(4)? as usize + 4;
+    let mut lengths = [0u8; 19];
+    for &position in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[position] = bits.take(3)? as u8;
+    }
+    let code = Huffman::new(&lengths)?;
+
+    let mut lengths = vec![0u8; literals + distances];
+    let mut at = 0;
+    while at < lengths.len() {
+        let symbol = code.decode(bits)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (*lengths.get(at.wrapping_sub(1)).ok_or("repeat with no previous length")?, 3 + bits.take(2)?),
+            17 => (0, 3 + bits.take(3)?),
+            _ => (0, 11 + bits.take(7)?),
+        };
+        let end = at.checked_add(repeat as usize).ok_or("too many code lengths")?;
+        lengths.get_mut(at..end).ok_or("too many code lengths")?.fill(value);
+        at = end;
+    }
+    if lengths[256] == 0 {
+        return Err("no end-of-block code".to_string());
+    }
+    Ok((Huffman::new(&lengths[..literals])?, Huffman::new(&lengths[literals..])?))
+}
+
+/// Decode literals and back-references until the end of the block.
+fn codes(bits: &mut Bits, out: &mut Vec<u8>, lengths: &Huffman, distances: &Huffman, limit: usize) -> Result<(), String> {
+    loop {
+        let symbol = lengths.decode(bits)? as usize;
+        match symbol {
+            0..=255 if out.len() == limit => return Err(too_long(limit)),
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                let base = *LENGTH_BASE.get(index).ok_or("bad length code")? as usize;
+                let length = base + bits.take(LENGTH_EXTRA[index] as u32)? as usize;
+                let index = distances.decode(bits)? as usize;
+                let base = *DISTANCE_BASE.get(index).ok_or("bad distance code")? as usize;
+                let distance = base + bits.take(DISTANCE_EXTRA[index] as u32)? as usize;
+                if distance > out.len() {
+                    return Err("distance reaches before the start".to_string());
+                }
+                if out.len().checked_add(length).is_none_or(|total| total > limit) {
+                    return Err(too_long(limit));
+                }
+                // Byte by byte: the copy may overlap what it is writing.
+                let start = out.len() - distance;
+                for offset in 0..length {
+                    out.push(out[start + offset]);
+                }
+            }
+        }
+    }
+}
+
+fn too_long(limit: usize) -> String {
+    format!("inflates to more than {limit} bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `bo$2bo$3o!` in one fixed-Huffman block.
+    const FIXED: [u8; 12] = [0x4b, 0xca, 0x57, 0x31, 0x4a, 0xca, 0x57, 0x31, 0xce, 0x57, 0x04, 0x00];
+    /// `$b3$$b222b2$333b332b3b$` in one dynamic-Huffman block.
+    const DYNAMIC: [u8; 23] =
+        [0x05, 0xc1, 0x81, 0x00, 0x00, 0x00, 0x00, 0x83, 0x30, 0x99, 0x08, 0x9e, 0x51, 0xfe, 0x10, 0xdf, 0x48, 0x68, 0x5b, 0x43, 0x4d, 0x97, 0x71];
+
+    #[test]
+    fn stored_blocks() {
+        // Two blocks, the first not final.
+        let stream = [0, 3, 0, 0xfc, 0xff, b'b', b'o', b'$', 1, 2, 0, 0xfd, 0xff, b'o', b'!'];
+        assert_eq!(inflate(&stream, 100).as_deref(), Ok(&b"bo$o!"[..]));
+        assert!(inflate(&[1, 3, 0, 0xfc, 0xfe, b'b', b'o', b'$'], 100).is_err());
+    }
+
+    #[test]
+    fn fixed_huffman_blocks() {
+        assert_eq!(inflate(&FIXED, 100).as_deref(), Ok(&b"bo$2bo$3o!"[..]));
+    }
+
+    #[test]
+    fn dynamic_huffman_blocks() {
+        assert_eq!(inflate(&DYNAMIC, 100).as_deref(), Ok(&b"$b3$$b222b2$333b332b3b$"[..]));
+    }
+
+    #[test]
+    fn refuses_truncated_streams() {
+        for stream in [&FIXED[..], &DYNAMIC[..], &[1, 3, 0, 0xfc, 0xff, b'b'][..]] {
+            for len in 0..stream.len() - 1 {
+                assert!(inflate(&stream[..len], 100).is_err(), "{len} of {} bytes", stream.len());
+            }
+        }
+    }
+
+    #[test]
+    fn refuses_back_references_before_the_start() {
+        // A fixed block opening with a length-3, distance-1 copy.
+        assert_eq!(inflate(&[0x03, 0x02], 100), Err("distance reaches before the start".to_string()));
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        assert_eq!(inflate(&FIXED, 9), Err(too_long(9)));
+        assert!(inflate(&[1, 3, 0, 0xfc, 0xff, b'b', b'o', b'$'], 2).is_err());
+        assert!(inflate(&DYNAMIC, 23).is_ok());
+    }
+}
@@ -0,0 +1,98 @@
+// JavaScript API
+//
+// With the `js-api` feature, wasm-bindgen exports the engine to plain web
+// pages that don't use Dioxus at all:
+//
+//     import init, { Universe } from "./life_formats.js";
+//     await init();
+//     const board = Universe.fromRle("bo$2bo$3o!", 64, 64);
+//     board.tick();
+//     board.cells(); // Uint8Array, row by row, 1 = alive
+//
+// `wasm-pack build life-formats --target web --features js-api` writes that
+// module to `life-formats/pkg/`.
+
+use crate::PatternText;
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+use life_engine::universe::{Boundary, CellState, UniverseBuilder};
+use wasm_bindgen::prelude::*;
+
+/// A board, as seen from JavaScript.
+#[wasm_bindgen(js_name = Universe)]
+pub struct JsUniverse {
+    universe: life_engine::universe::Universe,
+}
+
+#[wasm_bindgen(js_class = Universe)]
+impl JsUniverse {
+    /// An empty `width × height` torus under `rule` (B/S notation).
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize, rule: &str) -> Result<JsUniverse, JsError> {
+        let rule = Rule::parse(rule).map_err(|err| JsError::new(&err))?;
+        let universe = UniverseBuilder::new().size(width, height).rule(rule).seed(0).density(0.0).build();
+        Ok(Self { universe })
+    }
+
+    /// An RLE pattern centred on an empty `width × height` torus, under the
+    /// pattern's own rule (Conway's when it names none).
+    #[wasm_bindgen(js_name = fromRle)]
+    pub fn from_rle(rle: &str, width: usize, height: usize) -> Result<JsUniverse, JsError> {
+        let pattern = Pattern::parse_rle(rle).map_err(|err| JsError::new(&err))?;
+        let rule = pattern.rule.unwrap_or_default();
+        let mut universe = UniverseBuilder::new().size(width, height).rule(rule).seed(0).density(0.0).build();
+        let (top, left) = pattern.origin_centered_on((height / 2, width / 2), width, height);
+        pattern.stamp(&mut universe, top, left);
+        Ok(Self { universe })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.universe.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.universe.height
+    }
+
+    /// Whether cells past the edges count as dead instead of wrapping round.
+    #[wasm_bindgen(js_name = setDeadEdges)]
+    pub fn set_dead_edges(&mut self, dead: bool) {
+        self.universe.boundary = if dead { Boundary::Dead } else { Boundary::Torus };
+    }
+
+    /// Advance one generation, returning the new population.
+    pub fn tick(&mut self) -> usize {
+        self.universe.tick().population
+    }
+
+    /// Advance `n` generations, returning the final population.
+    pub fn step(&mut self, n: u32) -> usize {
+        self.universe.step_n(n as u64).population
+    }
+
+    pub fn population(&self) -> usize {
+        self.universe.population()
+    }
+
+    /// Whether `(row, col)` is alive; `false` off the board.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.universe.get(row, col).is_ok_and(CellState::is_alive)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, alive: bool) -> Result<(), JsError> {
+        self.universe.set(row, col, alive.into()).map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    /// Every cell, row by row, `1` for alive.
+    pub fn cells(&self) -> Vec<u8> {
+        self.universe.cells.iter().map(|&alive| alive as u8).collect()
+    }
+
+    /// The whole board as RLE.
+    #[wasm_bindgen(js_name = toRle)]
+    pub fn to_rle(&self) -> String {
+        Pattern::from_universe(&self.universe).to_rle()
+    }
+}
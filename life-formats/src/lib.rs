@@ -0,0 +1,33 @@
+// Life formats
+//
+// Reading and writing patterns and boards: the classic text formats (RLE,
+// plaintext, Life 1.05/1.06) in `text`, the compact binary snapshots in
+// `packed`, PNG thumbnails in `png`, and zip archives of pattern files in
+// `archive`. Depends on the engine for the types, on nothing UI. The `js-api`
+// feature adds JavaScript bindings for pages that only want the engine.
+
+pub mod archive;
+mod inflate;
+mod text;
+
+#[cfg(feature = "js-api")]
+pub mod js;
+pub mod packed;
+pub mod png;
+
+pub use text::{is_pattern_file, parse_file, parse_text, PatternText};
+
+/// Widest or tallest board any reader here accepts, in cells.
+pub const MAX_SIDE: usize = 8192;
+/// Most cells a board read here may have; files claiming more are refused
+/// before anything is allocated for them.
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// `Err` unless a `width` × `height` board is within the limits.
+pub fn check_size(width: usize, height: usize) -> Result<(), String> {
+    if width > MAX_SIDE || height > MAX_SIDE || width * height > MAX_CELLS {
+        return Err(format!("{width}×{height} is bigger than {MAX_SIDE}×{MAX_SIDE} or {MAX_CELLS} cells"));
+    }
+    Ok(())
+}
+
@@ -0,0 +1,215 @@
+// Packed board snapshots
+//
+// A compact binary form of a `Universe`, for storage and for pasting into
+// chat or a URL. As JSON a 512×512 board is a quarter of a million `true`s
+// and `false`s; packed, a mostly-empty board is a few hundred bytes.
+//
+//   "GOLP"  magic
+//   u8      format version (1)
+//   u32 LE  width, u32 LE height
+//   u64 LE  seed
+//   u8      boundary (0 = torus, 1 = dead, 2 = Klein bottle, 3 = cross-surface,
+//           4 = shifted torus, followed by the shift as i32 LE)
+//   u8      rule length, then the rule as B/S text
+//   ...     cells, one bit each (row-major, low bit first), PackBits-compressed
+//
+// PackBits: a control byte `n` below 128 is followed by `n + 1` literal bytes;
+// `n` from 129 up repeats the one byte after it `257 - n` times. Empty space
+// packs to long runs of zero bytes.
+//
+// `to_text`/`from_text` wrap the bytes in URL-safe base64.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use life_engine::rule::Rule;
+use life_engine::universe::{Boundary, Universe, UniverseBuilder};
+
+const MAGIC: &[u8; 4] = b"GOLP";
+const VERSION: u8 = 1;
+
+pub fn encode(universe: &Universe) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    out.extend((universe.width as u32).to_le_bytes());
+    out.extend((universe.height as u32).to_le_bytes());
+    out.extend(universe.seed.to_le_bytes());
+    match universe.boundary {
+        Boundary::Torus => out.push(0),
+        Boundary::Dead => out.push(1),
+        Boundary::Klein => out.push(2),
+        Boundary::CrossSurface => out.push(3),
+        Boundary::Shifted(k) => {
+            out.push(4);
+            out.extend(k.to_le_bytes());
+        }
+    }
+    let rule = universe.rule.to_string();
+    out.push(rule.len() as u8);
+    out.extend(rule.as_bytes());
+    out.extend(pack_bits(&bit_pack(&universe.cells)));
+    out
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Universe, String> {
+    let mut reader = Reader(bytes);
+    if reader.take(4)? != MAGIC {
+        return Err("not a packed board".to_string());
+    }
+    match reader.byte()? {
+        VERSION => {}
+        version => return Err(format!("packed board version {version} is newer than this build")),
+    }
+    let width = u32::from_le_bytes(reader.array()?) as usize;
+    let height = u32::from_le_bytes(reader.array()?) as usize;
+    let seed = u64::from_le_bytes(reader.array()?);
+    let boundary = match reader.byte()? {
+        0 => Boundary::Torus,
+        1 => Boundary::Dead,
+        2 => Boundary::Klein,
+        3 => Boundary::CrossSurface,
+        4 => Boundary::Shifted(i32::from_le_bytes(reader.array()?)),
+        other => return Err(format!("unknown boundary {other}")),
+    };
+    let rule_len = reader.byte()? as usize;
+    let rule = std::str::from_utf8(reader.take(rule_len)?).map_err(|_| "rule isn't text".to_string())?;
+    let rule = Rule::parse(rule)?;
+
+    crate::check_size(width, height)?;
+    let cells = width * height;
+    let packed = unpack_bits(reader.0, cells.div_ceil(8))?;
+    let cells = (0..cells).map(|i| packed[i / 8] & (1 << (i % 8)) != 0).collect();
+    Ok(UniverseBuilder::new().size(width, height).rule(rule).boundary(boundary).build_with(cells, seed))
+}
+
+/// `encode` as URL-safe base64.
+pub fn to_text(universe: &Universe) -> String {
+    URL_SAFE_NO_PAD.encode(encode(universe))
+}
+
+pub fn from_text(text: &str) -> Result<Universe, String> {
+    let _span = tracing::debug_span!("parse", format = "packed", bytes = text.len()).entered();
+    let bytes = URL_SAFE_NO_PAD.decode(text.trim()).map_err(|err| format!("bad packed board: {err}"));
+    bytes.and_then(|bytes| decode(&bytes)).inspect_err(|err| tracing::debug!("{err}"))
+}
+
+/// Eight cells per byte, low bit first.
+fn bit_pack(cells: &[bool]) -> Vec<u8> {
+    cells
+        .chunks(8)
+        .map(|byte| byte.iter().enumerate().fold(0, |acc, (bit, &alive)| acc | (alive as u8) << bit))
+        .collect()
+}
+
+fn pack_bits(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let run = bytes[i..].iter().take(128).take_while(|&&b| b == bytes[i]).count();
+        if run >= 2 {
+            out.push((257 - run) as u8);
+            out.push(bytes[i]);
+            i += run;
+            continue;
+        }
+        // Literals up to the next run of two or more.
+        let start = i;
+        while i < bytes.len() && i - start < 128 && !(i + 1 < bytes.len() && bytes[i] == bytes[i + 1]) {
+            i += 1;
+        }
+        out.push((i - start - 1) as u8);
+        out.extend(&bytes[start..i]);
+    }
+    out
+}
+
+/// Undo `pack_bits`, expecting exactly `len` bytes out.
+fn unpack_bits(mut packed: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    let truncated = || "packed board is truncated".to_string();
+    let mut out = Vec::with_capacity(len);
+    while out.len() < len {
+        let (&control, rest) = packed.split_first().ok_or_else(truncated)?;
+        match control {
+            0..=127 => {
+                let count = control as usize + 1;
+                out.extend(rest.get(..count).ok_or_else(truncated)?);
+                packed = &rest[count..];
+            }
+            128 => packed = rest,
+            _ => {
+                let (&byte, rest) = rest.split_first().ok_or_else(truncated)?;
+                out.extend(std::iter::repeat_n(byte, 257 - control as usize));
+                packed = rest;
+            }
+        }
+    }
+    if out.len() != len || !packed.is_empty() {
+        return Err("packed board has the wrong number of cells".to_string());
+    }
+    Ok(out)
+}
+
+/// Byte cursor over the header.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < count {
+            return Err("packed board is truncated".to_string());
+        }
+        let (head, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+}
+
+/// `#[serde(with = "packed::serde_text")]`: a `Universe` stored as its
+/// packed text rather than field by field.
+pub mod serde_text {
+    use super::{from_text, to_text};
+    use life_engine::universe::Universe;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(universe: &Universe, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_text(universe))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Universe, D::Error> {
+        from_text(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use life_engine::universe::UniverseBuilder;
+
+    #[test]
+    fn boards_round_trip() {
+        let boundaries = [Boundary::Torus, Boundary::Dead, Boundary::Klein, Boundary::CrossSurface, Boundary::Shifted(-3)];
+        for (boundary, density) in boundaries.into_iter().zip([0.0, 0.05, 0.5, 0.95, 1.0]) {
+            let rule = Rule::parse("B36/S23").unwrap();
+            let universe = UniverseBuilder::new().size(67, 13).rule(rule).boundary(boundary).seed(8).density(density).build();
+            assert_eq!(decode(&encode(&universe)), Ok(universe.clone()), "{boundary:?}");
+            assert_eq!(from_text(&to_text(&universe)), Ok(universe), "{boundary:?}");
+        }
+    }
+
+    #[test]
+    fn refuses_damaged_boards() {
+        let universe = UniverseBuilder::new().size(16, 16).seed(1).build();
+        let bytes = encode(&universe);
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode(b"nope").is_err());
+        let mut huge = bytes.clone();
+        huge[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decode(&huge).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+// PNG thumbnails
+//
+// Writes an engine `Thumbnail` as a PNG, and as a `data:` URL an `<img>` can
+// show straight from storage. The image is palette-based: sixteen steps from
+// the dead colour to the alive one, one byte per pixel. It is also left
+// uncompressed, in deflate's "stored" blocks; at thumbnail sizes that is a
+// couple of kilobytes, and saves carrying a compressor around. A mask is the
+// same image in white, with the shade as its opacity instead, for a CSS
+// `mask-image` over something coloured by the theme.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use life_engine::thumbnail::Thumbnail;
+
+const SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+/// Palette entries between (and including) dead and alive.
+const LEVELS: u32 = 16;
+/// Most bytes one stored deflate block can hold.
+const MAX_STORED: usize = 0xffff;
+
+/// `thumbnail` as PNG bytes, shaded from `dead` to `alive` (both `0xRRGGBB`).
+pub fn encode(thumbnail: &Thumbnail, dead: u32, alive: u32) -> Vec<u8> {
+    let palette: Vec<u8> = (0..LEVELS).flat_map(|level| blend(dead, alive, level)).collect();
+    encode_with(thumbnail, &palette, None)
+}
+
+/// `encode` as a `data:image/png;base64,…` URL.
+pub fn data_url(thumbnail: &Thumbnail, dead: u32, alive: u32) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(encode(thumbnail, dead, alive)))
+}
+
+/// `thumbnail` as a white PNG, transparent where dead and opaque where
+/// alive, as a `data:` URL.
+pub fn mask_url(thumbnail: &Thumbnail) -> String {
+    let palette = [0xff; 3 * LEVELS as usize];
+    let opacity: Vec<u8> = (0..LEVELS).map(|level| (level * 255 / (LEVELS - 1)) as u8).collect();
+    format!("data:image/png;base64,{}", STANDARD.encode(encode_with(thumbnail, &palette, Some(&opacity))))
+}
+
+/// The PNG of `thumbnail` with `LEVELS` palette entries in `palette`, and
+/// their opacities in `opacity` if it has any.
+fn encode_with(thumbnail: &Thumbnail, palette: &[u8], opacity: Option<&[u8]>) -> Vec<u8> {
+    let mut out = SIGNATURE.to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((thumbnail.width as u32).to_be_bytes());
+    header.extend((thumbnail.height as u32).to_be_bytes());
+    // 8-bit palette indices; default compression, filtering and no interlace.
+    header.extend([8, 3, 0, 0, 0]);
+    chunk(&mut out, b"IHDR", &header);
+
+    chunk(&mut out, b"PLTE", palette);
+    if let Some(opacity) = opacity {
+        chunk(&mut out, b"tRNS", opacity);
+    }
+
+    // Each scanline starts with its filter type, 0 for none.
+    let mut raw = Vec::with_capacity((thumbnail.width + 1) * thumbnail.height);
+    for row in thumbnail.shades.chunks(thumbnail.width.max(1)) {
+        raw.push(0);
+        raw.extend(row.iter().map(|&shade| ((shade as u32 * (LEVELS - 1) + 127) / 255) as u8));
+    }
+    chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+/// The colour `level` steps of `LEVELS - 1` from `dead` to `alive`, as RGB.
+fn blend(dead: u32, alive: u32, level: u32) -> [u8; 3] {
+    let channel = |shift: u32| {
+        let (from, to) = ((dead >> shift) & 0xff, (alive >> shift) & 0xff);
+        ((from * (LEVELS - 1 - level) + to * level) / (LEVELS - 1)) as u8
+    };
+    [channel(16), channel(8), channel(0)]
+}
+
+/// Append a chunk: length, type, data and the CRC of type and data.
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// A zlib stream of `data` in uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate, 32K window, no preset dictionary; the check bits make it a
+    // multiple of 31.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
@@ -0,0 +1,442 @@
+// Pattern file formats
+//
+// Run Length Encoded (RLE) is the lingua franca of Life pattern collections:
+//
+//   #N Glider
+//   x = 3, y = 3, rule = B3/S23
+//   bo$2bo$3o!
+//
+// `b` is a dead cell, `o` (or any other letter) a live one, `$` ends a row and
+// `!` ends the pattern; a number in front repeats the next token.
+//
+// Plaintext (`.cells`) is the ASCII-art format, handy for chat and forums:
+//
+//   !Name: Glider
+//   .O.
+//   ..O
+//   OOO
+//
+// Life 1.05 / 1.06 (`.lif`) are older formats: 1.06 lists live cells as
+// `x y` pairs, 1.05 has `#P x y` blocks of `.`/`*` rows.
+
+use life_engine::pattern::Pattern;
+use life_engine::rule::Rule;
+
+/// Reading and writing [`Pattern`]s in the text formats above.
+pub trait PatternText: Sized {
+    /// Parse an RLE file. The `x`/`y` header is optional: when missing the
+    /// size is taken from the body.
+    fn parse_rle(text: &str) -> Result<Self, String>;
+
+    /// Encode as RLE, lines wrapped at 70 characters as most tools expect.
+    fn to_rle(&self) -> String;
+
+    /// Parse plaintext rows. Besides `.`/`O`, `*`, `X` and `#` are read as
+    /// alive and spaces as dead, so most hand-typed art works; `!` lines are
+    /// comments (`!Name:` sets the name, `!Author:` the author).
+    fn parse_plaintext(text: &str) -> Result<Self, String>;
+
+    /// Parse Life 1.05 or 1.06, told apart by the `#Life` header line.
+    fn parse_life(text: &str) -> Result<Self, String>;
+
+    /// Encode as plaintext: `!Name:`, `!Author:` and comments if known,
+    /// then `.`/`O` rows.
+    fn to_plaintext(&self) -> String;
+}
+
+impl PatternText for Pattern {
+    fn parse_rle(text: &str) -> Result<Self, String> {
+        let mut name = None;
+        let mut author = None;
+        let mut comments = Vec::new();
+        let mut rule = None;
+        let mut declared = None;
+        let mut body = String::new();
+        for line in text.lines().map(str::trim) {
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(title) = comment.strip_prefix('N') {
+                    name = Some(title.trim().to_string());
+                } else if let Some(by) = comment.strip_prefix('O') {
+                    author = Some(by.trim().to_string());
+                } else if let Some(note) = comment.strip_prefix(['C', 'c']) {
+                    comments.push(note.trim().to_string());
+                }
+            } else if line.starts_with('x') {
+                let mut size = (0, 0);
+                for field in line.split(',') {
+                    let Some((key, value)) = field.split_once('=') else { continue };
+                    let value = value.trim();
+                    match key.trim() {
+                        "x" => size.0 = value.parse().map_err(|_| format!("bad width `{value}`"))?,
+                        "y" => size.1 = value.parse().map_err(|_| format!("bad height `{value}`"))?,
+                        "rule" => rule = Some(Rule::parse(value)?),
+                        _ => {}
+                    }
+                }
+                crate::check_size(size.0, size.1)?;
+                declared = Some(size);
+            } else {
+                body.push_str(line);
+            }
+        }
+
+        // Decode the body into rows of live columns first; the size may not be known yet.
+        let mut rows: Vec<Vec<usize>> = vec![Vec::new()];
+        let mut col = 0usize;
+        let mut width = 0;
+        let mut run = 0usize;
+        let mut live = 0;
+        for token in body.chars() {
+            if let Some(digit) = token.to_digit(10) {
+                run = run.saturating_mul(10).saturating_add(digit as usize);
+                continue;
+            }
+            let count = run.max(1);
+            run = 0;
+            // Runs are checked against the limits before they're expanded.
+            let (rows_after, cols_after) = match token {
+                '$' => (rows.len().saturating_add(count), 0),
+                '.' | 'b' | 'a'..='z' | 'A'..='Z' => (rows.len(), col.saturating_add(count)),
+                _ => (rows.len(), col),
+            };
+            if rows_after > crate::MAX_SIDE || cols_after > crate::MAX_SIDE || live > crate::MAX_CELLS {
+                return Err(format!("pattern is bigger than {0}×{0}", crate::MAX_SIDE));
+            }
+            match token {
+                '!' => break,
+                '$' => {
+                    rows.extend((0..count).map(|_| Vec::new()));
+                    col = 0;
+                }
+                'b' | '.' => col += count,
+                token if token.is_ascii_alphabetic() => {
+                    let row = rows.last_mut().expect("rows is never empty");
+                    row.extend(col..col + count);
+                    col += count;
+                    live += count;
+                }
+                token if token.is_whitespace() => {}
+                token => return Err(format!("unexpected `{token}` in pattern body")),
+            }
+            width = width.max(col);
+        }
+        // A trailing `$` before `!` doesn't add a row.
+        while rows.len() > 1 && rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let (width, height) = match declared {
+            Some((x, y)) => (x.max(width), y.max(rows.len())),
+            None => (width, rows.len()),
+        };
+        crate::check_size(width, height)?;
+        let mut cells = vec![false; width * height];
+        for (row, live) in rows.iter().enumerate() {
+            for &col in live {
+                cells[row * width + col] = true;
+            }
+        }
+        Ok(Self { name, author, comments, rule, ..Self::new(width, height, cells) })
+    }
+
+    fn to_rle(&self) -> String {
+        let mut out = String::new();
+        if let Some(name) = &self.name {
+            out.push_str(&format!("#N {name}\n"));
+        }
+        if let Some(author) = &self.author {
+            out.push_str(&format!("#O {author}\n"));
+        }
+        for comment in &self.comments {
+            out.push_str(&format!("#C {comment}\n"));
+        }
+        out.push_str(&format!("x = {}, y = {}", self.width, self.height));
+        if let Some(rule) = self.rule {
+            out.push_str(&format!(", rule = {rule}"));
+        }
+        out.push('\n');
+
+        let mut tokens = Vec::new();
+        let mut pending_rows = 0;
+        for row in self.cells.chunks(self.width.max(1)) {
+            // Trailing dead cells are implied by the `$`.
+            let end = row.iter().rposition(|&alive| alive).map_or(0, |last| last + 1);
+            if end > 0 && pending_rows > 0 {
+                tokens.push(run(pending_rows, '$'));
+                pending_rows = 0;
+            }
+            let mut col = 0;
+            while col < end {
+                let alive = row[col];
+                let length = row[col..end].iter().take_while(|&&cell| cell == alive).count();
+                tokens.push(run(length, if alive { 'o' } else { 'b' }));
+                col += length;
+            }
+            pending_rows += 1;
+        }
+        tokens.push("!".to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if line.len() + token.len() > 70 {
+                out.push_str(&line);
+                out.push('\n');
+                line.clear();
+            }
+            line.push_str(&token);
+        }
+        out.push_str(&line);
+        out.push('\n');
+        out
+    }
+
+    fn parse_plaintext(text: &str) -> Result<Self, String> {
+        let mut name = None;
+        let mut author = None;
+        let mut comments = Vec::new();
+        let mut rows = Vec::new();
+        for line in text.lines() {
+            if let Some(comment) = line.strip_prefix('!') {
+                if let Some(title) = comment.strip_prefix("Name:") {
+                    name = Some(title.trim().to_string());
+                } else if let Some(by) = comment.strip_prefix("Author:") {
+                    author = Some(by.trim().to_string());
+                } else if !comment.trim().is_empty() {
+                    comments.push(comment.trim().to_string());
+                }
+                continue;
+            }
+            let row = line
+                .trim_end()
+                .chars()
+                .map(|c| match c {
+                    'O' | 'o' | '*' | 'X' | 'x' | '#' => Ok(true),
+                    '.' | ' ' => Ok(false),
+                    c => Err(format!("unexpected `{c}` in plaintext pattern")),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+        // Blank lines around the art aren't rows of it.
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+        let leading = rows.iter().take_while(|row| row.is_empty()).count();
+        rows.drain(..leading);
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            cells.extend(row.iter().copied().chain(std::iter::repeat(false)).take(width));
+        }
+        Ok(Self { name, author, comments, ..Self::new(width, rows.len(), cells) })
+    }
+
+    fn parse_life(text: &str) -> Result<Self, String> {
+        let mut name = None;
+        let mut comments = Vec::new();
+        let mut rule = None;
+        let mut live: Vec<(i64, i64)> = Vec::new();
+        let mut block = None::<(i64, i64, i64)>; // 1.05 block: (x, y, rows so far)
+        let mut version_106 = false;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix("#Life") {
+                version_106 = header.trim() == "1.06";
+            } else if let Some(offset) = line.strip_prefix("#P") {
+                let coords = numbers(offset)?;
+                let [x, y] = coords[..] else { return Err(format!("bad block position `{line}`")) };
+                block = Some((x, y, 0));
+            } else if line == "#N" {
+                rule = Some(Rule::CONWAY);
+            } else if let Some(text) = line.strip_prefix("#R") {
+                rule = Some(Rule::parse(text)?);
+            } else if let Some(description) = line.strip_prefix("#D") {
+                // The first description line is taken as the name.
+                match name {
+                    None => name = Some(description.trim().to_string()),
+                    Some(_) => comments.push(description.trim().to_string()),
+                }
+            } else if line.starts_with('#') {
+                continue;
+            } else if version_106 {
+                let coords = numbers(line)?;
+                let [x, y] = coords[..] else { return Err(format!("bad cell `{line}`")) };
+                live.push((y, x));
+            } else {
+                let (x, y, row) = block.get_or_insert((0, 0, 0));
+                for (col, c) in line.chars().enumerate() {
+                    match c {
+                        '*' | 'O' => {
+                            let cell = y.checked_add(*row).zip(x.checked_add(col as i64));
+                            live.push(cell.ok_or("block is off the edge of the plane")?);
+                        }
+                        '.' => {}
+                        c => return Err(format!("unexpected `{c}` in Life 1.05 block")),
+                    }
+                }
+                *row += 1;
+            }
+        }
+
+        let top = live.iter().map(|&(r, _)| r).min().unwrap_or(0);
+        let left = live.iter().map(|&(_, c)| c).min().unwrap_or(0);
+        // Widened so cells at opposite ends of the i64 range can't overflow.
+        let extent = |span: i128| usize::try_from(span).unwrap_or(usize::MAX);
+        let height = extent(live.iter().map(|&(r, _)| r as i128 - top as i128 + 1).max().unwrap_or(0));
+        let width = extent(live.iter().map(|&(_, c)| c as i128 - left as i128 + 1).max().unwrap_or(0));
+        crate::check_size(width, height)?;
+        let mut cells = vec![false; width * height];
+        for (row, col) in live {
+            cells[(row - top) as usize * width + (col - left) as usize] = true;
+        }
+        Ok(Self { name, comments, rule, ..Self::new(width, height, cells) })
+    }
+
+    fn to_plaintext(&self) -> String {
+        let mut out = String::new();
+        if let Some(name) = &self.name {
+            out.push_str(&format!("!Name: {name}\n"));
+        }
+        if let Some(author) = &self.author {
+            out.push_str(&format!("!Author: {author}\n"));
+        }
+        for comment in &self.comments {
+            out.push_str(&format!("!{comment}\n"));
+        }
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(|&alive| if alive { 'O' } else { '.' }));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// `count` repeats of an RLE token, e.g. `3o`.
+fn run(count: usize, token: char) -> String {
+    if count == 1 { token.to_string() } else { format!("{count}{token}") }
+}
+
+/// Whitespace-separated integers.
+fn numbers(text: &str) -> Result<Vec<i64>, String> {
+    text.split_whitespace()
+        .map(|n| n.parse().map_err(|_| format!("`{n}` is not a number")))
+        .collect()
+}
+
+fn extension(name: &str) -> String {
+    name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default()
+}
+
+/// Whether [`parse_file`] knows the format of a file called `name`.
+pub fn is_pattern_file(name: &str) -> bool {
+    matches!(extension(name).as_str(), "rle" | "cells" | "lif" | "life")
+}
+
+/// Parse a pattern file, picking the format from the file name's extension.
+pub fn parse_file(name: &str, text: &str) -> Result<Pattern, String> {
+    let _span = tracing::debug_span!("parse", name, bytes = text.len()).entered();
+    let parsed = match extension(name).as_str() {
+        "rle" => Pattern::parse_rle(text),
+        "cells" => Pattern::parse_plaintext(text),
+        "lif" | "life" => Pattern::parse_life(text),
+        _ => Err(format!("don't know how to read `{name}`")),
+    };
+    let mut pattern = parsed.inspect_err(|err| tracing::debug!("not a pattern: {err}"))?;
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    pattern.name.get_or_insert_with(|| stem.to_string());
+    Ok(pattern)
+}
+
+/// Parse pasted text: RLE when it looks like RLE (a header or a `!`
+/// terminator), plaintext otherwise.
+pub fn parse_text(text: &str) -> Result<Pattern, String> {
+    let _span = tracing::debug_span!("parse", bytes = text.len()).entered();
+    let looks_like_rle = text.lines().any(|line| {
+        let line = line.trim();
+        line.starts_with("x ") || line.starts_with("x=") || (!line.starts_with('!') && line.ends_with('!'))
+    });
+    let parsed = if looks_like_rle { Pattern::parse_rle(text) } else { Pattern::parse_plaintext(text) };
+    parsed.inspect_err(|err| tracing::debug!(looks_like_rle, "not a pattern: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship.\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+
+    fn glider() -> Pattern {
+        Pattern::new(3, 3, [false, true, false, false, false, true, true, true, true].to_vec())
+    }
+
+    #[test]
+    fn rle_reads_cells_and_metadata() {
+        let pattern = Pattern::parse_rle(GLIDER).unwrap();
+        assert_eq!(pattern.cells, glider().cells);
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(pattern.comments, ["The smallest spaceship."]);
+        assert_eq!(pattern.rule, Some(Rule::CONWAY));
+    }
+
+    #[test]
+    fn rle_round_trips() {
+        let pattern = Pattern::parse_rle(GLIDER).unwrap();
+        assert_eq!(Pattern::parse_rle(&pattern.to_rle()), Ok(pattern));
+        // Long runs and blank rows, across line wraps.
+        let mut wide = Pattern::new(200, 5, vec![false; 1000]);
+        for col in (0..200).step_by(3) {
+            wide.cells[col] = true;
+            wide.cells[4 * 200 + 199 - col] = true;
+        }
+        assert_eq!(Pattern::parse_rle(&wide.to_rle()).map(|parsed| parsed.cells), Ok(wide.cells));
+    }
+
+    #[test]
+    fn rle_refuses_boards_past_the_limits() {
+        assert!(Pattern::parse_rle("x = 100000, y = 1\no!").is_err());
+        assert!(Pattern::parse_rle("x = 8192, y = 8192\no!").is_err());
+        assert!(Pattern::parse_rle("99999999999999999999999o!").is_err());
+        assert!(Pattern::parse_rle("9000$o!").is_err());
+    }
+
+    #[test]
+    fn plaintext_round_trips() {
+        let pattern = Pattern { name: Some("Glider".to_string()), ..glider() };
+        assert_eq!(Pattern::parse_plaintext(&pattern.to_plaintext()), Ok(pattern));
+    }
+
+    #[test]
+    fn life_106_matches_rle() {
+        let text = "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n";
+        assert_eq!(Pattern::parse_life(text).map(|pattern| pattern.cells), Ok(glider().cells));
+        // Negative coordinates only move the origin.
+        let shifted = "#Life 1.06\n-9 -5\n-8 -4\n-10 -3\n-9 -3\n-8 -3\n";
+        assert_eq!(Pattern::parse_life(shifted).map(|pattern| pattern.cells), Ok(glider().cells));
+    }
+
+    #[test]
+    fn life_105_matches_rle() {
+        let text = "#Life 1.05\n#D Glider\n#P -1 -1\n.*.\n..*\n***\n";
+        let pattern = Pattern::parse_life(text).unwrap();
+        assert_eq!(pattern.cells, glider().cells);
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    }
+
+    #[test]
+    fn life_refuses_spreads_past_the_limits() {
+        assert!(Pattern::parse_life("#Life 1.06\n0 0\n1000000 0\n").is_err());
+        assert!(Pattern::parse_life("#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n").is_err());
+    }
+
+    #[test]
+    fn files_are_read_by_extension() {
+        assert_eq!(parse_file("glider.rle", GLIDER).map(|pattern| pattern.cells), Ok(glider().cells));
+        assert_eq!(parse_file("glider.cells", ".O.\n..O\nOOO\n").map(|pattern| pattern.name), Ok(Some("glider".to_string())));
+        assert!(parse_file("glider.txt", GLIDER).is_err());
+        assert_eq!(parse_text("bo$2bo$3o!").map(|pattern| pattern.cells), Ok(glider().cells));
+    }
+}